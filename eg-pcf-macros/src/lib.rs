@@ -1,48 +1,312 @@
-use embedded_graphics::{prelude::*, primitives::Rectangle};
-use pcf_parser::{BoundingBox, Glyph, PcfFont};
+use pcf_parser::{BdfFont, Filter, Font, PcfFont, SubsetGlyph};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro_crate::{crate_name, FoundCrate};
 use quote::quote;
-use std::{fs, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
 use syn::{
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
-    Ident, LitChar, LitStr, Result, Token,
+    Error, Ident, LitChar, LitInt, LitStr, Result, Token,
 };
 
 struct IncludePcf {
-    filename: LitStr,
+    filenames: Vec<LitStr>,
     character_ranges: Option<CharacterRanges>,
+    chars: Option<LitStr>,
+    subset: Option<Subset>,
+    data_layout: DataLayout,
+    table_layout: TableLayout,
+    max_bytes: Option<LitInt>,
+    codepage: Option<Codepage>,
+    scale: Scale,
+    filter: Filter,
+    default: Option<LitChar>,
 }
 
 impl IncludePcf {
     fn contains(&self, c: char) -> bool {
-        self.character_ranges
+        if self
+            .default
             .as_ref()
-            .map(|ranges| ranges.contains(c))
-            .unwrap_or(true)
+            .is_some_and(|default| default.value() == c)
+        {
+            return true;
+        }
+
+        match (&self.character_ranges, &self.chars, &self.subset) {
+            (None, None, None) => true,
+            (ranges, chars, subset) => {
+                ranges.as_ref().is_some_and(|ranges| ranges.contains(c))
+                    || chars
+                        .as_ref()
+                        .is_some_and(|chars| chars.value().contains(c))
+                    || subset.as_ref().is_some_and(|subset| subset.contains(c))
+            }
+        }
     }
 }
 
 impl Parse for IncludePcf {
     fn parse(input: ParseStream) -> Result<Self> {
-        let filename = input.parse()?;
+        let mut filenames = vec![input.parse()?];
+        while input.peek(Token![,]) && input.peek2(LitStr) {
+            input.parse::<Token![,]>()?;
+            filenames.push(input.parse()?);
+        }
+        let mut character_ranges = None;
+        let mut chars = None;
+        let mut subset = None;
+        let mut data_layout = DataLayout::default();
+        let mut table_layout = TableLayout::default();
+        let mut max_bytes = None;
+        let mut codepage = None;
+        let mut scale = Scale::default();
+        let mut filter = Filter::default();
+        let mut default = None;
 
-        let character_ranges = if input.lookahead1().peek(Token![,]) {
-            Some(input.parse()?)
-        } else {
-            None
-        };
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            if input.peek(LitChar) {
+                character_ranges = Some(input.parse()?);
+                continue;
+            }
+
+            let option: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            match option.to_string().as_str() {
+                "chars" => chars = Some(input.parse()?),
+                "subset" => subset = Some(input.parse()?),
+                "data" => data_layout = input.parse()?,
+                "layout" => table_layout = input.parse()?,
+                "max_bytes" => max_bytes = Some(input.parse()?),
+                "codepage" => codepage = Some(input.parse()?),
+                "scale" => scale = input.parse()?,
+                "default" => default = Some(input.parse()?),
+                "filter" => {
+                    let name: Ident = input.parse()?;
+                    filter = match name.to_string().as_str() {
+                        "nearest" => Filter::Nearest,
+                        "scale_nx" => Filter::ScaleNx,
+                        _ => {
+                            return Err(Error::new(name.span(), "expected `nearest` or `scale_nx`"))
+                        }
+                    };
+                }
+                _ => {
+                    return Err(Error::new(
+                        option.span(),
+                        format!("unknown option `{option}`"),
+                    ))
+                }
+            }
+        }
 
         Ok(Self {
-            filename,
+            filenames,
             character_ranges,
+            chars,
+            subset,
+            data_layout,
+            table_layout,
+            max_bytes,
+            codepage,
+            scale,
+            filter,
+            default,
         })
     }
 }
 
+/// Element type of the emitted glyph data.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum DataLayout {
+    /// Bytes, with glyphs packed back to back.
+    #[default]
+    Bytes,
+    /// 32-bit words, with every glyph starting on a word boundary.
+    Words,
+}
+
+impl Parse for DataLayout {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ty: Ident = input.parse()?;
+
+        match ty.to_string().as_str() {
+            "u8" => Ok(Self::Bytes),
+            "u32" => Ok(Self::Words),
+            _ => Err(Error::new(ty.span(), "expected `u8` or `u32`")),
+        }
+    }
+}
+
+/// Type of the emitted font.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum TableLayout {
+    /// `PcfFont`, with an array of `PcfGlyph`s.
+    #[default]
+    Glyphs,
+    /// `raw::PcfFontRaw`, with the glyphs encoded as records of a byte table.
+    Raw,
+}
+
+impl Parse for TableLayout {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let layout: Ident = input.parse()?;
+
+        match layout.to_string().as_str() {
+            "glyphs" => Ok(Self::Glyphs),
+            "raw" => Ok(Self::Raw),
+            _ => Err(Error::new(layout.span(), "expected `glyphs` or `raw`")),
+        }
+    }
+}
+
+/// Horizontal and vertical scale factors, either `2` or `(2, 1)`.
+struct Scale {
+    x: u32,
+    y: u32,
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self { x: 1, y: 1 }
+    }
+}
+
+impl Parse for Scale {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let factor = |input: ParseStream| {
+            let factor: LitInt = input.parse()?;
+            match factor.base10_parse()? {
+                0 => Err(Error::new(factor.span(), "scale must be at least 1")),
+                factor => Ok(factor),
+            }
+        };
+
+        if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            let x = factor(&content)?;
+            content.parse::<Token![,]>()?;
+            let y = factor(&content)?;
+
+            Ok(Self { x, y })
+        } else {
+            let factor = factor(input)?;
+
+            Ok(Self {
+                x: factor,
+                y: factor,
+            })
+        }
+    }
+}
+
+/// Variant of `eg_pcf::codepage::Codepage` the font is encoded in.
+struct Codepage(Ident);
+
+impl Parse for Codepage {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let codepage: Ident = input.parse()?;
+
+        let variant = match codepage.to_string().as_str() {
+            "unicode" => "Unicode",
+            "latin1" => "Latin1",
+            "cp437" => "Cp437",
+            "koi8_r" => "Koi8R",
+            _ => {
+                return Err(Error::new(
+                    codepage.span(),
+                    "expected `unicode`, `latin1`, `cp437` or `koi8_r`",
+                ))
+            }
+        };
+
+        Ok(Self(Ident::new(variant, codepage.span())))
+    }
+}
+
+/// Printable ASCII, which all presets include.
+const ASCII: &[(char, char)] = &[(' ', '~')];
+
+/// The printable characters of ISO 8859-1.
+const LATIN1: &[(char, char)] = &[(' ', '~'), ('\u{A0}', '\u{FF}')];
+
+/// The printable characters of ISO 8859-15, which replaces 8 symbols of ISO 8859-1 with `€`
+/// and letters for French, Finnish and Estonian.
+const LATIN9: &[(char, char)] = &[
+    (' ', '~'),
+    ('\u{A0}', '\u{A3}'),
+    ('\u{20AC}', '\u{20AC}'),
+    ('\u{A5}', '\u{A5}'),
+    ('\u{160}', '\u{160}'),
+    ('\u{A7}', '\u{A7}'),
+    ('\u{161}', '\u{161}'),
+    ('\u{A9}', '\u{B3}'),
+    ('\u{17D}', '\u{17D}'),
+    ('\u{B5}', '\u{B7}'),
+    ('\u{17E}', '\u{17E}'),
+    ('\u{B9}', '\u{BB}'),
+    ('\u{152}', '\u{153}'),
+    ('\u{178}', '\u{178}'),
+    ('\u{BF}', '\u{FF}'),
+];
+
+/// Printable ASCII and the Cyrillic letters and `№` of ISO 8859-5.
+const CYRILLIC: &[(char, char)] = &[(' ', '~'), ('\u{400}', '\u{45F}'), ('\u{2116}', '\u{2116}')];
+
+/// Printable ASCII and the Greek letters, accents and punctuation of ISO 8859-7.
+const GREEK: &[(char, char)] = &[(' ', '~'), ('\u{384}', '\u{3CE}')];
+
+/// JIS X 0201, ASCII with `¥` and `‾` in place of `\` and `~` and half-width katakana.
+/// Both replaced characters are included.
+const JIS_X0201: &[(char, char)] = &[
+    (' ', '~'),
+    ('\u{A5}', '\u{A5}'),
+    ('\u{203E}', '\u{203E}'),
+    ('\u{FF61}', '\u{FF9F}'),
+];
+
+/// A `|` separated list of named character sets, e.g. `latin1 | greek`.
+struct Subset(Vec<&'static [(char, char)]>);
+
+impl Subset {
+    fn contains(&self, c: char) -> bool {
+        self.0
+            .iter()
+            .any(|ranges| ranges.iter().any(|(from, to)| (*from..=*to).contains(&c)))
+    }
+}
+
+impl Parse for Subset {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let names = Punctuated::<Ident, Token![|]>::parse_separated_nonempty(input)?;
+
+        names
+            .iter()
+            .map(|name| match name.to_string().as_str() {
+                "ascii" => Ok(ASCII),
+                "latin1" => Ok(LATIN1),
+                "latin9" => Ok(LATIN9),
+                "cyrillic" => Ok(CYRILLIC),
+                "greek" => Ok(GREEK),
+                "jis_x0201" => Ok(JIS_X0201),
+                _ => Err(Error::new(
+                    name.span(),
+                    "expected `ascii`, `latin1`, `latin9`, `cyrillic`, `greek` or `jis_x0201`",
+                )),
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+}
+
 struct CharacterRanges {
     ranges: Punctuated<CharacterRange, Token![|]>,
 }
@@ -61,8 +325,6 @@ impl CharacterRanges {
 
 impl Parse for CharacterRanges {
     fn parse(input: ParseStream) -> Result<Self> {
-        input.parse::<Token![,]>()?;
-
         Ok(Self {
             ranges: Punctuated::parse_separated_nonempty(input)?,
         })
@@ -96,24 +358,52 @@ impl Parse for CharacterRange {
     }
 }
 
-/// Converts a PCF bounding box into an embedded-graphics rectangle.
-fn bounding_box_to_rectangle(bounding_box: &BoundingBox) -> Rectangle {
-    Rectangle::new(
-        Point::new(
-            bounding_box.offset.x,
-            -bounding_box.offset.y - bounding_box.size.y - 1,
-        ),
-        // TODO: check for negative values
-        Size::new(bounding_box.size.x as u32, bounding_box.size.y as u32),
-    )
+/// What the expansion of a macro depends on besides its input.
+struct CallSite {
+    /// The directory paths are relative to.
+    manifest_dir: PathBuf,
+    /// The path to the `eg-pcf` crate.
+    eg_pcf: proc_macro2::TokenStream,
 }
 
-fn rectangle_constructor(rectangle: &Rectangle) -> proc_macro2::TokenStream {
-    let Rectangle {
-        top_left: Point { x, y },
-        size: Size { width, height },
-    } = rectangle;
+impl CallSite {
+    /// Returns the call site of the crate being compiled, from the environment.
+    fn from_env(span: Span) -> Result<Self> {
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .ok_or_else(|| Error::new(span, "CARGO_MANIFEST_DIR is not set"))?;
+
+        Ok(Self {
+            manifest_dir: manifest_dir.into(),
+            eg_pcf: eg_pcf_path(),
+        })
+    }
+
+    /// Returns the path of a file relative to the manifest directory.
+    fn path(&self, filename: &LitStr) -> PathBuf {
+        self.manifest_dir.join(filename.value())
+    }
+}
+
+/// Returns the path to the `eg-pcf` crate as seen from the macro call site.
+fn eg_pcf_path() -> proc_macro2::TokenStream {
+    let found_crate = crate_name("eg-pcf").expect("eg-pcf is present in `Cargo.toml`");
+
+    match found_crate {
+        // Examples and integration tests of eg-pcf see the crate as `Itself` too, but have to
+        // refer to it by name.
+        FoundCrate::Itself if std::env::var("CARGO_CRATE_NAME").as_deref() == Ok("eg_pcf") => {
+            quote!(crate)
+        }
+        FoundCrate::Itself => quote!(::eg_pcf),
+        FoundCrate::Name(name) => {
+            let ident = Ident::new(&name, Span::call_site());
+            quote!(#ident)
+        }
+    }
+}
 
+/// Returns a rectangle with the left, top, width and height of a subset bounding box.
+fn rectangle_constructor((x, y, width, height): (i32, i32, u32, u32)) -> proc_macro2::TokenStream {
     quote! {
         ::embedded_graphics::primitives::Rectangle::new(
             ::embedded_graphics::geometry::Point::new(#x, #y),
@@ -122,111 +412,617 @@ fn rectangle_constructor(rectangle: &Rectangle) -> proc_macro2::TokenStream {
     }
 }
 
-fn glyph_literal(glyph: &Glyph, start_index: usize) -> (Vec<bool>, proc_macro2::TokenStream) {
-    let character = LitChar::new(glyph.encoding.unwrap(), Span::call_site());
+/// Returns the record of a glyph in the glyph table of `eg_pcf::raw::PcfFontRaw`.
+fn glyph_record(glyph: &SubsetGlyph, start_index: usize) -> std::result::Result<[u8; 16], String> {
+    let character = glyph.character;
+    let too_large = || format!("metrics of {character:?} don't fit a raw glyph record");
 
-    let rectangle = bounding_box_to_rectangle(&glyph.bounding_box);
-    let bounding_box = rectangle_constructor(&rectangle);
+    let mut record = [0; 16];
+    record[0..4].copy_from_slice(&u32::from(character).to_le_bytes());
+    let start_index = u32::try_from(start_index).map_err(|_| too_large())?;
+    record[4..8].copy_from_slice(&start_index.to_le_bytes());
+    let left = i16::try_from(glyph.left).map_err(|_| too_large())?;
+    record[8..10].copy_from_slice(&left.to_le_bytes());
+    let top = i16::try_from(glyph.top).map_err(|_| too_large())?;
+    record[10..12].copy_from_slice(&top.to_le_bytes());
+    record[12] = u8::try_from(glyph.width).map_err(|_| too_large())?;
+    record[13] = u8::try_from(glyph.height).map_err(|_| too_large())?;
+    let device_width = i16::try_from(glyph.device_width).map_err(|_| too_large())?;
+    record[14..16].copy_from_slice(&device_width.to_le_bytes());
+
+    Ok(record)
+}
+
+fn glyph_literal(
+    glyph: &SubsetGlyph,
+    start_index: usize,
+    eg_pcf: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let character = LitChar::new(glyph.character, Span::call_site());
+    let bounding_box = rectangle_constructor((glyph.left, glyph.top, glyph.width, glyph.height));
 
     // TODO: handle height != 0
-    // TODO: check for negative values
-    let device_width = glyph.shift_x as u32;
+    let device_width = glyph.device_width;
 
-    let mut data = Vec::new();
+    quote! {
+        #eg_pcf::PcfGlyph {
+            character: #character,
+            bounding_box: #bounding_box,
+            device_width: #device_width,
+            start_index: #start_index,
+        }
+    }
+}
 
-    for y in 0..glyph.bounding_box.size.y as usize {
-        for x in 0..glyph.bounding_box.size.x as usize {
-            data.push(glyph.pixel(x, y))
+/// Glyph bitmaps of one or more fonts, packed into a single data blob.
+struct GlyphPool {
+    layout: DataLayout,
+    bits: Vec<bool>,
+    /// Start indices of the bitmaps in the pool, if identical bitmaps are stored once.
+    shared: Option<HashMap<Vec<bool>, usize>>,
+}
+
+impl GlyphPool {
+    fn new(layout: DataLayout, share: bool) -> Self {
+        Self {
+            layout,
+            bits: Vec::new(),
+            shared: share.then(HashMap::new),
         }
     }
 
-    let found_crate = crate_name("eg-pcf").expect("eg-pcf is present in `Cargo.toml`");
-    let pcf_glyph = match found_crate {
-        FoundCrate::Itself => quote!(crate::PcfGlyph),
-        FoundCrate::Name(name) => {
-            let ident = Ident::new(&name, Span::call_site());
-            quote!(#ident::PcfGlyph)
+    /// Adds a glyph bitmap to the pool and returns its start index.
+    fn push(&mut self, bitmap: Vec<bool>) -> usize {
+        if let Some(start) = self.shared.as_ref().and_then(|shared| shared.get(&bitmap)) {
+            return *start;
+        }
+
+        if self.layout == DataLayout::Words {
+            self.bits
+                .resize(self.bits.len().next_multiple_of(32), false);
         }
+        let start = self.bits.len();
+        self.bits.extend_from_slice(&bitmap);
+        if let Some(shared) = &mut self.shared {
+            shared.insert(bitmap, start);
+        }
+
+        start
+    }
+
+    /// Returns the `GlyphData` variant holding the pool and its size in bytes.
+    fn data(&self) -> (proc_macro2::TokenStream, usize) {
+        match self.layout {
+            DataLayout::Bytes => {
+                let bytes = bits_to_bytes(&self.bits);
+                (quote!(Slice(&[ #( #bytes ),* ])), bytes.len())
+            }
+            DataLayout::Words => {
+                let words = bits_to_words(&self.bits);
+                (quote!(Words(&[ #( #words ),* ])), words.len() * 4)
+            }
+        }
+    }
+}
+
+/// Includes a PCF font as an `eg_pcf::PcfFont`.
+///
+/// ```ignore
+/// const FONT: PcfFont = include_pcf!("font.pcf", 'A'..='Z' | ' ', data = u32);
+/// ```
+///
+/// The path is relative to `CARGO_MANIFEST_DIR`. Files with a `.bdf` extension are parsed
+/// as BDF fonts, and files with a `.gz` extension, like `font.pcf.gz`, are decompressed
+/// first. It can be followed by a `|` separated list of characters and character ranges to
+/// include, and by these options:
+///
+/// * `chars = "0123456789.:°C%"`: includes the characters of a string, e.g. the exact UI
+///   strings of an application, in addition to the character ranges.
+/// * `subset = latin1`: includes a named character set, in addition to the character
+///   ranges. `ascii` is printable ASCII, `latin1` and `latin9` are the printable characters
+///   of ISO 8859-1 and ISO 8859-15, `cyrillic` and `greek` are printable ASCII and the
+///   letters of ISO 8859-5 and ISO 8859-7, and `jis_x0201` is ASCII and half-width
+///   katakana. Sets can be combined, e.g. `subset = latin9 | cyrillic`.
+/// * `data = u8 | u32`: element type of the emitted glyph data. With `u32` the data is emitted
+///   as `GlyphData::Words` and every glyph starts on a 32-bit word boundary.
+/// * `max_bytes = 16384`: fails compilation if the glyph table and data take up more than
+///   the given number of bytes (see `PcfFont::total_bytes`).
+/// * `codepage = unicode | latin1 | cp437 | koi8_r`: encoding of the font, used to map
+///   Unicode text to glyphs. Character ranges refer to code points of the font encoding.
+/// * `scale = 2` or `scale = (2, 1)`: scales glyphs and metrics by an integer factor, or by
+///   separate horizontal and vertical factors, e.g. for double width text.
+/// * `filter = nearest | scale_nx`: algorithm used by `scale`. `scale_nx` smooths diagonal
+///   edges with Scale2x or Scale3x for uniform factors 2 and 3 and uses `nearest` otherwise.
+/// * `layout = glyphs | raw`: with `raw`, emits an `eg_pcf::raw::PcfFontRaw` whose glyphs
+///   are stored in a byte table with the layout documented in `eg_pcf::raw`, which requires
+///   the `raw` feature of `eg-pcf`. Raw fonts are Unicode fonts with byte data.
+/// * `default = '?'`: character drawn in place of characters the font has no glyph for. It
+///   is always included. Without it the font's default character is used if it's included,
+///   otherwise REPLACEMENT CHARACTER or space.
+#[proc_macro]
+pub fn include_pcf(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as IncludePcf);
+
+    CallSite::from_env(Span::call_site())
+        .and_then(|site| expand_pcf(input, &site))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_pcf(input: IncludePcf, site: &CallSite) -> Result<proc_macro2::TokenStream> {
+    let filename = match input.filenames.as_slice() {
+        [filename] => filename,
+        [_, second, ..] => {
+            return Err(Error::new(
+                second.span(),
+                "expected a single font, use `include_pcf_family!` for several",
+            ))
+        }
+        [] => unreachable!(),
     };
 
-    (
-        data,
+    let mut pool = GlyphPool::new(input.data_layout, false);
+    let font = expand_font(&input, filename, &mut pool, site)?;
+    let (data, data_bytes) = pool.data();
+    let size_check = size_check(
+        &input,
+        format!("font `{}`", filename.value()),
+        font.glyphs.len(),
+        data_bytes,
+        site,
+    );
+    let eg_pcf = &site.eg_pcf;
+    let FontTables {
+        glyphs,
+        records,
+        bounding_box,
+        replacement_character,
+        line_height,
+        fields,
+    } = font;
+
+    if input.table_layout == TableLayout::Raw {
+        let bytes = bits_to_bytes(&pool.bits);
+
+        return Ok(quote! {
+            {
+                #size_check
+
+                #eg_pcf::raw::PcfFontRaw::new(
+                    #bounding_box,
+                    #replacement_character,
+                    #line_height,
+                    &[ #( #records ),* ],
+                    &[ #( #bytes ),* ],
+                )
+            }
+        });
+    }
+
+    Ok(quote! {
+        {
+            #size_check
+
+            #eg_pcf::PcfFont {
+                bounding_box: #bounding_box,
+                glyphs: &[ #( #glyphs ),* ],
+                data: #eg_pcf::GlyphData::#data,
+                line_height: #line_height,
+                replacement_character: #replacement_character,
+                #fields
+            }
+        }
+    })
+}
+
+/// Includes several PCF fonts as an `eg_pcf::family::PcfFontFamily`, e.g. the weights of a
+/// UI font.
+///
+/// ```ignore
+/// const FAMILY: PcfFontFamily = include_pcf_family!("regular.pcf", "bold.pcf", chars = "0123456789");
+/// ```
+///
+/// Takes one or more paths followed by the character set and options of `include_pcf!`,
+/// which apply to every font. The fonts share a single data blob in which identical glyph
+/// bitmaps are stored once, and `max_bytes` limits the size of all fonts together.
+/// `layout = raw` isn't supported.
+#[proc_macro]
+pub fn include_pcf_family(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as IncludePcf);
+
+    CallSite::from_env(Span::call_site())
+        .and_then(|site| expand_pcf_family(input, &site))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_pcf_family(input: IncludePcf, site: &CallSite) -> Result<proc_macro2::TokenStream> {
+    if input.table_layout == TableLayout::Raw {
+        return Err(Error::new(
+            input.filenames[0].span(),
+            "`layout = raw` isn't supported for font families",
+        ));
+    }
+
+    let mut pool = GlyphPool::new(input.data_layout, true);
+    let fonts = input
+        .filenames
+        .iter()
+        .map(|filename| expand_font(&input, filename, &mut pool, site))
+        .collect::<Result<Vec<_>>>()?;
+    let (data, data_bytes) = pool.data();
+
+    let names = input
+        .filenames
+        .iter()
+        .map(|filename| format!("`{}`", filename.value()))
+        .collect::<Vec<_>>();
+    let size_check = size_check(
+        &input,
+        format!("font family {}", names.join(", ")),
+        fonts.iter().map(|font| font.glyphs.len()).sum(),
+        data_bytes,
+        site,
+    );
+    let eg_pcf = &site.eg_pcf;
+
+    let fonts = fonts.into_iter().map(|font| {
+        let FontTables {
+            glyphs,
+            bounding_box,
+            replacement_character,
+            line_height,
+            fields,
+            ..
+        } = font;
+
         quote! {
-            #pcf_glyph {
-                character: #character,
+            #eg_pcf::PcfFont {
                 bounding_box: #bounding_box,
-                device_width: #device_width,
-                start_index: #start_index,
+                glyphs: &[ #( #glyphs ),* ],
+                data: DATA,
+                line_height: #line_height,
+                replacement_character: #replacement_character,
+                #fields
             }
-        },
-    )
+        }
+    });
+
+    Ok(quote! {
+        {
+            #size_check
+
+            const DATA: #eg_pcf::GlyphData<'static> = #eg_pcf::GlyphData::#data;
+
+            #eg_pcf::family::PcfFontFamily::new(&[ #( #fonts ),* ])
+        }
+    })
 }
 
+/// Includes a PCF font as an `eg_pcf::PcfFont` with only the characters used in text
+/// files, e.g. the translations of an application.
+///
+/// ```ignore
+/// const FONT: PcfFont = include_pcf_for_strings!("font.pcf", "strings/de.txt", "strings/en.txt");
+/// ```
+///
+/// Takes the path of the font followed by the paths of one or more UTF-8 text files, all
+/// relative to `CARGO_MANIFEST_DIR`, and the options of `include_pcf!`. The characters of
+/// the files, except for control characters like line breaks, are included like the ones
+/// of `chars`. The crate is rebuilt when the text files change.
 #[proc_macro]
-pub fn include_pcf(input: TokenStream) -> TokenStream {
+pub fn include_pcf_for_strings(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as IncludePcf);
 
-    // TODO: handle errors
-    let mut path = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap());
-    path.push(&input.filename.value());
+    CallSite::from_env(Span::call_site())
+        .and_then(|site| expand_pcf_for_strings(input, &site))
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_pcf_for_strings(
+    mut input: IncludePcf,
+    site: &CallSite,
+) -> Result<proc_macro2::TokenStream> {
+    let (font, strings) = input.filenames.split_first().unwrap();
+    if strings.is_empty() {
+        return Err(Error::new(
+            font.span(),
+            "expected the paths of one or more text files after the font",
+        ));
+    }
+
+    let mut chars = input.chars.as_ref().map(LitStr::value).unwrap_or_default();
+    let mut paths = Vec::new();
+    for filename in strings {
+        let path = site.path(filename);
+        let text = fs::read_to_string(&path)
+            .map_err(|e| Error::new(filename.span(), format!("{}: {}", path.display(), e)))?;
+        chars.extend(text.chars().filter(|c| !c.is_control()));
+        paths.push(path.display().to_string());
+    }
+
+    input.chars = Some(LitStr::new(&chars, font.span()));
+    input.filenames.truncate(1);
+    let font = expand_pcf(input, site)?;
+
+    Ok(quote! {
+        {
+            // makes cargo rebuild the crate when the text files change
+            #( const _: &str = ::core::include_str!(#paths); )*
+
+            #font
+        }
+    })
+}
+
+/// A font subset by `expand_font`, whose glyph bitmaps were added to a `GlyphPool`.
+struct FontTables {
+    glyphs: Vec<proc_macro2::TokenStream>,
+    /// The glyph table of `layout = raw`.
+    records: Vec<u8>,
+    bounding_box: proc_macro2::TokenStream,
+    replacement_character: usize,
+    line_height: u32,
+    /// The remaining fields of `PcfFont`.
+    fields: proc_macro2::TokenStream,
+}
+
+/// Loads and subsets a font, adding its glyph bitmaps to `pool`.
+fn expand_font(
+    input: &IncludePcf,
+    filename: &LitStr,
+    pool: &mut GlyphPool,
+    site: &CallSite,
+) -> Result<FontTables> {
+    let path = site.path(filename);
+    let error = |message: String| {
+        let message = format!("{}: {}", path.display(), message);
+        Error::new(filename.span(), message)
+    };
+
+    let mut bytes = fs::read(&path).map_err(|e| error(e.to_string()))?;
+    let mut uncompressed_path = path.clone();
+    if has_extension(&path, "gz") {
+        bytes = pcf_parser::gunzip(&bytes).map_err(|e| error(e.to_string()))?;
+        uncompressed_path.set_extension("");
+    }
+    let font = load_font(&uncompressed_path, &bytes).map_err(error)?;
+
+    let default = input.default.as_ref().map(LitChar::value);
+    if let (Some(default), Some(literal)) = (default, &input.default) {
+        if !font.contains(default) {
+            return Err(Error::new(
+                literal.span(),
+                format!("{}: font has no glyph for {default:?}", path.display()),
+            ));
+        }
+    }
 
-    // TODO: handle errors
-    let pcf = fs::read(&path).unwrap();
+    let raw = input.table_layout == TableLayout::Raw;
+    if raw && (input.data_layout != DataLayout::Bytes || input.codepage.is_some()) {
+        return Err(error(
+            "`layout = raw` can't be combined with `data` or `codepage`".to_string(),
+        ));
+    }
 
-    let font = PcfFont::new(&pcf);
+    let scale = (input.scale.x, input.scale.y);
+    let subset = pcf_parser::Subset::scaled(
+        font.as_ref(),
+        |c| input.contains(c),
+        default,
+        scale,
+        input.filter,
+    )
+    .map_err(error)?;
 
-    let mut data = Vec::new();
     let mut glyphs = Vec::new();
-    let mut replacement_character = None;
+    let mut records = Vec::new();
+    for glyph in &subset.glyphs {
+        let start_index = pool.push(glyph.pixels().collect());
+        if raw {
+            records.extend(glyph_record(glyph, start_index).map_err(error)?);
+        }
+        glyphs.push(glyph_literal(glyph, start_index, &site.eg_pcf));
+    }
 
-    //TODO: sort glyphs to make it possible to use binary search
-    for glyph in font.glyphs.values() {
-        if let Some(c) = glyph.encoding {
-            if !input.contains(c) {
-                continue;
-            }
+    let bounding_box = rectangle_constructor(subset.bounding_box);
+    let eg_pcf = &site.eg_pcf;
 
-            if c == std::char::REPLACEMENT_CHARACTER
-                || (c == ' ' && replacement_character.is_none())
-            {
-                replacement_character = Some(glyphs.len());
-            }
+    let design_size = match subset.design_size {
+        Some((point_size, x, y)) => quote! {
+            Some(#eg_pcf::size::DesignSize {
+                point_size: #point_size,
+                resolution: ::embedded_graphics::geometry::Size::new(#x, #y),
+            })
+        },
+        None => quote!(None),
+    };
+
+    let codepage = match &input.codepage {
+        Some(Codepage(variant)) => quote!(#eg_pcf::codepage::Codepage::#variant),
+        None => quote!(#eg_pcf::codepage::Codepage::Unicode),
+    };
+
+    let option = |value: Option<u32>| match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    };
+    let pcf_parser::Subset {
+        superscript,
+        subscript,
+        ascent,
+        descent,
+        ..
+    } = subset;
+    let cap_height = option(subset.cap_height);
+    let x_height = option(subset.x_height);
+    let monospace_advance = match subset.monospace_advance {
+        Some(advance) => quote!(Some(#advance)),
+        None => quote!(None),
+    };
 
-            let (glyph_data, literal) = glyph_literal(glyph, data.len());
-            glyphs.push(literal);
-            data.extend_from_slice(&glyph_data);
+    Ok(FontTables {
+        glyphs,
+        records,
+        bounding_box,
+        replacement_character: subset.replacement_character,
+        // TODO: report error or calculate fallback value
+        line_height: subset.line_height,
+        fields: quote! {
+            design_size: #design_size,
+            script_offsets: #eg_pcf::script::ScriptOffsets {
+                superscript: #superscript,
+                subscript: #subscript,
+            },
+            metrics: #eg_pcf::metrics::FontMetrics {
+                ascent: #ascent,
+                descent: #descent,
+                cap_height: #cap_height,
+                x_height: #x_height,
+            },
+            monospace_advance: #monospace_advance,
+            codepage: #codepage,
+        },
+    })
+}
+
+/// Returns an assertion that the glyph tables and data of a font or family fit `max_bytes`.
+fn size_check(
+    input: &IncludePcf,
+    name: String,
+    glyph_count: usize,
+    data_bytes: usize,
+    site: &CallSite,
+) -> Option<proc_macro2::TokenStream> {
+    let eg_pcf = &site.eg_pcf;
+    let glyph_size = match input.table_layout {
+        TableLayout::Raw => quote!(#eg_pcf::raw::RECORD_SIZE),
+        TableLayout::Glyphs => quote!(::core::mem::size_of::<#eg_pcf::PcfGlyph>()),
+    };
+
+    input.max_bytes.as_ref().map(|max_bytes| {
+        let message = format!("{name} is larger than max_bytes = {max_bytes}");
+
+        quote! {
+            const _: () = ::core::assert!(
+                #glyph_count * #glyph_size + #data_bytes <= #max_bytes,
+                #message
+            );
         }
+    })
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+/// Parses a PCF font, or a BDF font if the path has a `.bdf` extension.
+fn load_font<'a>(path: &Path, bytes: &'a [u8]) -> std::result::Result<Box<dyn Font + 'a>, String> {
+    match has_extension(path, "bdf") {
+        true => BdfFont::parse(bytes)
+            .map(|font| Box::new(font) as Box<dyn Font>)
+            .map_err(|e| format!("invalid BDF font: {e}")),
+        false => PcfFont::parse(bytes)
+            .map(|font| Box::new(font) as Box<dyn Font>)
+            .map_err(|e| format!("invalid PCF font: {e}")),
     }
+}
 
-    let rectangle = bounding_box_to_rectangle(&font.bounding_box);
-    let bounding_box = rectangle_constructor(&rectangle);
-    // TODO: try to use DEFAULT_CHAR
-    let replacement_character = replacement_character.unwrap_or_default();
-    let data = bits_to_bytes(&data);
-    // TODO: report error or calculate fallback value
-    let line_height = font.bounding_box.size.y as u32;
-    let found_crate = crate_name("eg-pcf").expect("eg-pcf is present in `Cargo.toml`");
-    let pcf_font = match found_crate {
-        FoundCrate::Itself => quote!(crate::PcfFont),
-        FoundCrate::Name(name) => {
-            let ident = Ident::new(&name, Span::call_site());
-            quote!(#ident::PcfFont)
+/// Includes a kerning table as an `eg_pcf::kerning::KerningTable`.
+///
+/// ```ignore
+/// const KERNING: KerningTable = include_kerning!("kerning.txt");
+/// ```
+///
+/// The path is relative to `CARGO_MANIFEST_DIR`. Each line of the file contains a pair of
+/// characters followed by the adjustment in pixels, e.g. `AV -1`. Empty lines and lines
+/// starting with `#` are ignored.
+#[proc_macro]
+pub fn include_kerning(input: TokenStream) -> TokenStream {
+    let filename = parse_macro_input!(input as LitStr);
+
+    let site = match CallSite::from_env(filename.span()) {
+        Ok(site) => site,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let path = site.path(&filename);
+
+    let pairs = fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| parse_kerning(&text));
+
+    let pairs = match pairs {
+        Ok(pairs) => pairs,
+        Err(message) => {
+            let message = format!("{}: {}", path.display(), message);
+            return Error::new(filename.span(), message)
+                .to_compile_error()
+                .into();
         }
     };
 
-    let output = quote! {
-        #pcf_font {
-            bounding_box: #bounding_box,
-            glyphs: &[ #( #glyphs ),* ],
-            data: &[ #( #data ),* ],
-            line_height: #line_height,
-            replacement_character: #replacement_character,
+    let eg_pcf = &site.eg_pcf;
+    let pairs = pairs.iter().map(|(left, right, adjustment)| {
+        quote! {
+            #eg_pcf::kerning::KerningPair {
+                left: #left,
+                right: #right,
+                adjustment: #adjustment,
+            }
         }
+    });
+
+    let output = quote! {
+        #eg_pcf::kerning::KerningTable::new(&[ #( #pairs ),* ])
     };
 
     output.into()
 }
 
+/// Parses kerning pairs, returning them sorted by the pair of characters.
+fn parse_kerning(text: &str) -> std::result::Result<Vec<(char, char, i8)>, String> {
+    let mut pairs = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |message: &str| format!("line {}: {}", number + 1, message);
+
+        let mut fields = line.split_whitespace();
+        let mut chars = fields.next().unwrap_or_default().chars();
+        let (Some(left), Some(right), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(error("expected a pair of characters"));
+        };
+        let adjustment = fields
+            .next()
+            .and_then(|adjustment| adjustment.parse().ok())
+            .ok_or_else(|| error("expected an adjustment between -128 and 127"))?;
+
+        if fields.next().is_some() {
+            return Err(error("unexpected trailing characters"));
+        }
+
+        pairs.push((left, right, adjustment));
+    }
+
+    pairs.sort_by_key(|(left, right, _)| (*left, *right));
+
+    if let Some(pair) = pairs
+        .windows(2)
+        .find(|w| (w[0].0, w[0].1) == (w[1].0, w[1].1))
+    {
+        return Err(format!("duplicate pair `{}{}`", pair[0].0, pair[0].1));
+    }
+
+    Ok(pairs)
+}
+
 fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
     bits.chunks(8)
         .map(|bits| {
@@ -239,10 +1035,56 @@ fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
         .collect()
 }
 
+/// Packs bits MSB first into big endian ordered 32-bit words.
+fn bits_to_words(bits: &[bool]) -> Vec<u32> {
+    bits_to_bytes(bits)
+        .chunks(4)
+        .map(|bytes| {
+            bytes
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| u32::from(*byte) << (24 - 8 * i))
+                .sum()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn it_reports_invalid_fonts() {
+        let error = |path: &str, bytes: &[u8]| load_font(Path::new(path), bytes).err();
+
+        assert_eq!(
+            error("font.pcf", b"not a font").as_deref(),
+            Some("invalid PCF font: not a PCF font")
+        );
+        assert_eq!(
+            error("font.bdf", b"STARTCHAR a\nENCODING x\nENDCHAR").as_deref(),
+            Some("invalid BDF font: line 2: invalid number")
+        );
+    }
+
+    #[test]
+    fn it_expands_to_the_same_tokens_every_time() {
+        let site = CallSite {
+            manifest_dir: concat!(env!("CARGO_MANIFEST_DIR"), "/../eg-pcf").into(),
+            eg_pcf: quote!(::eg_pcf),
+        };
+        // every parse fills new hash maps, which iterate the glyphs in a different order
+        let expand = || {
+            let input = syn::parse_str(r#""examples/6x10.pcf", ' '..='~' | '\u{2588}'"#);
+            expand_pcf(input.unwrap(), &site).unwrap().to_string()
+        };
+
+        let expected = expand();
+        for _ in 0..4 {
+            assert_eq!(expand(), expected);
+        }
+    }
+
     #[test]
     fn test_bits_to_bytes() {
         let f = false;
@@ -263,4 +1105,39 @@ mod tests {
             vec![0x00, 0x80]
         );
     }
+
+    #[test]
+    fn test_bits_to_words() {
+        let f = false;
+        let t = true;
+
+        assert_eq!(bits_to_words(&[t, f, f, f, f, f, f, t]), vec![0x8100_0000]);
+        assert_eq!(
+            bits_to_words(&[[f; 32].as_slice(), &[f, t]].concat()),
+            vec![0, 0x4000_0000]
+        );
+    }
+
+    #[test]
+    fn test_parse_kerning() {
+        let pairs = parse_kerning("# comment\n\nVA -1\nAV  -2\n").unwrap();
+
+        assert_eq!(pairs, vec![('A', 'V', -2), ('V', 'A', -1)]);
+    }
+
+    #[test]
+    fn test_parse_kerning_errors() {
+        assert_eq!(
+            parse_kerning("A -1").unwrap_err(),
+            "line 1: expected a pair of characters"
+        );
+        assert_eq!(
+            parse_kerning("AV\n").unwrap_err(),
+            "line 1: expected an adjustment between -128 and 127"
+        );
+        assert_eq!(
+            parse_kerning("AV -1\nAV 1").unwrap_err(),
+            "duplicate pair `AV`"
+        );
+    }
 }
@@ -0,0 +1,115 @@
+use core::fmt;
+
+use embedded_graphics::prelude::*;
+
+use crate::{GlyphData, PcfGlyph};
+
+/// A glyph printed as rows of `#` for set and `·` for clear pixels, see
+/// [`PcfGlyph::ascii_art`].
+///
+/// A row of `-` marks the baseline below the pixel row that sits on it, and the last line
+/// marks the origin and the advance with `^`. Space around the bounding box that is needed
+/// to show the markers is left blank.
+#[derive(Debug, Clone, Copy)]
+pub struct AsciiArt<'a> {
+    glyph: &'a PcfGlyph,
+    data: &'a GlyphData<'a>,
+}
+
+impl PcfGlyph {
+    /// Returns a printable picture of the glyph, e.g. to debug rendering in tests.
+    pub fn ascii_art<'a>(&'a self, data: &'a GlyphData<'a>) -> AsciiArt<'a> {
+        AsciiArt { glyph: self, data }
+    }
+}
+
+impl fmt::Display for AsciiArt<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bounding_box = self.glyph.bounding_box;
+        let advance = self.glyph.device_width;
+        let bottom_right = bounding_box.top_left + bounding_box.size;
+        let left = bounding_box.top_left.x.min(0);
+        let right = bottom_right.x.max(advance + 1);
+
+        // rows above the origin have negative y coordinates
+        for y in bounding_box.top_left.y.min(-1)..bottom_right.y.max(0) {
+            if (bounding_box.top_left.y..bottom_right.y).contains(&y) {
+                for x in left..bottom_right.x {
+                    let p = Point::new(x, y);
+                    let symbol = match bounding_box.contains(p) {
+                        true if self.pixel(p) => '#',
+                        true => '·',
+                        false => ' ',
+                    };
+                    write!(f, "{}", symbol)?;
+                }
+            }
+            writeln!(f)?;
+
+            if y == -1 {
+                for _ in left..right {
+                    write!(f, "-")?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        for x in left..=advance.max(0) {
+            let symbol = if x == 0 || x == advance { '^' } else { ' ' };
+            write!(f, "{}", symbol)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AsciiArt<'_> {
+    fn pixel(&self, point: Point) -> bool {
+        let bounding_box = self.glyph.bounding_box;
+        let p = point - bounding_box.top_left;
+        let i = p.y as usize * bounding_box.size.width as usize + p.x as usize;
+
+        self.data.bit(self.glyph.start_index + i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::primitives::Rectangle;
+
+    #[test]
+    fn it_marks_the_baseline_and_bearings() {
+        let glyph = PcfGlyph::new(
+            'x',
+            Rectangle::new(Point::new(1, -2), Size::new(2, 3)),
+            4,
+            0,
+        );
+        let data = GlyphData::Slice(&[0b0110_1100]);
+
+        let mut text = [0; 32];
+        let mut cursor = Cursor(&mut text, 0);
+        fmt::write(&mut cursor, format_args!("{}", glyph.ascii_art(&data))).unwrap();
+        let len = cursor.1;
+
+        assert_eq!(
+            core::str::from_utf8(&text[..len]),
+            Ok(" ·#\n #·\n-----\n ##\n^   ^")
+        );
+    }
+
+    struct Cursor<'b>(&'b mut [u8], usize);
+
+    impl fmt::Write for Cursor<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.1 + s.len();
+            self.0
+                .get_mut(self.1..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.1 = end;
+            Ok(())
+        }
+    }
+}
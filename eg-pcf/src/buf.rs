@@ -0,0 +1,743 @@
+//! Fonts parsed at runtime, e.g. from an SD card, a flash file system or the network.
+//!
+//! `PcfFontBuf` decodes all glyphs up front and needs the `alloc` feature.
+//! [`LazyPcfFont`] decodes single glyphs when they are drawn through a
+//! [`GlyphCache`](crate::glyph_cache::GlyphCache) and doesn't allocate.
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::{
+    glyph_cache::{CacheError, GlyphSource},
+    PcfGlyph,
+};
+#[cfg(feature = "alloc")]
+use crate::{renderer::BitmapFontRenderer, GlyphData, PcfFont};
+
+const PCF_MAGIC: i32 = i32::from_le_bytes(*b"\x01fcp");
+
+const PCF_METRICS: i32 = 1 << 2;
+const PCF_BITMAPS: i32 = 1 << 3;
+const PCF_BDF_ENCODINGS: i32 = 1 << 5;
+
+const PCF_COMPRESSED_METRICS: i32 = 0x100;
+const PCF_GLYPH_PAD_MASK: i32 = 3;
+const PCF_BYTE_MASK: i32 = 1 << 2;
+const PCF_BIT_MASK: i32 = 1 << 3;
+const PCF_SCAN_UNIT_MASK: i32 = 3 << 4;
+
+/// An error returned by `PcfFontBuf::parse` and [`LazyPcfFont`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The data doesn't start with the PCF magic number.
+    BadMagic,
+    /// The metrics, bitmaps or encodings table is missing.
+    MissingTable,
+    /// A table extends past the end of the data.
+    Truncated,
+    /// The byte ranges of the encodings table are empty or exceed 0..=255.
+    InvalidEncoding,
+    /// The glyph bitmaps are larger than the bitmap data of the font.
+    BitmapsTooLarge,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a PCF font"),
+            Self::MissingTable => write!(f, "missing PCF table"),
+            Self::Truncated => write!(f, "truncated PCF font"),
+            Self::InvalidEncoding => write!(f, "invalid PCF encoding range"),
+            Self::BitmapsTooLarge => write!(f, "PCF glyph bitmaps exceed the bitmap data"),
+        }
+    }
+}
+
+/// A font parsed at runtime, e.g. from an SD card, a flash file system or the network.
+///
+/// The buffer owns the glyph table and bitmap data that [`include_pcf!`](crate::include_pcf)
+/// embeds in the binary. [`font`](Self::font) borrows them as a [`PcfFont`] for use with
+/// [`PcfTextStyle`](crate::text::PcfTextStyle).
+///
+/// Only the tables required for drawing are read, so fonts have no design size, script
+/// offsets or codepage.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PcfFontBuf {
+    bounding_box: Rectangle,
+    replacement_character: usize,
+    line_height: u32,
+    glyphs: Vec<PcfGlyph>,
+    data: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl PcfFontBuf {
+    /// Parses all glyphs of a PCF font.
+    pub fn parse(bytes: &[u8]) -> Result<Self, ParseError> {
+        let tables = Tables::read(bytes)?;
+
+        let mut font = Self {
+            bounding_box: Rectangle::zero(),
+            replacement_character: 0,
+            line_height: 0,
+            glyphs: Vec::new(),
+            data: Vec::new(),
+        };
+        let mut bits = 0;
+        let mut source_len = 0usize;
+        // code points sharing a glyph index share its bitmap data
+        let mut start_indices = BTreeMap::new();
+        let mut replacement_character = None;
+        let mut fallback_character = None;
+        let (mut min, mut max) = (
+            Point::new(i32::MAX, i32::MAX),
+            Point::new(i32::MIN, i32::MIN),
+        );
+
+        // code points are visited in ascending order, so the glyphs end up sorted
+        let (min_byte1, max_byte1) = tables.byte1;
+        let (min_byte2, max_byte2) = tables.byte2;
+        for code_point in (min_byte1..=max_byte1)
+            .flat_map(|byte1| (min_byte2..=max_byte2).map(move |byte2| byte1 << 8 | byte2))
+        {
+            let Some(character) = char::from_u32(code_point) else {
+                continue;
+            };
+            let Some(index) = tables.index(code_point)? else {
+                continue;
+            };
+
+            let metrics = tables.metrics(index)?;
+            let [left, right, _, ascent, descent] = metrics;
+            min = min.component_min(Point::new(left, -ascent));
+            max = max.component_max(Point::new(right, descent));
+            let mut glyph = glyph(character, metrics);
+
+            glyph.start_index = match start_indices.get(&index) {
+                Some(start_index) => *start_index,
+                None => {
+                    source_len = source_len.saturating_add(tables.bitmap_len(&glyph));
+                    if source_len > tables.available {
+                        return Err(ParseError::BitmapsTooLarge);
+                    }
+
+                    let start_index = bits;
+                    tables.decode(index, &glyph, |set| {
+                        if bits % 8 == 0 {
+                            font.data.push(0);
+                        }
+                        if set {
+                            *font.data.last_mut().unwrap() |= 0x80 >> (bits % 8);
+                        }
+                        bits += 1;
+                    })?;
+
+                    start_indices.insert(index, start_index);
+                    start_index
+                }
+            };
+
+            if code_point == u32::from(tables.default_char) {
+                replacement_character = Some(font.glyphs.len());
+            }
+            if character == char::REPLACEMENT_CHARACTER
+                || (character == ' ' && fallback_character.is_none())
+            {
+                fallback_character = Some(font.glyphs.len());
+            }
+
+            font.glyphs.push(glyph);
+        }
+
+        if !font.glyphs.is_empty() {
+            font.bounding_box =
+                Rectangle::with_corners(min - Point::new(0, 1), max - Point::new(1, 2));
+        }
+        font.replacement_character = replacement_character
+            .or(fallback_character)
+            .unwrap_or_default();
+        font.line_height = font.bounding_box.size.height;
+
+        Ok(font)
+    }
+
+    /// Returns the font for drawing text.
+    pub fn font(&self) -> PcfFont<'_> {
+        PcfFont::new(
+            self.bounding_box,
+            self.replacement_character,
+            self.line_height,
+            &self.glyphs,
+            GlyphData::Slice(&self.data),
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitmapFontRenderer for PcfFontBuf {
+    fn contains(&self, c: char) -> bool {
+        self.font().contains(c)
+    }
+
+    fn advance(&self, c: char) -> i32 {
+        self.font().advance(c)
+    }
+
+    fn line_height(&self) -> u32 {
+        self.line_height
+    }
+
+    fn glyph_bounding_box(&self, c: char) -> Rectangle {
+        self.font().glyph_bounding_box(c)
+    }
+
+    fn draw_glyph<D: DrawTarget>(
+        &self,
+        c: char,
+        position: Point,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<Point, D::Error> {
+        self.font().draw_glyph(c, position, color, target)
+    }
+}
+
+/// A font that decodes single glyphs from PCF data when they are looked up.
+///
+/// Parsing only reads the table layout, so fonts in memory mapped flash can be drawn without
+/// a heap or a copy of their bitmaps. Glyphs are drawn through a
+/// [`GlyphCache`](crate::glyph_cache::GlyphCache), e.g. with
+/// [`draw_string`](crate::glyph_cache::GlyphCache::draw_string).
+///
+/// Fonts have the glyphs of the PCF encodings table, like `PcfFontBuf`. Missing characters
+/// are drawn with the default character of the font, or REPLACEMENT CHARACTER or space if it
+/// has none.
+#[derive(Debug, Clone, Copy)]
+pub struct LazyPcfFont<'a> {
+    tables: Tables<'a>,
+    replacement_character: Option<char>,
+}
+
+impl<'a> LazyPcfFont<'a> {
+    /// Reads the table layout of a PCF font.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, ParseError> {
+        let tables = Tables::read(bytes)?;
+
+        let mut replacement_character = None;
+        let candidates = [u32::from(tables.default_char), 0xFFFD, u32::from(' ')];
+        for code_point in candidates {
+            if tables.index(code_point)?.is_some() {
+                replacement_character = char::from_u32(code_point);
+                break;
+            }
+        }
+
+        Ok(Self {
+            tables,
+            replacement_character,
+        })
+    }
+
+    /// Returns whether the font has a glyph for `c`.
+    pub fn contains(&self, c: char) -> Result<bool, ParseError> {
+        Ok(self.tables.index(c as u32)?.is_some())
+    }
+}
+
+impl GlyphSource for LazyPcfFont<'_> {
+    type Error = ParseError;
+
+    fn load_glyph(
+        &mut self,
+        c: char,
+        bitmap: &mut [u8],
+    ) -> Result<Option<PcfGlyph>, CacheError<Self::Error>> {
+        let tables = &self.tables;
+        let Some(index) = tables.index(c as u32).map_err(CacheError::Source)? else {
+            return Ok(None);
+        };
+        let glyph = glyph(c, tables.metrics(index).map_err(CacheError::Source)?);
+
+        let bits = (glyph.bounding_box.size.width * glyph.bounding_box.size.height) as usize;
+        let bytes = bits.div_ceil(8);
+        let bitmap = bitmap
+            .get_mut(..bytes)
+            .ok_or(CacheError::TooLarge { c, bytes })?;
+
+        bitmap.fill(0);
+        let mut i = 0;
+        tables
+            .decode(index, &glyph, |set| {
+                if set {
+                    bitmap[i / 8] |= 0x80 >> (i % 8);
+                }
+                i += 1;
+            })
+            .map_err(CacheError::Source)?;
+
+        Ok(Some(glyph))
+    }
+
+    fn replacement_character(&self) -> Option<char> {
+        self.replacement_character
+    }
+}
+
+/// Returns the glyph with the left and right side bearings, width, ascent and descent of
+/// `metrics` and a `start_index` of 0.
+fn glyph(character: char, [left, right, width, ascent, descent]: [i32; 5]) -> PcfGlyph {
+    let size = Size::new(
+        (right - left).max(0) as u32,
+        (ascent + descent).max(0) as u32,
+    );
+
+    PcfGlyph {
+        character,
+        bounding_box: Rectangle::new(Point::new(left, -ascent - 1), size),
+        device_width: width,
+        start_index: 0,
+    }
+}
+
+/// The tables required for drawing, with their layout read and validated.
+#[derive(Debug, Clone, Copy)]
+struct Tables<'a> {
+    pcf: Reader<'a>,
+    metrics: Table,
+    bitmaps: Table,
+    encodings: Table,
+    bitmap_data: usize,
+    /// The size of the bitmap data, which the decoded bitmaps never exceed.
+    available: usize,
+    pad: usize,
+    scan_unit: usize,
+    msb_first: bool,
+    byte1: (u32, u32),
+    byte2: (u32, u32),
+    default_char: u16,
+}
+
+impl<'a> Tables<'a> {
+    fn read(bytes: &'a [u8]) -> Result<Self, ParseError> {
+        let pcf = Reader { bytes };
+
+        if pcf.i32(0, false)? != PCF_MAGIC {
+            return Err(ParseError::BadMagic);
+        }
+
+        let metrics = pcf.table(PCF_METRICS)?;
+        let bitmaps = pcf.table(PCF_BITMAPS)?;
+        let encodings = pcf.table(PCF_BDF_ENCODINGS)?;
+
+        let bitmap_count = pcf.usize(bitmaps.offset + 4, bitmaps.big_endian)?;
+        // the glyph offsets are followed by the four bitmap sizes
+        let bitmap_data = offset(bitmaps.offset + 8, bitmap_count + 4, 4)?;
+        let bitmap_sizes = bitmap_data - 16;
+        let pad_index = (bitmaps.format & PCF_GLYPH_PAD_MASK) as usize;
+        let pad = 1 << pad_index;
+        let available = pcf
+            .usize(bitmap_sizes + 4 * pad_index, bitmaps.big_endian)?
+            .min(bytes.len().saturating_sub(bitmap_data));
+
+        let encoding = |i: usize| pcf.i16(encodings.offset + 4 + 2 * i, encodings.big_endian);
+        // both bytes of a code point must be in 0..=255 and the ranges must not be empty
+        let range = |i: usize| match (encoding(i)?, encoding(i + 1)?) {
+            (min, max) if 0 <= min && min <= max && max <= 0xFF => Ok((min as u32, max as u32)),
+            _ => Err(ParseError::InvalidEncoding),
+        };
+
+        Ok(Self {
+            pcf,
+            metrics,
+            bitmaps,
+            encodings,
+            bitmap_data,
+            available,
+            pad,
+            scan_unit: (1 << ((bitmaps.format & PCF_SCAN_UNIT_MASK) >> 4)).min(pad),
+            msb_first: bitmaps.format & PCF_BIT_MASK != 0,
+            byte2: range(0)?,
+            byte1: range(2)?,
+            default_char: pcf.u16(encodings.offset + 12, encodings.big_endian)?,
+        })
+    }
+
+    /// Returns the glyph index of a code point, or `None` if it isn't encoded. Two byte
+    /// encodings store the first byte in the high byte.
+    fn index(&self, code_point: u32) -> Result<Option<usize>, ParseError> {
+        let (byte1, byte2) = (code_point >> 8, code_point & 0xFF);
+        let ((min_byte1, max_byte1), (min_byte2, max_byte2)) = (self.byte1, self.byte2);
+        if !(min_byte1..=max_byte1).contains(&byte1) || !(min_byte2..=max_byte2).contains(&byte2) {
+            return Ok(None);
+        }
+
+        let i = ((byte1 - min_byte1) * (max_byte2 - min_byte2 + 1) + byte2 - min_byte2) as usize;
+        let encodings = &self.encodings;
+        let index = self
+            .pcf
+            .u16(encodings.offset + 14 + 2 * i, encodings.big_endian)?;
+
+        Ok((index != 0xFFFF).then_some(usize::from(index)))
+    }
+
+    fn metrics(&self, index: usize) -> Result<[i32; 5], ParseError> {
+        self.pcf.metrics(&self.metrics, index)
+    }
+
+    /// Returns the row stride of the bitmap of `glyph`.
+    fn stride(&self, glyph: &PcfGlyph) -> usize {
+        let pad = self.pad;
+        pad * (glyph.bounding_box.size.width as usize).div_ceil(8 * pad)
+    }
+
+    /// Returns the size of the bitmap of `glyph` in the font.
+    fn bitmap_len(&self, glyph: &PcfGlyph) -> usize {
+        self.stride(glyph)
+            .saturating_mul(glyph.bounding_box.size.height as usize)
+    }
+
+    /// Decodes the bitmap of the glyph at `index`, calling `pixel` for every pixel row by
+    /// row.
+    fn decode(
+        &self,
+        index: usize,
+        glyph: &PcfGlyph,
+        mut pixel: impl FnMut(bool),
+    ) -> Result<(), ParseError> {
+        let (pcf, bitmaps, scan_unit) = (&self.pcf, &self.bitmaps, self.scan_unit);
+        let size = glyph.bounding_box.size;
+        let stride = self.stride(glyph);
+        if self.bitmap_len(glyph) > self.available {
+            return Err(ParseError::BitmapsTooLarge);
+        }
+        let glyph_offset = pcf.usize(bitmaps.offset + 8 + 4 * index, bitmaps.big_endian)?;
+        let glyph_data = offset(self.bitmap_data, glyph_offset, 1)?;
+
+        for y in 0..size.height as usize {
+            let row = pcf.slice(offset(glyph_data, y, stride)?, stride)?;
+            for x in 0..size.width as usize {
+                let mut byte = x / 8;
+                if bitmaps.big_endian != self.msb_first {
+                    byte = byte - byte % scan_unit + scan_unit - 1 - byte % scan_unit;
+                }
+                let mask = match self.msb_first {
+                    true => 0x80 >> (x % 8),
+                    false => 1 << (x % 8),
+                };
+
+                pixel(row[byte] & mask != 0);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the offset of the `index`th entry of `size` bytes after `base`.
+///
+/// Offsets are read from the font, so the sum is checked to not overflow on 32 bit targets.
+fn offset(base: usize, index: usize, size: usize) -> Result<usize, ParseError> {
+    index
+        .checked_mul(size)
+        .and_then(|offset| offset.checked_add(base))
+        .ok_or(ParseError::Truncated)
+}
+
+/// A table of contents entry.
+#[derive(Debug, Clone, Copy)]
+struct Table {
+    format: i32,
+    big_endian: bool,
+    offset: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl fmt::Debug for Reader<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reader")
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
+
+impl<'a> Reader<'a> {
+    fn slice(&self, offset: usize, len: usize) -> Result<&'a [u8], ParseError> {
+        self.bytes
+            .get(offset..offset.checked_add(len).ok_or(ParseError::Truncated)?)
+            .ok_or(ParseError::Truncated)
+    }
+
+    fn array<const N: usize>(
+        &self,
+        offset: usize,
+        big_endian: bool,
+    ) -> Result<[u8; N], ParseError> {
+        let mut bytes: [u8; N] = self.slice(offset, N)?.try_into().unwrap();
+        if !big_endian {
+            bytes.reverse();
+        }
+
+        Ok(bytes)
+    }
+
+    fn i16(&self, offset: usize, big_endian: bool) -> Result<i32, ParseError> {
+        Ok(i16::from_be_bytes(self.array(offset, big_endian)?).into())
+    }
+
+    fn u16(&self, offset: usize, big_endian: bool) -> Result<u16, ParseError> {
+        Ok(u16::from_be_bytes(self.array(offset, big_endian)?))
+    }
+
+    fn i32(&self, offset: usize, big_endian: bool) -> Result<i32, ParseError> {
+        Ok(i32::from_be_bytes(self.array(offset, big_endian)?))
+    }
+
+    fn usize(&self, offset: usize, big_endian: bool) -> Result<usize, ParseError> {
+        self.i32(offset, big_endian)?
+            .try_into()
+            .map_err(|_| ParseError::Truncated)
+    }
+
+    fn table(&self, kind: i32) -> Result<Table, ParseError> {
+        let count = self.usize(4, false)?;
+
+        for i in 0..count {
+            let entry = offset(8, i, 16)?;
+            if self.i32(entry, false)? == kind {
+                let offset = self.usize(entry + 12, false)?;
+                // the format at the start of the table is always little endian
+                let format = self.i32(offset, false)?;
+
+                return Ok(Table {
+                    format,
+                    big_endian: format & PCF_BYTE_MASK != 0,
+                    offset,
+                });
+            }
+        }
+
+        Err(ParseError::MissingTable)
+    }
+
+    /// Returns the left and right side bearings, width, ascent and descent of a glyph.
+    fn metrics(&self, table: &Table, index: usize) -> Result<[i32; 5], ParseError> {
+        if table.format & PCF_COMPRESSED_METRICS != 0 {
+            let metrics = self.slice(offset(table.offset + 6, index, 5)?, 5)?;
+            Ok(core::array::from_fn(|i| i32::from(metrics[i]) - 0x80))
+        } else {
+            let entry = offset(table.offset + 8, index, 12)?;
+            let mut metrics = [0; 5];
+            for (i, value) in metrics.iter_mut().enumerate() {
+                *value = self.i16(entry + 2 * i, table.big_endian)?;
+            }
+
+            Ok(metrics)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, text::PcfTextStyle};
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        text::{renderer::TextRenderer, Baseline},
+    };
+
+    const FONT: PcfFont = include_pcf!("examples/OpenSans-Regular-12.pcf");
+    const BYTES: &[u8] = include_bytes!("../examples/OpenSans-Regular-12.pcf");
+
+    /// Returns the font with `value` written at `at` bytes into the table of `kind`.
+    fn patched<const N: usize>(kind: i32, at: usize, value: [u8; N]) -> Vec<u8> {
+        let mut bytes = BYTES.to_vec();
+        let table = Reader { bytes: BYTES }.table(kind).unwrap();
+        let mut value = value;
+        if !table.big_endian {
+            value.reverse();
+        }
+        bytes[table.offset + at..][..N].copy_from_slice(&value);
+
+        bytes
+    }
+
+    #[test]
+    fn it_parses_the_same_font_as_the_macro() {
+        let buf = PcfFontBuf::parse(include_bytes!("../examples/OpenSans-Regular-12.pcf")).unwrap();
+        let font = buf.font();
+
+        assert_eq!(font.glyphs, FONT.glyphs);
+        assert_eq!(font.data, FONT.data);
+        assert_eq!(font.bounding_box, FONT.bounding_box);
+        assert_eq!(font.replacement_character, FONT.replacement_character);
+        assert_eq!(font.line_height, FONT.line_height);
+
+        let mut display = MockDisplay::new();
+        PcfTextStyle::new(&font, BinaryColor::On)
+            .draw_string("Hi!", Point::new(0, 12), Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        PcfTextStyle::new(&FONT, BinaryColor::On)
+            .draw_string(
+                "Hi!",
+                Point::new(0, 12),
+                Baseline::Alphabetic,
+                &mut expected,
+            )
+            .unwrap();
+        display.assert_eq(&expected);
+
+        assert_eq!(buf.advance('H'), FONT.advance('H'));
+        assert_eq!(buf.glyph_bounding_box('i'), FONT.glyph_bounding_box('i'));
+    }
+
+    #[test]
+    fn it_parses_little_endian_fonts() {
+        let lsb = include_bytes!("../../assets/OpenSans-Regular-12-lsb.pcf");
+        let msb = include_bytes!("../examples/OpenSans-Regular-12.pcf");
+
+        assert_eq!(PcfFontBuf::parse(lsb), PcfFontBuf::parse(msb));
+
+        let lsbit = include_bytes!("../../assets/OpenSans-Regular-12-p2-u2-lsbit.pcf");
+        assert_eq!(PcfFontBuf::parse(lsbit), PcfFontBuf::parse(msb));
+    }
+
+    #[test]
+    fn it_parses_two_byte_encodings() {
+        let bytes = include_bytes!("../examples/OpenSans-Regular-12.pcf");
+        let mut pcf = pcf_parser::PcfFont::new(&bytes[..]);
+        let glyph = pcf.glyphs[&('A' as i32)].clone();
+        for c in ['\u{4E00}', '\u{9FA5}', '\u{FF21}'] {
+            let glyph = pcf_parser::Glyph {
+                code_point: c as i32,
+                encoding: Some(c),
+                ..glyph.clone()
+            };
+            pcf.glyphs.insert(c as i32, glyph);
+        }
+        let bytes = pcf_parser::PcfWriter::new(&pcf).to_bytes();
+
+        let buf = PcfFontBuf::parse(&bytes).unwrap();
+        let font = buf.font();
+        assert_eq!(font.glyphs.len(), FONT.glyphs.len() + 3);
+        assert!(font
+            .glyphs
+            .windows(2)
+            .all(|g| g[0].character < g[1].character));
+
+        let style = PcfTextStyle::new(&font, BinaryColor::On);
+        for text in ["\u{4E00}", "\u{9FA5}", "\u{FF21}"] {
+            let mut expected = MockDisplay::new();
+            style
+                .draw_string("A", Point::new(0, 20), Baseline::Alphabetic, &mut expected)
+                .unwrap();
+            let mut display = MockDisplay::new();
+            style
+                .draw_string(text, Point::new(0, 20), Baseline::Alphabetic, &mut display)
+                .unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn it_rejects_malformed_fonts() {
+        let bytes = include_bytes!("../examples/OpenSans-Regular-12.pcf");
+
+        assert_eq!(PcfFontBuf::parse(b"not a font"), Err(ParseError::BadMagic));
+        assert_eq!(
+            PcfFontBuf::parse(b"\x01fcp\0\0\0\0"),
+            Err(ParseError::MissingTable)
+        );
+        assert_eq!(
+            PcfFontBuf::parse(&bytes[..3000]),
+            Err(ParseError::Truncated)
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_encoding_ranges() {
+        // a second byte above 0xFF would overlap the first byte
+        let bytes = patched(PCF_BDF_ENCODINGS, 6, 0x100i16.to_be_bytes());
+        assert_eq!(PcfFontBuf::parse(&bytes), Err(ParseError::InvalidEncoding));
+
+        let bytes = patched(PCF_BDF_ENCODINGS, 4, (-1i16).to_be_bytes());
+        assert_eq!(PcfFontBuf::parse(&bytes), Err(ParseError::InvalidEncoding));
+
+        let mut bytes = patched(PCF_BDF_ENCODINGS, 8, 1i16.to_be_bytes());
+        let table = Reader { bytes: &bytes }.table(PCF_BDF_ENCODINGS).unwrap();
+        bytes[table.offset + 10..][..2].fill(0);
+        assert_eq!(PcfFontBuf::parse(&bytes), Err(ParseError::InvalidEncoding));
+    }
+
+    #[test]
+    fn it_bounds_the_decoded_bitmaps() {
+        let table = Reader { bytes: BYTES }.table(PCF_BITMAPS).unwrap();
+        let sizes = 8 + 4 * Reader { bytes: BYTES }
+            .usize(table.offset + 4, table.big_endian)
+            .unwrap();
+        let pad = (table.format & PCF_GLYPH_PAD_MASK) as usize;
+        let bytes = patched(PCF_BITMAPS, sizes + 4 * pad, 0i32.to_be_bytes());
+        assert_eq!(PcfFontBuf::parse(&bytes), Err(ParseError::BitmapsTooLarge));
+
+        // characters sharing a glyph decode it once
+        let pcf = Reader { bytes: BYTES };
+        let table = pcf.table(PCF_BDF_ENCODINGS).unwrap();
+        let encoding =
+            |i: usize| pcf.i16(table.offset + 4 + 2 * i, table.big_endian).unwrap() as usize;
+        assert_eq!((encoding(2), encoding(3)), (0, 0));
+        let index = 'W' as usize - encoding(0);
+        let w = pcf
+            .u16(table.offset + 14 + 2 * index, table.big_endian)
+            .unwrap();
+        let mut bytes = BYTES.to_vec();
+        let entries = &mut bytes[table.offset + 14..][..2 * (encoding(1) - encoding(0) + 1)];
+        for entry in entries.chunks_mut(2) {
+            if entry != [0xFF, 0xFF] {
+                entry.copy_from_slice(&w.to_be_bytes());
+            }
+        }
+
+        let buf = PcfFontBuf::parse(&bytes).unwrap();
+        let size = FONT.glyph_bounding_box('W').size;
+        assert_eq!(
+            buf.data.len(),
+            (size.width * size.height).div_ceil(8) as usize
+        );
+        assert!(buf.glyphs.iter().all(|glyph| glyph.start_index == 0));
+    }
+
+    #[test]
+    fn it_rejects_offsets_past_the_end() {
+        let bytes = patched(PCF_BITMAPS, 4, i32::MAX.to_be_bytes());
+        assert_eq!(PcfFontBuf::parse(&bytes), Err(ParseError::Truncated));
+
+        let bytes = patched(PCF_BITMAPS, 8, i32::MAX.to_be_bytes());
+        assert_eq!(PcfFontBuf::parse(&bytes), Err(ParseError::Truncated));
+
+        // the table of contents is always little endian
+        let mut bytes = BYTES.to_vec();
+        let entry = (0..)
+            .map(|i| 8 + 16 * i)
+            .find(|entry| bytes[*entry..][..4] == PCF_METRICS.to_le_bytes())
+            .unwrap();
+        bytes[entry + 12..][..4].copy_from_slice(&i32::MAX.to_le_bytes());
+        assert_eq!(PcfFontBuf::parse(&bytes), Err(ParseError::Truncated));
+
+        assert_eq!(offset(usize::MAX - 1, 1, 2), Err(ParseError::Truncated));
+        assert_eq!(
+            offset(usize::MAX / 2, 2, usize::MAX / 2),
+            Err(ParseError::Truncated)
+        );
+    }
+}
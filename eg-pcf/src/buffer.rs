@@ -0,0 +1,138 @@
+use core::convert::Infallible;
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::Rectangle,
+    text::{renderer::TextRenderer, Text},
+};
+
+/// An off-screen `W`x`H` pixel buffer for flicker-free text updates.
+///
+/// Text is drawn into the buffer first and then transferred to the display with a single
+/// [`fill_contiguous`](DrawTarget::fill_contiguous) call, so the erase-then-draw sequence of
+/// updating a value in place is never visible on slow displays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextBuffer<C, const W: usize, const H: usize> {
+    pixels: [[C; W]; H],
+    background: C,
+}
+
+impl<C: PixelColor, const W: usize, const H: usize> TextBuffer<C, W, H> {
+    pub fn new(background: C) -> Self {
+        Self {
+            pixels: [[background; W]; H],
+            background,
+        }
+    }
+
+    /// Returns the background color the buffer is cleared with.
+    pub fn background(&self) -> C {
+        self.background
+    }
+
+    /// Returns the color of the pixel at `point` or `None` if it is outside the buffer.
+    pub fn pixel(&self, point: Point) -> Option<C> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+
+        self.pixels.get(y)?.get(x).copied()
+    }
+
+    /// Copies the buffer to `target` with its top left corner at `position`.
+    pub fn blit<D>(&self, position: Point, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let area = Rectangle::new(position, self.size());
+
+        target.fill_contiguous(&area, self.pixels.iter().flatten().copied())
+    }
+
+    /// Redraws the buffer with `text` and copies it to `target`.
+    ///
+    /// `text_position` is relative to the buffer, `position` is where the buffer is placed
+    /// on the target.
+    pub fn update<S, D>(
+        &mut self,
+        text: &str,
+        text_position: Point,
+        style: S,
+        position: Point,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        S: TextRenderer<Color = C>,
+        D: DrawTarget<Color = C>,
+    {
+        self.clear(self.background).ok();
+        Text::new(text, text_position, style).draw(self).ok();
+
+        self.blit(position, target)
+    }
+}
+
+impl<C: PixelColor, const W: usize, const H: usize> OriginDimensions for TextBuffer<C, W, H> {
+    fn size(&self) -> Size {
+        Size::new(W as u32, H as u32)
+    }
+}
+
+impl<C: PixelColor, const W: usize, const H: usize> DrawTarget for TextBuffer<C, W, H> {
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let (Ok(x), Ok(y)) = (usize::try_from(point.x), usize::try_from(point.y)) else {
+                continue;
+            };
+
+            if let Some(pixel) = self.pixels.get_mut(y).and_then(|row| row.get_mut(x)) {
+                *pixel = color;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.pixels = [[color; W]; H];
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, text::PcfTextStyle, PcfFont};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+
+    #[test]
+    fn it_blits_the_whole_region() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut buffer = TextBuffer::<_, 12, 10>::new(BinaryColor::Off);
+        let mut display = MockDisplay::new();
+
+        buffer
+            .update(
+                "10",
+                Point::new(0, 9),
+                style,
+                Point::new(4, 4),
+                &mut display,
+            )
+            .unwrap();
+
+        assert_eq!(
+            display.affected_area(),
+            Rectangle::new(Point::new(4, 4), Size::new(12, 10))
+        );
+        assert_eq!(buffer.pixel(Point::new(12, 0)), None);
+    }
+}
@@ -0,0 +1,185 @@
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// Maps characters to the code points of a font encoding.
+///
+/// Implement this for proprietary or exotic encodings and use it with
+/// [`Codepage::Custom`]. Mappers are `Sync`, so fonts using them can be stored in `static`s.
+pub trait CodepageMapper: Sync {
+    /// Returns the code point of `c` in the font encoding, or `None` if the encoding has no
+    /// such character.
+    fn encode(&self, c: char) -> Option<u32>;
+}
+
+/// A legacy 8-bit character encoding of a font.
+///
+/// Many old bitmap fonts store their glyphs at the code points of an 8-bit codepage instead
+/// of Unicode. Setting [`PcfFont::codepage`](crate::PcfFont::codepage), e.g. with the
+/// `codepage` option of `include_pcf!`, maps Unicode text to the font's code points when
+/// glyphs are looked up, so `'█'` finds glyph `0xDB` of a CP437 font.
+///
+/// Fonts in other encodings can use a [`CodepageMapper`], e.g.
+/// `PcfFont { codepage: Codepage::Custom(&MAPPER), ..include_pcf!("font.pcf") }`.
+#[derive(Default, Clone, Copy)]
+pub enum Codepage {
+    /// The font is encoded in Unicode.
+    #[default]
+    Unicode,
+    /// ISO 8859-1, where code points 0 to 255 match Unicode.
+    Latin1,
+    /// The IBM PC character set, including the graphical symbols of the control characters.
+    Cp437,
+    /// The Russian KOI8-R encoding.
+    Koi8R,
+    /// An encoding defined by a user supplied mapper.
+    Custom(&'static dyn CodepageMapper),
+}
+
+impl Codepage {
+    /// Returns the code point of `c` in the font encoding, or `None` if the codepage has no
+    /// such character.
+    pub fn encode(self, c: char) -> Option<u32> {
+        let code_point = c as u32;
+
+        match self {
+            Self::Custom(mapper) => mapper.encode(c),
+            Self::Unicode => Some(code_point),
+            Self::Latin1 => (code_point <= 0xFF).then_some(code_point),
+            _ if c.is_ascii() => Some(code_point),
+            Self::Cp437 if c == '⌂' => Some(0x7F),
+            Self::Cp437 => {
+                position(&CP437_LOW, c).or_else(|| position(&CP437_HIGH, c).map(|i| i + 0x80))
+            }
+            Self::Koi8R => position(&KOI8_R_HIGH, c).map(|i| i + 0x80),
+        }
+    }
+}
+
+impl CodepageMapper for Codepage {
+    fn encode(&self, c: char) -> Option<u32> {
+        Codepage::encode(*self, c)
+    }
+}
+
+impl Codepage {
+    /// Returns a key for comparisons, with custom mappers compared by address.
+    fn key(&self) -> (u8, usize) {
+        match self {
+            Self::Unicode => (0, 0),
+            Self::Latin1 => (1, 0),
+            Self::Cp437 => (2, 0),
+            Self::Koi8R => (3, 0),
+            Self::Custom(mapper) => (
+                4,
+                *mapper as *const dyn CodepageMapper as *const () as usize,
+            ),
+        }
+    }
+}
+
+impl fmt::Debug for Codepage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unicode => f.write_str("Unicode"),
+            Self::Latin1 => f.write_str("Latin1"),
+            Self::Cp437 => f.write_str("Cp437"),
+            Self::Koi8R => f.write_str("Koi8R"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl PartialEq for Codepage {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Codepage {}
+
+impl PartialOrd for Codepage {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Codepage {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for Codepage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+fn position(table: &[char], c: char) -> Option<u32> {
+    table.iter().position(|t| *t == c).map(|i| i as u32)
+}
+
+/// The graphical symbols CP437 fonts have at the control characters.
+const CP437_LOW: [char; 32] = [
+    '\u{0}', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼', '►', '◄',
+    '↕', '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+
+/// Code points 0x80 to 0xFF of CP437.
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', 'É', 'æ', 'Æ',
+    'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', 'á', 'í', 'ó', 'ú', 'ñ', 'Ñ',
+    'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕',
+    '╣', '║', '╗', '╝', '╜', '╛', '┐', '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦',
+    '╠', '═', '╬', '╧', '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐',
+    '▀', 'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', '≡', '±',
+    '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{A0}',
+];
+
+/// Code points 0x80 to 0xFF of KOI8-R.
+const KOI8_R_HIGH: [char; 128] = [
+    '─', '│', '┌', '┐', '└', '┘', '├', '┤', '┬', '┴', '┼', '▀', '▄', '█', '▌', '▐', '░', '▒', '▓',
+    '⌠', '■', '∙', '√', '≈', '≤', '≥', '\u{A0}', '⌡', '°', '²', '·', '÷', '═', '║', '╒', 'ё', '╓',
+    '╔', '╕', '╖', '╗', '╘', '╙', '╚', '╛', '╜', '╝', '╞', '╟', '╠', '╡', 'Ё', '╢', '╣', '╤', '╥',
+    '╦', '╧', '╨', '╩', '╪', '╫', '╬', '©', 'ю', 'а', 'б', 'ц', 'д', 'е', 'ф', 'г', 'х', 'и', 'й',
+    'к', 'л', 'м', 'н', 'о', 'п', 'я', 'р', 'с', 'т', 'у', 'ж', 'в', 'ь', 'ы', 'з', 'ш', 'э', 'щ',
+    'ч', 'ъ', 'Ю', 'А', 'Б', 'Ц', 'Д', 'Е', 'Ф', 'Г', 'Х', 'И', 'Й', 'К', 'Л', 'М', 'Н', 'О', 'П',
+    'Я', 'Р', 'С', 'Т', 'У', 'Ж', 'В', 'Ь', 'Ы', 'З', 'Ш', 'Э', 'Щ', 'Ч', 'Ъ',
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_legacy_codepages() {
+        assert_eq!(Codepage::Cp437.encode('█'), Some(0xDB));
+        assert_eq!(Codepage::Cp437.encode('☺'), Some(0x01));
+        assert_eq!(Codepage::Cp437.encode('A'), Some(0x41));
+        assert_eq!(Codepage::Cp437.encode('€'), None);
+        assert_eq!(Codepage::Koi8R.encode('Ж'), Some(0xF6));
+        assert_eq!(Codepage::Latin1.encode('é'), Some(0xE9));
+        assert_eq!(Codepage::Latin1.encode('█'), None);
+    }
+
+    struct Rot1;
+
+    impl CodepageMapper for Rot1 {
+        fn encode(&self, c: char) -> Option<u32> {
+            Some(c as u32 + 1)
+        }
+    }
+
+    #[test]
+    fn it_uses_custom_mappers() {
+        static ROT1: Rot1 = Rot1;
+        let codepage = Codepage::Custom(&ROT1);
+
+        assert_eq!(codepage.encode('A'), Some(0x42));
+        assert_eq!(codepage, Codepage::Custom(&ROT1));
+        assert_ne!(codepage, Codepage::Unicode);
+    }
+}
@@ -0,0 +1,482 @@
+use core::fmt;
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::Rectangle,
+    text::{renderer::TextRenderer, Baseline},
+};
+
+use crate::{
+    shaping::{PassThrough, Shaper},
+    text::PcfTextStyle,
+};
+
+/// A text console that draws formatted text into a [`DrawTarget`].
+///
+/// The console implements [`fmt::Write`], so `write!` can be used to print to a display. Text
+/// is wrapped at the console width and the console scrolls up by one line when the cursor
+/// moves past the last of its `LINES` lines. Because most displays can't be read back, the
+/// visible text is retained (up to `COLUMNS` bytes per line) and redrawn when scrolling.
+///
+/// The characters of each written string are drawn together up to the end of a line, so
+/// kerning, ligatures and the other features of the style apply between them. Separate
+/// writes, like the pieces of a `write!` format string, are drawn separately. Characters
+/// whose UTF-8 encoding is longer than `COLUMNS` bytes are written as `?`.
+///
+/// Lines that scroll off the top are kept in a ring buffer of the last `HISTORY` lines, none
+/// by default, and the viewport can be scrolled back to them with [`scroll_up`](Self::scroll_up).
+#[derive(Debug)]
+pub struct TextConsole<
+    'a,
+    D: DrawTarget,
+    const LINES: usize,
+    const COLUMNS: usize,
+    S = PassThrough,
+    const HISTORY: usize = 0,
+> {
+    target: D,
+    style: PcfTextStyle<'a, D::Color, S>,
+    background: D::Color,
+    position: Point,
+    width: u32,
+    lines: [[u8; COLUMNS]; LINES],
+    lengths: [usize; LINES],
+    row: usize,
+    x: i32,
+    history: [[u8; COLUMNS]; HISTORY],
+    history_lengths: [usize; HISTORY],
+    /// The index the next line is added to the history at.
+    history_next: usize,
+    history_count: usize,
+    /// The number of lines the viewport is scrolled back by.
+    offset: usize,
+}
+
+impl<'a, D, const LINES: usize, const COLUMNS: usize, S, const HISTORY: usize>
+    TextConsole<'a, D, LINES, COLUMNS, S, HISTORY>
+where
+    D: DrawTarget,
+    S: Shaper,
+{
+    /// Creates a console whose top left corner is at `position` and that is `width` pixels
+    /// wide and `LINES` lines high.
+    pub fn new(
+        target: D,
+        style: PcfTextStyle<'a, D::Color, S>,
+        background: D::Color,
+        position: Point,
+        width: u32,
+    ) -> Self {
+        const { assert!(LINES > 0, "a console needs at least one line") };
+
+        Self {
+            target,
+            style,
+            background,
+            position,
+            width,
+            lines: [[0; COLUMNS]; LINES],
+            lengths: [0; LINES],
+            row: 0,
+            x: 0,
+            history: [[0; COLUMNS]; HISTORY],
+            history_lengths: [0; HISTORY],
+            history_next: 0,
+            history_count: 0,
+            offset: 0,
+        }
+    }
+
+    /// Returns the area covered by the console.
+    pub fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(
+            self.position,
+            Size::new(self.width, self.style.line_height() * LINES as u32),
+        )
+    }
+
+    /// Returns the retained text of a visible line, which is a line of the history if the
+    /// viewport is scrolled back.
+    pub fn line(&self, index: usize) -> &str {
+        let (line, length) = self.visible_line(index);
+
+        core::str::from_utf8(&line[..length]).unwrap_or_default()
+    }
+
+    /// Returns the number of lines the viewport is scrolled back by.
+    pub fn scroll_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Scrolls the viewport back by up to `lines` lines of the history and redraws it.
+    pub fn scroll_up(&mut self, lines: usize) -> Result<(), D::Error> {
+        self.scroll_to(self.offset.saturating_add(lines))
+    }
+
+    /// Scrolls the viewport forward by up to `lines` lines and redraws it.
+    pub fn scroll_down(&mut self, lines: usize) -> Result<(), D::Error> {
+        self.scroll_to(self.offset.saturating_sub(lines))
+    }
+
+    /// Scrolls the viewport back to the cursor. Writing text does this too.
+    pub fn scroll_to_bottom(&mut self) -> Result<(), D::Error> {
+        self.scroll_to(0)
+    }
+
+    /// Clears the console and its history and moves the cursor to the first line.
+    pub fn clear(&mut self) -> Result<(), D::Error> {
+        self.lengths = [0; LINES];
+        self.row = 0;
+        self.x = 0;
+        self.history_count = 0;
+        self.offset = 0;
+
+        self.target
+            .fill_solid(&self.bounding_box(), self.background)
+    }
+
+    /// Returns a reference to the wrapped draw target.
+    pub fn target(&self) -> &D {
+        &self.target
+    }
+
+    /// Returns a mutable reference to the wrapped draw target.
+    pub fn target_mut(&mut self) -> &mut D {
+        &mut self.target
+    }
+
+    /// Consumes the console and returns the wrapped draw target.
+    pub fn into_inner(self) -> D {
+        self.target
+    }
+
+    fn baseline(&self, row: usize) -> Point {
+        let ascent = -self.style.font_box().top_left.y;
+
+        self.position + Point::new(0, row as i32 * self.style.line_height() as i32 + ascent)
+    }
+
+    fn scroll_to(&mut self, offset: usize) -> Result<(), D::Error> {
+        let offset = offset.min(self.history_count);
+        if offset == self.offset {
+            return Ok(());
+        }
+
+        self.offset = offset;
+        self.redraw()
+    }
+
+    /// Returns the buffer and length of the line shown in row `index` of the viewport.
+    fn visible_line(&self, index: usize) -> (&[u8; COLUMNS], usize) {
+        match index.checked_sub(self.offset) {
+            Some(index) => (&self.lines[index], self.lengths[index]),
+            None => {
+                let index = self.history_index(self.history_count - self.offset + index);
+                (&self.history[index], self.history_lengths[index])
+            }
+        }
+    }
+
+    /// Returns the index in the ring buffer of the `index`th oldest line of the history.
+    fn history_index(&self, index: usize) -> usize {
+        (self.history_next + HISTORY - self.history_count + index) % HISTORY
+    }
+
+    fn write_text(&mut self, text: &str) -> Result<(), D::Error> {
+        self.scroll_to_bottom()?;
+
+        // the retained text of the current line from `start` on, which is drawn at `start_x`,
+        // its width and the start of its last character
+        let mut start = self.lengths[self.row];
+        let mut start_x = self.x;
+        let mut run_width = 0;
+        let mut previous = None;
+
+        for c in text.chars() {
+            if c == '\n' {
+                self.draw_run(start, start_x)?;
+                self.new_line()?;
+                (start, start_x, run_width, previous) = (0, 0, 0, None);
+                continue;
+            }
+
+            // a character longer than a line would move the cursor to a new line forever
+            let c = match c.len_utf8() <= COLUMNS {
+                true => c,
+                false => '?',
+            };
+            if c.len_utf8() > COLUMNS {
+                continue;
+            }
+
+            let mut buffer = [0; 4];
+            let c = c.encode_utf8(&mut buffer);
+            let index = self.lengths[self.row];
+            if self.push(c) {
+                // measuring the character after the previous one includes their kerning
+                let pair = previous.unwrap_or(index);
+                let added = self.measure(pair, index + c.len()) - self.measure(pair, index);
+                let x = start_x + run_width + added;
+                if self.x == 0 || x <= self.width as i32 {
+                    self.x = x;
+                    run_width += added;
+                    previous = Some(index);
+                    continue;
+                }
+                self.lengths[self.row] -= c.len();
+            }
+
+            self.draw_run(start, start_x)?;
+            self.new_line()?;
+            (start, start_x, previous) = (0, 0, Some(0));
+            // an empty line always fits the character
+            self.push(c);
+            run_width = self.measure(0, c.len());
+            self.x = run_width;
+        }
+
+        self.draw_run(start, start_x)
+    }
+
+    /// Appends `c` to the current line and returns `false` if the line is full.
+    fn push(&mut self, c: &str) -> bool {
+        let length = self.lengths[self.row];
+        match self.lines[self.row].get_mut(length..length + c.len()) {
+            Some(line) => {
+                line.copy_from_slice(c.as_bytes());
+                self.lengths[self.row] += c.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the width of the bytes `start..end` of the current line.
+    fn measure(&self, start: usize, end: usize) -> i32 {
+        let text = core::str::from_utf8(&self.lines[self.row][start..end]).unwrap_or_default();
+
+        self.style
+            .measure_string(text, Point::zero(), Baseline::Alphabetic)
+            .next_position
+            .x
+    }
+
+    fn draw_run(&mut self, start: usize, x: i32) -> Result<(), D::Error> {
+        if start == self.lengths[self.row] {
+            return Ok(());
+        }
+
+        let position = self.baseline(self.row) + Point::new(x, 0);
+        let line = self.lines[self.row];
+        let run = core::str::from_utf8(&line[start..self.lengths[self.row]]).unwrap_or_default();
+        self.style
+            .draw_string(run, position, Baseline::Alphabetic, &mut self.target)?;
+
+        Ok(())
+    }
+
+    fn new_line(&mut self) -> Result<(), D::Error> {
+        self.x = 0;
+
+        if self.row + 1 < LINES {
+            self.row += 1;
+            return Ok(());
+        }
+
+        self.scroll()
+    }
+
+    fn scroll(&mut self) -> Result<(), D::Error> {
+        if HISTORY > 0 {
+            self.history[self.history_next] = self.lines[0];
+            self.history_lengths[self.history_next] = self.lengths[0];
+            self.history_next = (self.history_next + 1) % HISTORY;
+            self.history_count = (self.history_count + 1).min(HISTORY);
+        }
+
+        self.lines.rotate_left(1);
+        self.lengths.rotate_left(1);
+        self.lengths[LINES - 1] = 0;
+
+        self.redraw()
+    }
+
+    fn redraw(&mut self) -> Result<(), D::Error> {
+        self.target
+            .fill_solid(&self.bounding_box(), self.background)?;
+
+        for row in 0..LINES {
+            let (line, length) = self.visible_line(row);
+            let line = *line;
+            let line = core::str::from_utf8(&line[..length]).unwrap_or_default();
+            if line.is_empty() {
+                continue;
+            }
+
+            self.style.draw_string(
+                line,
+                self.baseline(row),
+                Baseline::Alphabetic,
+                &mut self.target,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<D, const LINES: usize, const COLUMNS: usize, S, const HISTORY: usize> fmt::Write
+    for TextConsole<'_, D, LINES, COLUMNS, S, HISTORY>
+where
+    D: DrawTarget,
+    S: Shaper,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_text(s).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        include_pcf,
+        kerning::{KerningPair, KerningTable},
+        PcfFont,
+    };
+    use core::fmt::Write;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+
+    fn console(width: u32) -> TextConsole<'static, MockDisplay<BinaryColor>, 2, 16> {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+
+        TextConsole::new(display, style, BinaryColor::Off, Point::zero(), width)
+    }
+
+    #[test]
+    fn it_wraps_at_the_console_width() {
+        let mut console = console(30);
+
+        write!(console, "{}", 1234567).unwrap();
+
+        assert_eq!(console.line(0), "12345");
+        assert_eq!(console.line(1), "67");
+    }
+
+    #[test]
+    fn it_scrolls_past_the_last_line() {
+        let mut console = console(60);
+
+        write!(console, "a\nb\nc = {}", 3).unwrap();
+
+        assert_eq!(console.line(0), "b");
+        assert_eq!(console.line(1), "c = 3");
+    }
+
+    #[test]
+    fn it_writes_characters_longer_than_a_line_as_question_marks() {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut console: TextConsole<_, 2, 2> =
+            TextConsole::new(display, style, BinaryColor::Off, Point::zero(), 60);
+
+        console.write_str("a\u{E9}\u{20AC}b").unwrap();
+
+        assert_eq!([console.line(0), console.line(1)], ["\u{E9}", "?b"]);
+    }
+
+    #[test]
+    fn it_kerns_the_characters_of_a_write() {
+        const PAIRS: &[KerningPair] = &[KerningPair {
+            left: 'A',
+            right: 'V',
+            adjustment: -2,
+        }];
+        let style =
+            PcfTextStyle::new(&FONT, BinaryColor::On).with_kerning(KerningTable::new(PAIRS));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let mut console: TextConsole<_, 2, 16> =
+            TextConsole::new(display, style, BinaryColor::Off, Point::zero(), 60);
+
+        console.write_str("AVA\nAV").unwrap();
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        for (row, line) in ["AVA", "AV"].into_iter().enumerate() {
+            style
+                .draw_string(
+                    line,
+                    console.baseline(row),
+                    Baseline::Alphabetic,
+                    &mut expected,
+                )
+                .unwrap();
+        }
+        console.target().assert_eq(&expected);
+    }
+
+    #[test]
+    fn it_scrolls_the_viewport_through_the_history() {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut console: TextConsole<_, 2, 16, PassThrough, 2> =
+            TextConsole::new(display, style, BinaryColor::Off, Point::zero(), 60);
+
+        write!(console, "a\nb\nc\nd\ne").unwrap();
+        console.scroll_up(5).unwrap();
+
+        // the oldest line was overwritten
+        assert_eq!(console.scroll_offset(), 2);
+        assert_eq!([console.line(0), console.line(1)], ["b", "c"]);
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        expected
+            .fill_solid(&console.bounding_box(), BinaryColor::Off)
+            .unwrap();
+        for (row, line) in ["b", "c"].into_iter().enumerate() {
+            style
+                .draw_string(
+                    line,
+                    console.baseline(row),
+                    Baseline::Alphabetic,
+                    &mut expected,
+                )
+                .unwrap();
+        }
+        console.target().assert_eq(&expected);
+
+        console.scroll_down(1).unwrap();
+        assert_eq!([console.line(0), console.line(1)], ["c", "d"]);
+
+        write!(console, "f").unwrap();
+        assert_eq!(console.scroll_offset(), 0);
+        assert_eq!([console.line(0), console.line(1)], ["d", "ef"]);
+    }
+
+    #[test]
+    fn it_keeps_no_empty_lines_for_characters_longer_than_a_line() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut console: TextConsole<_, 2, 1, PassThrough, 4> = TextConsole::new(
+            MockDisplay::new(),
+            style,
+            BinaryColor::Off,
+            Point::zero(),
+            60,
+        );
+        console.target_mut().set_allow_overdraw(true);
+
+        console.write_str("\u{20AC}ab").unwrap();
+        console.scroll_up(4).unwrap();
+
+        assert_eq!(console.scroll_offset(), 1);
+        assert_eq!([console.line(0), console.line(1)], ["?", "a"]);
+    }
+}
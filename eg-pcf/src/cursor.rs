@@ -0,0 +1,175 @@
+use embedded_graphics::{
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        renderer::{CharacterStyle, TextRenderer},
+        Baseline,
+    },
+};
+
+use crate::{shaping::Shaper, text::PcfTextStyle};
+
+/// The shape of a text cursor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CursorShape {
+    /// A box covering the whole character cell.
+    #[default]
+    Block,
+    /// A line at the bottom of the character cell.
+    Underline,
+}
+
+/// A text cursor for input fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cursor<C> {
+    pub shape: CursorShape,
+    pub color: C,
+    /// Color the character under a block cursor is redrawn in, if any.
+    pub inverted_text: Option<C>,
+}
+
+impl<C: PixelColor> Cursor<C> {
+    pub const fn new(shape: CursorShape, color: C) -> Self {
+        Self {
+            shape,
+            color,
+            inverted_text: None,
+        }
+    }
+
+    /// Redraws the character under a block cursor in `color`, usually the background color.
+    pub const fn with_inverted_text(mut self, color: C) -> Self {
+        self.inverted_text = Some(color);
+        self
+    }
+
+    /// Returns the character cell of the cursor at character `index` of `text` drawn at
+    /// `position`.
+    ///
+    /// The cell spans the advance of the character and the height of the font bounding box.
+    /// At the end of the text the cell has the width of a space.
+    pub fn bounding_box<S: Shaper>(
+        &self,
+        style: &PcfTextStyle<'_, C, S>,
+        text: &str,
+        index: usize,
+        position: Point,
+    ) -> Rectangle {
+        let advance = |text| {
+            style
+                .measure_string(text, Point::zero(), Baseline::Alphabetic)
+                .next_position
+                .x
+        };
+
+        let (start, end) = match text.char_indices().nth(index) {
+            Some((i, c)) => (advance(&text[..i]), advance(&text[..i + c.len_utf8()])),
+            None => {
+                let x = advance(text);
+                (x, x + advance(" "))
+            }
+        };
+
+        let font_box = style.font_box();
+        let cell = Rectangle::new(
+            position + Point::new(start, font_box.top_left.y),
+            Size::new((end - start).max(1) as u32, font_box.size.height),
+        );
+
+        match self.shape {
+            CursorShape::Block => cell,
+            CursorShape::Underline => Rectangle::new(
+                cell.top_left + Point::new(0, cell.size.height.saturating_sub(1) as i32),
+                Size::new(cell.size.width, 1),
+            ),
+        }
+    }
+
+    /// Draws the cursor at character `index` of `text` drawn at `position` and returns the
+    /// area it covers.
+    ///
+    /// The text must already be drawn. Only a block cursor with
+    /// [`inverted_text`](Self::inverted_text) redraws the character under it.
+    pub fn draw<S, D>(
+        &self,
+        style: &PcfTextStyle<'_, C, S>,
+        text: &str,
+        index: usize,
+        position: Point,
+        target: &mut D,
+    ) -> Result<Rectangle, D::Error>
+    where
+        S: Shaper + Clone,
+        D: DrawTarget<Color = C>,
+    {
+        let area = self.bounding_box(style, text, index, position);
+        target.fill_solid(&area, self.color)?;
+
+        let under = text.char_indices().nth(index);
+        if let (CursorShape::Block, Some(color), Some((i, c))) =
+            (self.shape, self.inverted_text, under)
+        {
+            let mut inverted = style.clone();
+            inverted.set_text_color(Some(color));
+
+            let x = area.top_left.x - position.x;
+            inverted.draw_string(
+                &text[i..i + c.len_utf8()],
+                position + Point::new(x, 0),
+                Baseline::Alphabetic,
+                target,
+            )?;
+        }
+
+        Ok(area)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, PcfFont};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+
+    #[test]
+    fn it_places_the_cursor_cell() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let cursor = Cursor::new(CursorShape::Block, BinaryColor::On);
+        let position = Point::new(0, 8);
+
+        let cell = cursor.bounding_box(&style, "abc", 1, position);
+        assert_eq!(cell.top_left.x, 6);
+        assert_eq!(cell.size, Size::new(6, FONT.bounding_box.size.height));
+
+        let end = cursor.bounding_box(&style, "abc", 3, position);
+        assert_eq!(end.top_left.x, 18);
+
+        let underline = Cursor::new(CursorShape::Underline, BinaryColor::On);
+        let line = underline.bounding_box(&style, "abc", 1, position);
+        assert_eq!(line.size, Size::new(6, 1));
+        assert_eq!(line.bottom_right(), cell.bottom_right());
+    }
+
+    #[test]
+    fn it_inverts_the_character_under_a_block_cursor() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let cursor =
+            Cursor::new(CursorShape::Block, BinaryColor::On).with_inverted_text(BinaryColor::Off);
+        let position = Point::new(0, 12);
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let area = cursor.draw(&style, "a", 0, position, &mut display).unwrap();
+
+        let glyph = FONT.glyph('a').unwrap().bounding_box.translate(position);
+        let inside = glyph
+            .points()
+            .filter(|p| display.get_pixel(*p) == Some(BinaryColor::Off))
+            .count();
+
+        assert!(inside > 0);
+        assert_eq!(display.affected_area(), area);
+    }
+}
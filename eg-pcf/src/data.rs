@@ -0,0 +1,127 @@
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+/// Storage of the packed glyph bitmaps of a font.
+///
+/// Bitmaps are packed MSB first, with every glyph starting at
+/// [`PcfGlyph::start_index`](crate::PcfGlyph::start_index) bits into the data.
+#[derive(Debug, Clone, Copy)]
+pub enum GlyphData<'a> {
+    /// Data that can be read through a regular slice.
+    Slice(&'a [u8]),
+    /// Data stored as 32-bit words, as emitted by `include_pcf!` with `data = u32`.
+    ///
+    /// Every word holds four bytes in big endian order, so the bit order is the same as
+    /// for [`Slice`](Self::Slice). Each glyph starts on a word boundary, i.e. its
+    /// `start_index` is a multiple of 32, which allows word-wise blitting and avoids
+    /// unaligned accesses.
+    Words(&'a [u32]),
+    /// Data that is read one byte at a time through an accessor function.
+    ///
+    /// This is intended for Harvard architecture targets like AVR, where data placed in
+    /// program memory has to be read with special instructions.
+    Accessor(fn(usize) -> u8),
+}
+
+impl GlyphData<'_> {
+    /// Returns the size of the data in bytes, or 0 for data read through an accessor.
+    pub const fn len_bytes(&self) -> usize {
+        match self {
+            Self::Slice(data) => data.len(),
+            Self::Words(data) => data.len() * 4,
+            Self::Accessor(_) => 0,
+        }
+    }
+
+    /// Returns the byte at `index`.
+    pub fn byte(&self, index: usize) -> u8 {
+        match self {
+            Self::Slice(data) => data[index],
+            Self::Words(data) => (data[index / 4] >> (24 - 8 * (index % 4))) as u8,
+            Self::Accessor(read) => read(index),
+        }
+    }
+
+    /// Returns the bit at `index`, counted MSB first.
+    pub fn bit(&self, index: usize) -> bool {
+        self.byte(index / 8) & (0x80 >> (index % 8)) != 0
+    }
+
+    /// Accessors are compared by address.
+    fn key(&self) -> (u8, usize, &[u8], &[u32]) {
+        match self {
+            Self::Slice(data) => (0, 0, data, &[]),
+            Self::Words(data) => (1, 0, &[], data),
+            Self::Accessor(read) => (2, *read as usize, &[], &[]),
+        }
+    }
+}
+
+impl PartialEq for GlyphData<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for GlyphData<'_> {}
+
+impl PartialOrd for GlyphData<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GlyphData<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for GlyphData<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: [u8; 2] = [0x81, 0x40];
+
+    fn read(index: usize) -> u8 {
+        DATA[index]
+    }
+
+    #[test]
+    fn test_bits_are_msb_first() {
+        let data = GlyphData::Slice(&DATA);
+
+        assert!(data.bit(0));
+        assert!(!data.bit(1));
+        assert!(data.bit(7));
+        assert!(data.bit(9));
+    }
+
+    #[test]
+    fn test_words_read_the_same_bits() {
+        let slice = GlyphData::Slice(&DATA);
+        let words = GlyphData::Words(&[0x8140_0000]);
+
+        for i in 0..16 {
+            assert_eq!(slice.bit(i), words.bit(i));
+        }
+    }
+
+    #[test]
+    fn test_accessor_reads_the_same_bits() {
+        let slice = GlyphData::Slice(&DATA);
+        let accessor = GlyphData::Accessor(read);
+
+        for i in 0..16 {
+            assert_eq!(slice.bit(i), accessor.bit(i));
+        }
+    }
+}
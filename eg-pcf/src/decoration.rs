@@ -0,0 +1,150 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// The line style of a text decoration.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DecorationStyle {
+    #[default]
+    Solid,
+    /// Two solid lines separated by a gap of the line thickness.
+    Double,
+    /// Square dots separated by gaps of the line thickness.
+    Dotted,
+    /// A zigzag line, e.g. to highlight spelling errors.
+    Wavy,
+}
+
+/// A line drawn along the baseline of text, like an underline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decoration {
+    pub style: DecorationStyle,
+    /// Thickness of the line in pixels.
+    pub thickness: u32,
+    /// Distance of the top of the line from the baseline, with positive values pointing down.
+    pub offset: i32,
+}
+
+impl Decoration {
+    /// Creates a one pixel thick decoration directly below the baseline.
+    pub const fn new(style: DecorationStyle) -> Self {
+        Self {
+            style,
+            thickness: 1,
+            offset: 1,
+        }
+    }
+
+    pub const fn with_thickness(mut self, thickness: u32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    pub const fn with_offset(mut self, offset: i32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Draws the decoration from `position` on the baseline to the right for `width` pixels.
+    pub fn draw<D>(
+        &self,
+        position: Point,
+        width: u32,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+    {
+        let t = self.thickness.max(1);
+        let top_left = position + Point::new(0, self.offset);
+        let line =
+            |dy: u32| Rectangle::new(top_left + Point::new(0, dy as i32), Size::new(width, t));
+
+        match self.style {
+            DecorationStyle::Solid => target.fill_solid(&line(0), color),
+            DecorationStyle::Double => {
+                target.fill_solid(&line(0), color)?;
+                target.fill_solid(&line(2 * t), color)
+            }
+            DecorationStyle::Dotted => self.blocks(
+                top_left,
+                width,
+                |i| (i % 2 == 0).then_some(0),
+                color,
+                target,
+            ),
+            DecorationStyle::Wavy => self.blocks(
+                top_left,
+                width,
+                |i| Some([0, 1, 2, 1][i as usize % 4]),
+                color,
+                target,
+            ),
+        }
+    }
+
+    /// Fills `thickness` sized squares along the line, with `row` returning the row of the
+    /// `i`th square or `None` to leave a gap.
+    fn blocks<D>(
+        &self,
+        top_left: Point,
+        width: u32,
+        row: impl Fn(u32) -> Option<u32>,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+    {
+        let t = self.thickness.max(1);
+
+        for i in 0..width.div_ceil(t) {
+            let Some(row) = row(i) else {
+                continue;
+            };
+
+            let block = Rectangle::new(
+                top_left + Point::new((i * t) as i32, (row * t) as i32),
+                Size::new(t.min(width - i * t), t),
+            );
+            target.fill_solid(&block, color)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Decoration {
+    fn default() -> Self {
+        Self::new(DecorationStyle::Solid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    fn draw(decoration: Decoration, width: u32) -> MockDisplay<BinaryColor> {
+        let mut display = MockDisplay::new();
+        decoration
+            .draw(Point::new(0, -1), width, BinaryColor::On, &mut display)
+            .unwrap();
+
+        display
+    }
+
+    #[test]
+    fn it_draws_styles() {
+        draw(Decoration::new(DecorationStyle::Double), 3).assert_pattern(&["###", "   ", "###"]);
+        draw(Decoration::new(DecorationStyle::Dotted), 5).assert_pattern(&["# # #"]);
+        draw(Decoration::new(DecorationStyle::Wavy), 6)
+            .assert_pattern(&["#   # ", " # # #", "  #   "]);
+    }
+
+    #[test]
+    fn it_scales_with_thickness() {
+        let decoration = Decoration::new(DecorationStyle::Dotted).with_thickness(2);
+
+        draw(decoration, 5).assert_pattern(&["##  #", "##  #"]);
+    }
+}
@@ -0,0 +1,144 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A draw target that records the area of all pixels drawn to it.
+///
+/// Displays with partial refresh, like e-paper or memory LCDs, only need to flush the
+/// [`dirty_area`](Self::dirty_area) after drawing. Pixels outside the bounding box of the
+/// target aren't recorded.
+#[derive(Debug)]
+pub struct DirtyTracker<'d, D> {
+    target: &'d mut D,
+    area: Rectangle,
+    /// The top left and bottom right corners of the pixels drawn so far.
+    dirty: Option<(Point, Point)>,
+}
+
+impl<'d, D: DrawTarget> DirtyTracker<'d, D> {
+    pub fn new(target: &'d mut D) -> Self {
+        Self {
+            area: target.bounding_box(),
+            target,
+            dirty: None,
+        }
+    }
+
+    /// Returns the bounding box of the pixels drawn so far, or a zero sized rectangle if
+    /// nothing was drawn.
+    pub fn dirty_area(&self) -> Rectangle {
+        match self.dirty {
+            Some((top_left, bottom_right)) => Rectangle::with_corners(top_left, bottom_right),
+            None => Rectangle::zero(),
+        }
+    }
+
+    /// Forgets the pixels drawn so far, e.g. after the dirty area was flushed.
+    pub fn reset(&mut self) {
+        self.dirty = None;
+    }
+
+    /// Adds the part of `area` inside the bounding box of the target to the dirty area.
+    fn add(&mut self, area: &Rectangle) {
+        let area = area.intersection(&self.area);
+        if let Some(bottom_right) = area.bottom_right() {
+            self.dirty = Some(match self.dirty {
+                Some((top_left, corner)) => (
+                    top_left.component_min(area.top_left),
+                    corner.component_max(bottom_right),
+                ),
+                None => (area.top_left, bottom_right),
+            });
+        }
+    }
+}
+
+impl<D: DrawTarget> Dimensions for DirtyTracker<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for DirtyTracker<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (area, dirty) = (self.area, &mut self.dirty);
+
+        self.target
+            .draw_iter(pixels.into_iter().inspect(|Pixel(point, _)| {
+                if area.contains(*point) {
+                    *dirty = Some(match *dirty {
+                        Some((top_left, bottom_right)) => (
+                            top_left.component_min(*point),
+                            bottom_right.component_max(*point),
+                        ),
+                        None => (*point, *point),
+                    });
+                }
+            }))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.add(area);
+        self.target.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.add(area);
+        self.target.fill_solid(area, color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    #[test]
+    fn it_records_the_area_of_drawn_pixels() {
+        let mut display = MockDisplay::new();
+        display.set_allow_out_of_bounds_drawing(true);
+        let mut tracker = DirtyTracker::new(&mut display);
+        assert_eq!(tracker.dirty_area(), Rectangle::zero());
+
+        let pixels = [Point::new(3, 2), Point::new(1, 5), Point::new(-1, 9)];
+        tracker
+            .draw_iter(pixels.map(|p| Pixel(p, BinaryColor::On)))
+            .unwrap();
+        assert_eq!(
+            tracker.dirty_area(),
+            Rectangle::with_corners(Point::new(1, 2), Point::new(3, 5))
+        );
+
+        tracker.reset();
+        assert_eq!(tracker.dirty_area(), Rectangle::zero());
+    }
+
+    #[test]
+    fn it_forwards_fills_to_the_target() {
+        let mut display = MockDisplay::new();
+        display.set_allow_out_of_bounds_drawing(true);
+        let mut tracker = DirtyTracker::new(&mut display);
+
+        let area = Rectangle::new(Point::new(60, 2), Size::new(8, 3));
+        tracker.fill_solid(&area, BinaryColor::On).unwrap();
+        let area = Rectangle::new(Point::new(1, 1), Size::new(2, 2));
+        tracker
+            .fill_contiguous(&area, [BinaryColor::Off; 4])
+            .unwrap();
+
+        // the first fill is clipped to the display
+        assert_eq!(
+            tracker.dirty_area(),
+            Rectangle::with_corners(Point::new(1, 1), Point::new(63, 4))
+        );
+        assert_eq!(display.get_pixel(Point::new(63, 4)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(2, 2)), Some(BinaryColor::Off));
+    }
+}
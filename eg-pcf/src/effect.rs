@@ -0,0 +1,98 @@
+use embedded_graphics::prelude::*;
+
+/// An effect drawn behind text, e.g. to keep it legible on busy backgrounds.
+///
+/// The glyphs of a whole string are drawn in the effect color first and in the text color
+/// on top, so effects never cover neighboring glyphs. Effects don't change advances or the
+/// measured size of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TextEffect<C> {
+    /// A one pixel outline around the glyphs.
+    Outline(C),
+    /// A copy of the glyphs offset by `offset`, usually down and to the right.
+    Shadow { offset: Point, color: C },
+}
+
+impl<C: Copy> TextEffect<C> {
+    /// Returns a drop shadow one pixel down and to the right.
+    pub const fn shadow(color: C) -> Self {
+        Self::Shadow {
+            offset: Point::new(1, 1),
+            color,
+        }
+    }
+
+    pub const fn color(&self) -> C {
+        match *self {
+            Self::Outline(color) | Self::Shadow { color, .. } => color,
+        }
+    }
+
+    /// Returns the offsets glyphs are drawn at in the effect color.
+    pub(crate) fn offsets(&self) -> impl Iterator<Item = Point> {
+        const NEIGHBORS: [Point; 8] = [
+            Point::new(-1, -1),
+            Point::new(0, -1),
+            Point::new(1, -1),
+            Point::new(-1, 0),
+            Point::new(1, 0),
+            Point::new(-1, 1),
+            Point::new(0, 1),
+            Point::new(1, 1),
+        ];
+
+        let (offsets, count) = match *self {
+            Self::Outline(_) => (NEIGHBORS, NEIGHBORS.len()),
+            Self::Shadow { offset, .. } => ([offset; 8], 1),
+        };
+
+        offsets.into_iter().take(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, text::PcfTextStyle, PcfFont};
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::Rgb565,
+        text::{renderer::TextRenderer, Baseline},
+    };
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+
+    #[test]
+    fn it_draws_an_outline_below_the_text() {
+        let style =
+            PcfTextStyle::new(&FONT, Rgb565::WHITE).with_effect(TextEffect::Outline(Rgb565::BLUE));
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        style
+            .draw_string("-", Point::new(1, 8), Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        display.assert_pattern(&["       ", "       ", "BBBBBBB", "BWWWWWB", "BBBBBBB"]);
+    }
+
+    #[test]
+    fn it_draws_a_shadow_below_the_text() {
+        let style =
+            PcfTextStyle::new(&FONT, Rgb565::WHITE).with_effect(TextEffect::shadow(Rgb565::RED));
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        style
+            .draw_string("--", Point::new(0, 8), Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        display.assert_pattern(&[
+            "            ",
+            "            ",
+            "            ",
+            "WWWWW WWWWW ",
+            " RRRRR RRRRR",
+        ]);
+    }
+}
@@ -0,0 +1,106 @@
+//! Fonts that share their glyph data.
+
+use crate::PcfFont;
+
+/// Fonts that share a single glyph data blob, e.g. the weights of a UI font.
+///
+/// `include_pcf_family!` emits families whose fonts include the same characters and store
+/// identical glyph bitmaps once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PcfFontFamily<'a> {
+    /// The fonts, in the order of the `include_pcf_family!` arguments.
+    pub fonts: &'a [PcfFont<'a>],
+}
+
+impl<'a> PcfFontFamily<'a> {
+    /// Creates a family from fonts that share their glyph data.
+    pub const fn new(fonts: &'a [PcfFont<'a>]) -> Self {
+        Self { fonts }
+    }
+
+    /// Returns the font at `index`.
+    pub const fn font(&self, index: usize) -> Option<&'a PcfFont<'a>> {
+        if index < self.fonts.len() {
+            Some(&self.fonts[index])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the memory used by the glyph tables of all fonts and the shared glyph data in
+    /// bytes.
+    pub const fn total_bytes(&self) -> usize {
+        let mut bytes = 0;
+        let mut i = 0;
+        while i < self.fonts.len() {
+            bytes += self.fonts[i].total_bytes() - self.fonts[i].data_bytes();
+            i += 1;
+        }
+
+        match self.font(0) {
+            Some(font) => bytes + font.data_bytes(),
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, include_pcf_family, renderer::BitmapFontRenderer};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*};
+
+    const FAMILY: PcfFontFamily = include_pcf_family!(
+        "examples/6x10.pcf",
+        "examples/10x20.pcf",
+        'a'..='z',
+        data = u32
+    );
+    const SMALL: PcfFont = include_pcf!("examples/6x10.pcf", 'a'..='z', data = u32);
+    const LARGE: PcfFont = include_pcf!("examples/10x20.pcf", 'a'..='z', data = u32);
+
+    #[test]
+    fn it_draws_the_same_as_separate_fonts() {
+        for (font, expected_font) in FAMILY.fonts.iter().zip([SMALL, LARGE]) {
+            assert_eq!(font.data, FAMILY.fonts[0].data);
+            assert_eq!(font.bounding_box, expected_font.bounding_box);
+            assert_eq!(font.metrics, expected_font.metrics);
+
+            for c in ['a', 'g', 'z', 'A'] {
+                let mut expected = MockDisplay::<BinaryColor>::new();
+                expected_font
+                    .draw_glyph(c, Point::new(2, 20), BinaryColor::On, &mut expected)
+                    .unwrap();
+                let mut display = MockDisplay::new();
+                font.draw_glyph(c, Point::new(2, 20), BinaryColor::On, &mut display)
+                    .unwrap();
+
+                display.assert_eq(&expected);
+            }
+        }
+
+        assert_eq!(FAMILY.font(1), Some(&FAMILY.fonts[1]));
+        assert_eq!(FAMILY.font(2), None);
+        assert_eq!(
+            FAMILY.total_bytes(),
+            SMALL.total_bytes() + LARGE.total_bytes()
+        );
+    }
+
+    #[test]
+    fn it_stores_identical_bitmaps_once() {
+        const DIGITS: PcfFontFamily =
+            include_pcf_family!("examples/6x10.pcf", "examples/6x10-digits.bdf", '0'..='9');
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+
+        let [font, digits] = DIGITS.fonts else {
+            panic!("expected two fonts");
+        };
+        assert_eq!(font.glyphs, FONT.glyphs);
+        assert_eq!(digits.data, FONT.data);
+        assert_eq!(
+            DIGITS.total_bytes(),
+            FONT.total_bytes() + FONT.glyph_count() * core::mem::size_of::<crate::PcfGlyph>()
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use embedded_graphics::prelude::*;
+
+use crate::{
+    placement::Rotation,
+    shaping::Shaper,
+    text::{GlyphAdvance, PcfTextStyle},
+};
+
+/// A string squeezed into a maximum width, see [`fit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fitted<'t> {
+    /// The part of the text that is drawn.
+    pub text: &'t str,
+    /// Set if the text was truncated and is followed by an ellipsis.
+    pub ellipsis: Option<&'static str>,
+    /// Pixels added between glyphs, zero or negative.
+    pub spacing: i32,
+    /// The advance width of the fitted text in pixels.
+    pub width: u32,
+}
+
+impl Fitted<'_> {
+    /// Draws the fitted text at `position` and returns the position following the last glyph.
+    pub fn draw<C, S, D>(
+        &self,
+        style: &PcfTextStyle<'_, C, S>,
+        mut position: Point,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        C: PixelColor,
+        S: Shaper,
+        D: DrawTarget<Color = C>,
+    {
+        let start = position;
+        let ellipsis = self.ellipsis.unwrap_or_default();
+        let glyphs = style.advances(self.text).chain(style.advances(ellipsis));
+
+        let mut pen = position;
+        for g in glyphs.clone() {
+            style.draw_glyph_effect(&g, pen, Rotation::None, target)?;
+            pen.x += g.advance + self.spacing;
+        }
+
+        let mut glyphs = glyphs.peekable();
+        while let Some(g) = glyphs.next() {
+            style.draw_glyph(&g, position, Rotation::None, target)?;
+
+            position.x += g.advance;
+            if glyphs.peek().is_some() {
+                position.x += self.spacing;
+            }
+        }
+
+        style.draw_decorations(start, position.x, target)?;
+
+        Ok(position)
+    }
+}
+
+/// Fits `text` into `max_width` pixels, e.g. for right aligned numeric fields.
+///
+/// The spacing between glyphs is reduced pixel by pixel down to `min_spacing`, which may be
+/// negative to let glyphs overlap. If the text still doesn't fit, it is truncated and
+/// followed by `…`, or `...` if the font has no ellipsis glyph. If not even the ellipsis
+/// fits, the returned width exceeds `max_width`.
+pub fn fit<'t, C, S>(
+    style: &PcfTextStyle<'_, C, S>,
+    text: &'t str,
+    max_width: u32,
+    min_spacing: i32,
+) -> Fitted<'t>
+where
+    C: PixelColor,
+    S: Shaper,
+{
+    let min_spacing = min_spacing.min(0);
+    let fitted = |text, ellipsis: Option<&'static str>| {
+        let glyphs = style
+            .advances(text)
+            .chain(style.advances(ellipsis.unwrap_or_default()));
+        let (width, count) = measure(glyphs);
+        let gaps = count.saturating_sub(1) as i32;
+
+        let spacing = match width - max_width as i32 {
+            overflow if overflow <= 0 => 0,
+            _ if gaps == 0 => i32::MIN,
+            overflow => -((overflow + gaps - 1) / gaps),
+        };
+
+        (spacing >= min_spacing).then_some(Fitted {
+            text,
+            ellipsis,
+            spacing,
+            width: (width + spacing * gaps).max(0) as u32,
+        })
+    };
+
+    if let Some(fitted) = fitted(text, None) {
+        return fitted;
+    }
+
+    let ellipsis = match style.advances("…").any(|g| g.missing) {
+        true => "...",
+        false => "…",
+    };
+
+    text.char_indices()
+        .rev()
+        .map(|(i, _)| text[..i].trim_end())
+        .find_map(|prefix| fitted(prefix, Some(ellipsis)))
+        .unwrap_or_else(|| {
+            let (width, _) = measure(style.advances(ellipsis));
+            Fitted {
+                text: "",
+                ellipsis: Some(ellipsis),
+                spacing: 0,
+                width: width.max(0) as u32,
+            }
+        })
+}
+
+/// Returns the advance width and the number of glyphs.
+fn measure<'a>(glyphs: impl Iterator<Item = GlyphAdvance<'a>>) -> (i32, usize) {
+    glyphs.fold((0, 0), |(width, count), g| (width + g.advance, count + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, PcfFont};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+
+    #[test]
+    fn it_reduces_spacing_to_fit() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+
+        let fitted = fit(&style, "1234", 24, -2);
+        assert_eq!((fitted.text, fitted.spacing, fitted.width), ("1234", 0, 24));
+
+        let fitted = fit(&style, "1234", 20, -2);
+        assert_eq!(
+            (fitted.text, fitted.spacing, fitted.width),
+            ("1234", -2, 18)
+        );
+        assert_eq!(fitted.ellipsis, None);
+    }
+
+    #[test]
+    fn it_falls_back_to_an_ellipsis() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let fitted = fit(&style, "123 456", 31, -1);
+
+        assert_eq!(fitted.text, "123");
+        assert_eq!(fitted.ellipsis, Some("..."));
+        assert_eq!(fitted.spacing, -1);
+        assert_eq!(fitted.width, 6 * 6 - 5);
+
+        let mut display = MockDisplay::new();
+        let end = fitted.draw(&style, Point::new(0, 8), &mut display).unwrap();
+        assert_eq!(end.x, fitted.width as i32);
+    }
+}
@@ -0,0 +1,153 @@
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+use embedded_graphics::prelude::*;
+
+/// A signed 26.6 fixed point number, i.e. a pixel value with 1/64 pixel precision.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i32);
+
+impl Fixed {
+    const FRACTION_BITS: u32 = 6;
+
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << Self::FRACTION_BITS);
+
+    /// Creates a value from its raw 26.6 representation.
+    pub const fn from_bits(bits: i32) -> Self {
+        Self(bits)
+    }
+
+    /// Creates a value from a whole number of pixels.
+    pub const fn from_int(value: i32) -> Self {
+        Self(value << Self::FRACTION_BITS)
+    }
+
+    /// Creates a value from a fraction of pixels, rounded towards zero to 1/64 pixel.
+    pub const fn from_ratio(numerator: i32, denominator: i32) -> Self {
+        Self((numerator << Self::FRACTION_BITS) / denominator)
+    }
+
+    /// Returns the raw 26.6 representation.
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    /// Rounds to the nearest whole pixel, with halves rounded up.
+    pub const fn round(self) -> i32 {
+        (self.0 + (Self::ONE.0 >> 1)) >> Self::FRACTION_BITS
+    }
+
+    /// Rounds down to a whole pixel.
+    pub const fn floor(self) -> i32 {
+        self.0 >> Self::FRACTION_BITS
+    }
+}
+
+impl From<i32> for Fixed {
+    fn from(value: i32) -> Self {
+        Self::from_int(value)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+/// A point with 26.6 fixed point coordinates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedPoint {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl FixedPoint {
+    pub const fn new(x: Fixed, y: Fixed) -> Self {
+        Self { x, y }
+    }
+
+    /// Rounds both coordinates to the nearest pixel.
+    pub const fn round(self) -> Point {
+        Point::new(self.x.round(), self.y.round())
+    }
+}
+
+impl From<Point> for FixedPoint {
+    fn from(point: Point) -> Self {
+        Self::new(point.x.into(), point.y.into())
+    }
+}
+
+impl Add for FixedPoint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for FixedPoint {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round() {
+        assert_eq!(Fixed::from_ratio(1, 2).round(), 1);
+        assert_eq!(Fixed::from_ratio(-1, 2).round(), 0);
+        assert_eq!(Fixed::from_ratio(5, 4).round(), 1);
+        assert_eq!(Fixed::from_ratio(-5, 4).round(), -1);
+        assert_eq!(Fixed::from_ratio(-5, 4).floor(), -2);
+    }
+
+    #[test]
+    fn test_accumulation() {
+        let step = Fixed::from_ratio(1, 3);
+        let mut x = Fixed::ZERO;
+
+        for _ in 0..3 {
+            x += step;
+        }
+
+        assert_eq!(x.round(), 1);
+        assert_eq!(x.to_bits(), 63);
+    }
+}
@@ -0,0 +1,17 @@
+//! Logging macros that forward to `log` or `defmt` when the corresponding feature is enabled.
+//!
+//! Neither crate provides a clock on `no_std` targets, so durations are derived from the
+//! timestamps attached by the logger (e.g. `defmt::timestamp!`) to the start and end events.
+
+macro_rules! trace {
+    ($s:literal $(, $x:expr)* $(,)?) => {
+        {
+            #[cfg(feature = "log")]
+            ::log::trace!($s $(, $x)*);
+            #[cfg(feature = "defmt")]
+            ::defmt::trace!($s $(, $x)*);
+            #[cfg(not(any(feature = "log", feature = "defmt")))]
+            let _ = ($( & $x ),*);
+        }
+    };
+}
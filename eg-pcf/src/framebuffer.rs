@@ -0,0 +1,214 @@
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+
+use crate::{GlyphData, PcfFont, PcfGlyph};
+
+/// A 1 bit per pixel framebuffer in caller provided memory that glyphs are blitted into
+/// directly, up to a byte at a time, instead of pixel by pixel through a `DrawTarget`.
+///
+/// Rows are `stride` bytes apart and start `offset` bytes into the buffer, e.g. after a
+/// command byte that is sent along with the pixels. The leftmost pixel of a byte is its most
+/// significant bit, and set bits are [`BinaryColor::On`]. Drivers of displays with other
+/// layouts, like the vertical pages of the SSD1306 and SH1106, convert the rows when flushing
+/// the buffer.
+///
+/// Text is drawn with the glyph advances of the font only. Styles like kerning or scaling
+/// need a [`PcfTextStyle`](crate::text::PcfTextStyle) and a `DrawTarget`.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct PackedFramebuffer<'b> {
+    buffer: &'b mut [u8],
+    size: Size,
+    stride: usize,
+    offset: usize,
+}
+
+impl<'b> PackedFramebuffer<'b> {
+    /// Creates a framebuffer of `size` pixels whose rows are `stride` bytes apart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a row is too short for the width or the buffer too short for the rows.
+    pub fn new(buffer: &'b mut [u8], size: Size, stride: usize) -> Self {
+        Self::with_offset(buffer, size, stride, 0)
+    }
+
+    /// Creates a framebuffer whose first row starts `offset` bytes into the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a row is too short for the width or the buffer too short for the rows.
+    pub fn with_offset(buffer: &'b mut [u8], size: Size, stride: usize, offset: usize) -> Self {
+        assert!(stride * 8 >= size.width as usize, "rows are too short");
+        assert!(
+            offset + stride * size.height as usize <= buffer.len(),
+            "buffer is too short"
+        );
+
+        Self {
+            buffer,
+            size,
+            stride,
+            offset,
+        }
+    }
+
+    /// Returns the size of the framebuffer in pixels.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Returns the color of the pixel at `point` or `None` if it is outside the framebuffer.
+    pub fn pixel(&self, point: Point) -> Option<BinaryColor> {
+        let Point { x, y } = point;
+        if !Rectangle::new(Point::zero(), self.size).contains(point) {
+            return None;
+        }
+
+        let byte = self.buffer[self.offset + y as usize * self.stride + x as usize / 8];
+        Some(BinaryColor::from(byte & (0x80 >> (x % 8)) != 0))
+    }
+
+    /// Sets all pixels of the rows to `color`.
+    pub fn clear(&mut self, color: BinaryColor) {
+        let rows = self.stride * self.size.height as usize;
+        let byte = match color {
+            BinaryColor::On => 0xFF,
+            BinaryColor::Off => 0,
+        };
+
+        self.buffer[self.offset..][..rows].fill(byte);
+    }
+
+    /// Draws the glyph of `c` with its origin at `position` and returns the origin of the
+    /// next glyph. Pixels outside the framebuffer are clipped.
+    pub fn draw_glyph(
+        &mut self,
+        font: &PcfFont<'_>,
+        c: char,
+        position: Point,
+        color: BinaryColor,
+    ) -> Point {
+        let glyph = font.get_glyph(c);
+        self.blit(
+            glyph,
+            &font.data,
+            position + glyph.bounding_box.top_left,
+            color,
+        );
+
+        position + Point::new(glyph.device_width, 0)
+    }
+
+    /// Draws the glyphs of a string with the origin of the first at `position` and returns
+    /// the origin after the last one.
+    pub fn draw_string(
+        &mut self,
+        font: &PcfFont<'_>,
+        text: &str,
+        mut position: Point,
+        color: BinaryColor,
+    ) -> Point {
+        for c in text.chars() {
+            position = self.draw_glyph(font, c, position, color);
+        }
+
+        position
+    }
+
+    /// Writes the set pixels of a glyph with its top left corner at `top_left`, in runs that
+    /// each fill part of one byte of a row.
+    fn blit(&mut self, glyph: &PcfGlyph, data: &GlyphData, top_left: Point, color: BinaryColor) {
+        let Size { width, height } = glyph.bounding_box.size;
+        // the columns of the glyph inside the framebuffer
+        let first = (-top_left.x).clamp(0, width as i32) as usize;
+        let last = (self.size.width as i32 - top_left.x).clamp(0, width as i32) as usize;
+
+        for y in 0..height as usize {
+            let row_y = top_left.y + y as i32;
+            if row_y < 0 || row_y >= self.size.height as i32 {
+                continue;
+            }
+            let row = self.offset + row_y as usize * self.stride;
+            let bits = glyph.start_index + y * width as usize;
+
+            let mut x = first;
+            while x < last {
+                let column = (top_left.x + x as i32) as usize;
+                let count = (8 - column % 8).min(last - x);
+                let run = read_bits(data, bits + x, count) >> (column % 8);
+
+                let byte = &mut self.buffer[row + column / 8];
+                match color {
+                    BinaryColor::On => *byte |= run,
+                    BinaryColor::Off => *byte &= !run,
+                }
+                x += count;
+            }
+        }
+    }
+}
+
+/// Returns `count` bits, up to 8, starting at bit `index` of the data, in the most
+/// significant bits of a byte.
+fn read_bits(data: &GlyphData, index: usize, count: usize) -> u8 {
+    let (byte, shift) = (index / 8, index % 8);
+    let mut bits = data.byte(byte) << shift;
+    if shift + count > 8 {
+        bits |= data.byte(byte + 1) >> (8 - shift);
+    }
+
+    bits & (0xFF00u16 >> count) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, renderer::BitmapFontRenderer};
+    use embedded_graphics::mock_display::MockDisplay;
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'A'..='z');
+
+    /// Asserts that the framebuffer has the pixels drawn by the font renderer at `position`.
+    fn assert_draws_like_the_renderer(text: &str, position: Point) {
+        let mut buffer = [0; 2 + 4 * 12];
+        let mut framebuffer = PackedFramebuffer::with_offset(&mut buffer, Size::new(26, 12), 4, 2);
+        let next = framebuffer.draw_string(&FONT, text, position, BinaryColor::On);
+
+        let mut display = MockDisplay::new();
+        display.set_allow_out_of_bounds_drawing(true);
+        let mut expected_next = position;
+        for c in text.chars() {
+            expected_next = FONT
+                .draw_glyph(c, expected_next, BinaryColor::On, &mut display)
+                .unwrap();
+        }
+
+        assert_eq!(next, expected_next);
+        for point in Rectangle::new(Point::zero(), framebuffer.size()).points() {
+            let expected = display.get_pixel(point).unwrap_or(BinaryColor::Off);
+            assert_eq!(framebuffer.pixel(point), Some(expected), "{point:?}");
+        }
+        // the bytes before the rows and the padding at their ends are untouched
+        assert_eq!(buffer[..2], [0, 0]);
+        assert!(buffer[2..].chunks(4).all(|row| row[3] & 0x3F == 0));
+    }
+
+    #[test]
+    fn it_blits_glyphs_at_any_bit_offset() {
+        assert_draws_like_the_renderer("Ag", Point::new(0, 9));
+        assert_draws_like_the_renderer("Wy", Point::new(3, 10));
+        // clipped on every side
+        assert_draws_like_the_renderer("MQW", Point::new(-3, 4));
+        assert_draws_like_the_renderer("MQW", Point::new(11, 14));
+    }
+
+    #[test]
+    fn it_clears_pixels_with_the_off_color() {
+        let mut buffer = [0; 2 * 10];
+        let mut framebuffer = PackedFramebuffer::new(&mut buffer, Size::new(16, 10), 2);
+        framebuffer.clear(BinaryColor::On);
+        framebuffer.draw_glyph(&FONT, 'W', Point::new(5, 9), BinaryColor::Off);
+        framebuffer.draw_glyph(&FONT, 'W', Point::new(5, 9), BinaryColor::On);
+
+        assert!(buffer.iter().all(|byte| *byte == 0xFF));
+    }
+}
@@ -0,0 +1,423 @@
+//! Caching glyphs decoded on demand.
+//!
+//! Fonts parsed at runtime don't have to be decoded up front like `PcfFontBuf` does. A
+//! [`GlyphSource`] can decode single glyphs when they are drawn, e.g. by seeking in a font
+//! file on an SD card, and a [`GlyphCache`] keeps the most recently used ones, so large fonts
+//! can be drawn with little memory without decoding the same glyphs over and over.
+//!
+//! [`LazyPcfFont`](crate::buf::LazyPcfFont) decodes glyphs from PCF data in memory, and
+//! [`GlyphCache::draw_string`] draws text through the cache.
+
+use core::{cmp::Reverse, convert::Infallible, fmt};
+
+use embedded_graphics::prelude::*;
+
+use crate::{placement::Rotation, GlyphData, PcfFont, PcfGlyph};
+
+/// An error returned by a [`GlyphCache`] or [`GlyphSource`].
+///
+/// `S` is the error of the glyph source and `D` the error of the draw target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheError<S, D = Infallible> {
+    /// The source failed to decode a glyph.
+    Source(S),
+    /// The bitmap of the glyph of `c` takes `bytes` bytes, more than the cache holds.
+    TooLarge { c: char, bytes: usize },
+    /// The draw target failed to draw a glyph.
+    Target(D),
+}
+
+impl<S> CacheError<S> {
+    fn with_target<D>(self) -> CacheError<S, D> {
+        match self {
+            Self::Source(error) => CacheError::Source(error),
+            Self::TooLarge { c, bytes } => CacheError::TooLarge { c, bytes },
+            Self::Target(never) => match never {},
+        }
+    }
+}
+
+impl<S: fmt::Display, D: fmt::Display> fmt::Display for CacheError<S, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source(error) => write!(f, "failed to decode glyph: {error}"),
+            Self::TooLarge { c, bytes } => {
+                write!(
+                    f,
+                    "glyph of {c:?} needs {bytes} bytes, too large for the cache"
+                )
+            }
+            Self::Target(error) => write!(f, "failed to draw glyph: {error}"),
+        }
+    }
+}
+
+/// A font that decodes single glyphs on demand.
+pub trait GlyphSource {
+    type Error;
+
+    /// Decodes the glyph of `c` into `bitmap`, packed row by row MSB first without padding
+    /// like [`GlyphData::Slice`], and returns its metrics with a `start_index` of 0.
+    ///
+    /// Returns `None` if the font has no glyph for `c`, and [`CacheError::TooLarge`] if its
+    /// bitmap doesn't fit `bitmap`.
+    fn load_glyph(
+        &mut self,
+        c: char,
+        bitmap: &mut [u8],
+    ) -> Result<Option<PcfGlyph>, CacheError<Self::Error>>;
+
+    /// Returns a character the font has a glyph for that is drawn in place of missing ones.
+    fn replacement_character(&self) -> Option<char> {
+        None
+    }
+}
+
+impl GlyphSource for PcfFont<'_> {
+    type Error = Infallible;
+
+    fn load_glyph(
+        &mut self,
+        c: char,
+        bitmap: &mut [u8],
+    ) -> Result<Option<PcfGlyph>, CacheError<Self::Error>> {
+        let Some(glyph) = self.glyph(c) else {
+            return Ok(None);
+        };
+        let bits = (glyph.bounding_box.size.width * glyph.bounding_box.size.height) as usize;
+        let bytes = bits.div_ceil(8);
+        let bitmap = bitmap
+            .get_mut(..bytes)
+            .ok_or(CacheError::TooLarge { c, bytes })?;
+
+        bitmap.fill(0);
+        for i in 0..bits {
+            if self.data.bit(glyph.start_index + i) {
+                bitmap[i / 8] |= 0x80 >> (i % 8);
+            }
+        }
+
+        Ok(Some(PcfGlyph {
+            start_index: 0,
+            ..*glyph
+        }))
+    }
+
+    fn replacement_character(&self) -> Option<char> {
+        // glyph characters are code points of the codepage, which only round trip if they're
+        // encoded as themselves
+        let c = self.glyphs.get(self.replacement_character)?.character;
+        (self.codepage.encode(c) == Some(c as u32)).then_some(c)
+    }
+}
+
+/// A glyph stored in a [`GlyphCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CachedGlyph<'a> {
+    /// The glyph metrics, with a `start_index` of 0.
+    pub glyph: PcfGlyph,
+    /// The glyph bitmap, packed like [`GlyphData::Slice`].
+    pub bitmap: &'a [u8],
+}
+
+impl CachedGlyph<'_> {
+    /// Draws the glyph with its origin at `position` and returns the origin of the next
+    /// glyph.
+    pub fn draw<D: DrawTarget>(
+        &self,
+        position: Point,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<Point, D::Error> {
+        let data = GlyphData::Slice(self.bitmap);
+        self.glyph
+            .draw(position, Rotation::None, color, &data, target)?;
+
+        Ok(position + Point::new(self.glyph.device_width, 0))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry<const BYTES: usize> {
+    /// The character looked up, which differs from `glyph.character` in fonts with a
+    /// [`Codepage`](crate::codepage::Codepage).
+    c: char,
+    glyph: PcfGlyph,
+    bitmap: [u8; BYTES],
+    last_used: u32,
+}
+
+/// A least recently used cache of up to `N` glyphs whose bitmaps take up to `BYTES` bytes.
+///
+/// The cache doesn't allocate, so it can be placed in a `static` on targets without a heap.
+/// `N` has to be at least 1, and `BYTES` has to hold the largest glyph drawn, e.g.
+/// `(width * height).div_ceil(8)` of the font bounding box. Larger glyphs fail with
+/// [`CacheError::TooLarge`].
+#[derive(Debug, Clone)]
+pub struct GlyphCache<const N: usize, const BYTES: usize> {
+    entries: [Option<Entry<BYTES>>; N],
+    clock: u32,
+    hits: u32,
+    misses: u32,
+}
+
+impl<const N: usize, const BYTES: usize> Default for GlyphCache<N, BYTES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const BYTES: usize> GlyphCache<N, BYTES> {
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "a glyph cache needs at least one entry") };
+
+        Self {
+            entries: [None; N],
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the glyph of `c`, decoding it from `source` if it isn't cached.
+    ///
+    /// A decoded glyph replaces the least recently used one if the cache is full. Returns
+    /// `None` if `source` has no glyph for `c`, which isn't cached.
+    pub fn glyph<S: GlyphSource>(
+        &mut self,
+        source: &mut S,
+        c: char,
+    ) -> Result<Option<CachedGlyph<'_>>, CacheError<S::Error>> {
+        self.clock = self.clock.wrapping_add(1);
+
+        let cached = self
+            .entries
+            .iter()
+            .position(|entry| entry.is_some_and(|entry| entry.c == c));
+        let index = match cached {
+            Some(index) => {
+                self.hits = self.hits.wrapping_add(1);
+                index
+            }
+            None => {
+                self.misses = self.misses.wrapping_add(1);
+                let mut bitmap = [0; BYTES];
+                let Some(glyph) = source.load_glyph(c, &mut bitmap)? else {
+                    return Ok(None);
+                };
+
+                trace!(
+                    "glyph_cache: miss, {} hits, {} misses",
+                    self.hits,
+                    self.misses
+                );
+
+                let index = self.victim();
+                self.entries[index] = Some(Entry {
+                    c,
+                    glyph,
+                    bitmap,
+                    last_used: 0,
+                });
+                index
+            }
+        };
+
+        let entry = self.entries[index].as_mut().unwrap();
+        entry.last_used = self.clock;
+
+        Ok(Some(CachedGlyph {
+            glyph: entry.glyph,
+            bitmap: &entry.bitmap,
+        }))
+    }
+
+    /// Draws `text` on one line with the baseline of its first glyph at `position` and
+    /// returns the position of the next glyph.
+    ///
+    /// Characters `source` has no glyph for are drawn with its
+    /// [`replacement_character`](GlyphSource::replacement_character), if any.
+    pub fn draw_string<S: GlyphSource, D: DrawTarget>(
+        &mut self,
+        source: &mut S,
+        text: &str,
+        mut position: Point,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<Point, CacheError<S::Error, D::Error>> {
+        for c in text.chars() {
+            let glyph = match self.glyph(source, c).map_err(CacheError::with_target)? {
+                Some(glyph) => glyph,
+                None => {
+                    let Some(replacement) = source.replacement_character() else {
+                        continue;
+                    };
+                    let glyph = self.glyph(source, replacement);
+                    match glyph.map_err(CacheError::with_target)? {
+                        Some(glyph) => glyph,
+                        None => continue,
+                    }
+                }
+            };
+
+            position = glyph
+                .draw(position, color, target)
+                .map_err(CacheError::Target)?;
+        }
+
+        Ok(position)
+    }
+
+    /// Returns the index of a free entry, or of the least recently used one.
+    fn victim(&self) -> usize {
+        let clock = self.clock;
+
+        // free entries sort first, ages are relative to the clock so they survive wrapping
+        self.entries
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| {
+                entry.map(|entry| Reverse(clock.wrapping_sub(entry.last_used)))
+            })
+            .map(|(index, _)| index)
+            .expect("the cache has no entries")
+    }
+
+    /// Returns the number of cached glyphs.
+    pub fn len(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of lookups that found a cached glyph and that had to decode one.
+    ///
+    /// The counts wrap around on overflow. With the `log` or `defmt` feature they are also
+    /// traced on every miss.
+    pub const fn stats(&self) -> (u32, u32) {
+        (self.hits, self.misses)
+    }
+
+    /// Removes all glyphs, e.g. after switching fonts.
+    pub fn clear(&mut self) {
+        self.entries = [None; N];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{buf::LazyPcfFont, include_pcf, renderer::BitmapFontRenderer, text::PcfTextStyle};
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        text::{renderer::TextRenderer, Baseline},
+    };
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'a'..='z');
+
+    #[test]
+    fn it_draws_decoded_glyphs() {
+        let mut cache = GlyphCache::<2, 8>::new();
+        let mut source = FONT;
+
+        for c in ['a', 'g', 'z'] {
+            let glyph = cache.glyph(&mut source, c).unwrap().unwrap();
+            let mut display = MockDisplay::new();
+            let next = glyph
+                .draw(Point::new(1, 10), BinaryColor::On, &mut display)
+                .unwrap();
+
+            let mut expected = MockDisplay::new();
+            let expected_next = FONT
+                .draw_glyph(c, Point::new(1, 10), BinaryColor::On, &mut expected)
+                .unwrap();
+            display.assert_eq(&expected);
+            assert_eq!(next, expected_next);
+        }
+
+        assert_eq!(cache.glyph(&mut source, 'A'), Ok(None));
+        assert_eq!(
+            GlyphCache::<1, 7>::new().glyph(&mut source, 'a'),
+            Err(CacheError::TooLarge { c: 'a', bytes: 8 })
+        );
+    }
+
+    #[test]
+    fn it_evicts_the_least_recently_used_glyph() {
+        let mut cache = GlyphCache::<2, 8>::new();
+        let mut source = FONT;
+        let mut lookup = |c| {
+            cache
+                .glyph(&mut source, c)
+                .unwrap()
+                .unwrap()
+                .glyph
+                .character
+        };
+
+        for c in ['a', 'b', 'a', 'c', 'a', 'b'] {
+            assert_eq!(lookup(c), c);
+        }
+
+        // `b` was evicted by `c`, and `c` by `b`
+        assert_eq!(cache.stats(), (2, 4));
+        assert_eq!(cache.len(), 2);
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn it_caches_glyphs_by_the_character_looked_up() {
+        let mut cache = GlyphCache::<2, 8>::new();
+        let mut source = include_pcf!("examples/6x10.pcf", '\u{DB}', codepage = cp437);
+
+        let block = cache.glyph(&mut source, '█').unwrap().unwrap().glyph;
+        assert_eq!(cache.glyph(&mut source, '█').unwrap().unwrap().glyph, block);
+        assert_eq!(cache.stats(), (1, 1));
+        // `Û` is at 0xDB in Unicode, but not in CP437
+        assert_eq!(cache.glyph(&mut source, 'Û'), Ok(None));
+    }
+
+    #[test]
+    fn it_draws_strings_decoded_from_pcf_data() {
+        let bytes = include_bytes!("../examples/6x10.pcf");
+        let mut source = LazyPcfFont::parse(bytes).unwrap();
+        let mut cache = GlyphCache::<4, 8>::new();
+        // U+10000 isn't encoded and is drawn with the default character
+        let text = "Hi!\u{10000}";
+
+        let mut display = MockDisplay::new();
+        let next = cache
+            .draw_string(
+                &mut source,
+                text,
+                Point::new(0, 8),
+                BinaryColor::On,
+                &mut display,
+            )
+            .unwrap();
+
+        let font = include_pcf!("examples/6x10.pcf", '\0'..='~');
+        let mut expected = MockDisplay::new();
+        let expected_next = PcfTextStyle::new(&font, BinaryColor::On)
+            .draw_string(text, Point::new(0, 8), Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+        assert_eq!(next, expected_next);
+
+        assert_eq!(source.contains('\u{10000}'), Ok(false));
+        assert_eq!(
+            GlyphCache::<1, 7>::new().draw_string(
+                &mut source,
+                "a",
+                Point::zero(),
+                BinaryColor::On,
+                &mut display,
+            ),
+            Err(CacheError::TooLarge { c: 'a', bytes: 8 })
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::placement::Rotation;
+
+/// Pixel rows of the 3x5 hex digits, three bits per row with the top row first.
+const DIGITS: [u16; 16] = [
+    0b111_101_101_101_111, // 0
+    0b010_110_010_010_111, // 1
+    0b111_001_111_100_111, // 2
+    0b111_001_111_001_111, // 3
+    0b101_101_111_001_001, // 4
+    0b111_100_111_001_111, // 5
+    0b111_100_111_101_111, // 6
+    0b111_001_001_001_001, // 7
+    0b111_101_111_101_111, // 8
+    0b111_101_111_001_111, // 9
+    0b111_101_111_101_101, // A
+    0b110_101_110_101_110, // B
+    0b111_100_100_100_111, // C
+    0b110_101_101_101_110, // D
+    0b111_100_111_100_111, // E
+    0b111_100_111_100_100, // F
+];
+
+const DIGIT_WIDTH: u32 = 3;
+const DIGIT_HEIGHT: u32 = 5;
+/// Width of the frame plus the padding between the frame and the digits.
+const BORDER: u32 = 2;
+
+/// A box containing the hexadecimal code point of a character, drawn in place of glyphs
+/// that are missing from the font.
+///
+/// The code point is written with a built-in 3x5 pixel micro-font in a grid of two rows
+/// and two columns, or three columns for code points above U+FFFF. Boxes for short fonts
+/// write the digits in a single row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexBox {
+    code_point: u32,
+    rows: u32,
+}
+
+impl HexBox {
+    /// Creates a box with two rows of digits, which is 15 pixels high.
+    pub const fn new(c: char) -> Self {
+        Self {
+            code_point: c as u32,
+            rows: 2,
+        }
+    }
+
+    /// Creates a box with two rows of digits if it is at most `height` pixels high, and
+    /// with a single row, which is 9 pixels high, otherwise.
+    pub const fn fitting(c: char, height: u32) -> Self {
+        let hex_box = Self::new(c);
+        if hex_box.size().height <= height {
+            return hex_box;
+        }
+
+        Self { rows: 1, ..hex_box }
+    }
+
+    const fn columns(&self) -> u32 {
+        let digits = if self.code_point > 0xFFFF { 6 } else { 4 };
+
+        digits / self.rows
+    }
+
+    /// Returns the size of the box.
+    pub const fn size(&self) -> Size {
+        Size::new(
+            self.columns() * (DIGIT_WIDTH + 1) - 1 + 2 * BORDER,
+            self.rows * (DIGIT_HEIGHT + 1) - 1 + 2 * BORDER,
+        )
+    }
+
+    /// Returns the distance from the origin of the box to the origin of the next glyph.
+    pub const fn advance(&self) -> i32 {
+        self.size().width as i32 + 1
+    }
+
+    /// Returns the area covered by the box if it is drawn with its origin at `position`.
+    ///
+    /// The bottom edge of the box sits on the baseline.
+    pub fn bounding_box(&self, position: Point) -> Rectangle {
+        let size = self.size();
+
+        Rectangle::new(position - Point::new(0, size.height as i32), size)
+    }
+
+    /// Draws the box with its origin on the baseline at `position`.
+    pub fn draw<D>(&self, position: Point, color: D::Color, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+    {
+        self.draw_rotated(position, Rotation::None, color, target)
+    }
+
+    /// Draws the box rotated by `rotation` around its origin at `position`.
+    pub fn draw_rotated<D>(
+        &self,
+        position: Point,
+        rotation: Rotation,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+    {
+        let area = self.bounding_box(Point::zero());
+        let pixels = area
+            .points()
+            .filter(|p| self.pixel(*p - area.top_left))
+            .map(|p| Pixel(position + rotation.apply_to_pixel(p), color));
+
+        target.draw_iter(pixels)
+    }
+
+    /// Returns whether the pixel at `point`, relative to the top left corner, is set.
+    fn pixel(&self, point: Point) -> bool {
+        let size = self.size();
+        let (x, y) = (point.x as u32, point.y as u32);
+
+        if x == 0 || y == 0 || x == size.width - 1 || y == size.height - 1 {
+            return true;
+        }
+
+        let (Some(x), Some(y)) = (x.checked_sub(BORDER), y.checked_sub(BORDER)) else {
+            return false;
+        };
+        let (column, x) = (x / (DIGIT_WIDTH + 1), x % (DIGIT_WIDTH + 1));
+        let (row, y) = (y / (DIGIT_HEIGHT + 1), y % (DIGIT_HEIGHT + 1));
+
+        if x >= DIGIT_WIDTH || y >= DIGIT_HEIGHT || column >= self.columns() || row >= self.rows {
+            return false;
+        }
+
+        let shift = 4 * (self.rows * self.columns() - 1 - (row * self.columns() + column));
+        let digit = DIGITS[(self.code_point >> shift) as usize & 0xF];
+        let bit = (DIGIT_HEIGHT - 1 - y) * DIGIT_WIDTH + (DIGIT_WIDTH - 1 - x);
+
+        digit & (1 << bit) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    #[test]
+    fn it_draws_the_code_point() {
+        let mut display = MockDisplay::new();
+
+        HexBox::new('\u{01AF}')
+            .draw(Point::new(0, 15), BinaryColor::On, &mut display)
+            .unwrap();
+
+        display.assert_pattern(&[
+            "###########",
+            "#         #",
+            "# ###  #  #",
+            "# # # ##  #",
+            "# # #  #  #",
+            "# # #  #  #",
+            "# ### ### #",
+            "#         #",
+            "# ### ### #",
+            "# # # #   #",
+            "# ### ### #",
+            "# # # #   #",
+            "# # # #   #",
+            "#         #",
+            "###########",
+        ]);
+    }
+
+    #[test]
+    fn it_widens_for_astral_code_points() {
+        assert_eq!(HexBox::new('A').size(), Size::new(11, 15));
+        assert_eq!(HexBox::new('\u{1F600}').size(), Size::new(15, 15));
+    }
+
+    #[test]
+    fn it_uses_one_row_for_short_fonts() {
+        assert_eq!(HexBox::fitting('A', 15), HexBox::new('A'));
+        assert_eq!(HexBox::fitting('A', 9).size(), Size::new(19, 9));
+        assert_eq!(HexBox::fitting('\u{1F600}', 9).size(), Size::new(27, 9));
+
+        let mut display = MockDisplay::new();
+        HexBox::fitting('\u{01AF}', 9)
+            .draw(Point::new(0, 9), BinaryColor::On, &mut display)
+            .unwrap();
+
+        display.assert_pattern(&[
+            "###################",
+            "#                 #",
+            "# ###  #  ### ### #",
+            "# # # ##  # # #   #",
+            "# # #  #  ### ### #",
+            "# # #  #  # # #   #",
+            "# ### ### # # #   #",
+            "#                 #",
+            "###################",
+        ]);
+    }
+}
@@ -0,0 +1,77 @@
+use embedded_graphics::{image::ImageRaw, pixelcolor::BinaryColor};
+
+use crate::{GlyphData, PcfGlyph};
+
+impl PcfGlyph {
+    /// Copies the glyph bitmap into `buffer` and returns it as an image, e.g. to draw icons
+    /// stored in a font with [`Image`](embedded_graphics::image::Image).
+    ///
+    /// The image covers the glyph bounding box, so it has to be drawn at the glyph origin
+    /// plus `bounding_box.top_left`. Returns `None` if the buffer is too small; rows are
+    /// padded to whole bytes, so `⌈w / 8⌉ * h` bytes are needed for a `w`x`h` glyph.
+    pub fn image<'b>(
+        &self,
+        data: &GlyphData,
+        buffer: &'b mut [u8],
+    ) -> Option<ImageRaw<'b, BinaryColor>> {
+        let width = self.bounding_box.size.width as usize;
+        let height = self.bounding_box.size.height as usize;
+        let stride = width.div_ceil(8);
+
+        let bitmap = buffer.get_mut(..stride * height)?;
+        bitmap.fill(0);
+
+        for y in 0..height {
+            for x in 0..width {
+                if data.bit(self.start_index + y * width + x) {
+                    bitmap[y * stride + x / 8] |= 0x80 >> (x % 8);
+                }
+            }
+        }
+
+        Some(ImageRaw::new(bitmap, width as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{include_pcf, text::PcfTextStyle, PcfFont};
+    use embedded_graphics::{
+        image::Image,
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        prelude::*,
+        text::{renderer::TextRenderer, Baseline},
+    };
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+
+    #[test]
+    fn it_draws_glyph_images_like_text() {
+        let glyph = FONT.glyph('7').unwrap();
+        let position = Point::new(2, 12);
+        let mut buffer = [0; 10];
+
+        let image = glyph.image(&FONT.data, &mut buffer).unwrap();
+        let mut display = MockDisplay::new();
+        Image::new(&image, position + glyph.bounding_box.top_left)
+            .draw(&mut display)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        PcfTextStyle::new(&FONT, BinaryColor::On)
+            .draw_string("7", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+
+        // images draw clear pixels as well
+        let mut set = MockDisplay::new();
+        for p in display.affected_area().points() {
+            if display.get_pixel(p) == Some(BinaryColor::On) {
+                Pixel(p, BinaryColor::On).draw(&mut set).unwrap();
+            }
+        }
+        set.assert_eq(&expected);
+
+        assert!(glyph.image(&FONT.data, &mut [0; 9]).is_none());
+    }
+}
@@ -0,0 +1,54 @@
+/// A pixel adjustment applied between two characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KerningPair {
+    pub left: char,
+    pub right: char,
+    pub adjustment: i8,
+}
+
+/// A table of kerning pairs.
+///
+/// PCF fonts don't contain kerning information, but a few pairs (e.g. `AV`, `To` or `r.`)
+/// improve the look of proportional fonts considerably. Tables can be built from a text
+/// file at compile time with [`include_kerning!`](crate::include_kerning).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KerningTable<'a> {
+    pairs: &'a [KerningPair],
+}
+
+impl<'a> KerningTable<'a> {
+    /// Creates a kerning table.
+    ///
+    /// The pairs must be sorted by `left` and then by `right`.
+    pub const fn new(pairs: &'a [KerningPair]) -> Self {
+        Self { pairs }
+    }
+
+    /// Returns the pairs in the table.
+    pub const fn pairs(&self) -> &'a [KerningPair] {
+        self.pairs
+    }
+
+    /// Returns the adjustment between `left` and `right` in pixels.
+    pub fn adjustment(&self, left: char, right: char) -> i32 {
+        self.pairs
+            .binary_search_by_key(&(left, right), |pair| (pair.left, pair.right))
+            .map(|index| self.pairs[index].adjustment.into())
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::include_kerning;
+
+    #[test]
+    fn it_looks_up_pairs() {
+        let table = include_kerning!("examples/kerning.txt");
+
+        assert_eq!(table.adjustment('A', 'V'), -1);
+        assert_eq!(table.adjustment('T', 'o'), -2);
+        assert_eq!(table.adjustment('V', 'A'), -1);
+        assert_eq!(table.adjustment('A', 'A'), 0);
+    }
+}
@@ -0,0 +1,346 @@
+use embedded_graphics::{
+    prelude::*,
+    primitives::Rectangle,
+    text::{renderer::TextRenderer, Baseline},
+};
+
+use crate::{
+    shaping::{PassThrough, Shaper},
+    text::PcfTextStyle,
+};
+
+/// A single line of text that only redraws the part that changed between updates.
+///
+/// The label keeps a copy of the last rendered string (up to `N` bytes) and, on
+/// [`update`](Label::update), redraws only the changed region with [`draw_diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Label<'a, C, const N: usize, S = PassThrough> {
+    style: PcfTextStyle<'a, C, S>,
+    background: C,
+    position: Point,
+    text: [u8; N],
+    len: usize,
+    bounding_box: Rectangle,
+}
+
+impl<'a, C: PixelColor, const N: usize, S: Shaper> Label<'a, C, N, S> {
+    pub fn new(style: PcfTextStyle<'a, C, S>, background: C, position: Point) -> Self {
+        Self {
+            style,
+            background,
+            position,
+            text: [0; N],
+            len: 0,
+            bounding_box: Rectangle::new(position, Size::zero()),
+        }
+    }
+
+    /// Returns the last rendered text.
+    pub fn text(&self) -> &str {
+        core::str::from_utf8(&self.text[..self.len]).unwrap_or_default()
+    }
+
+    /// Returns the area covered by the last rendered text.
+    pub fn bounding_box(&self) -> Rectangle {
+        self.bounding_box
+    }
+
+    /// Replaces the label text and redraws the changed region.
+    ///
+    /// Text longer than `N` bytes is truncated at a character boundary. Returns the area
+    /// that was cleared and redrawn, which is empty if the text didn't change.
+    pub fn update<D>(&mut self, text: &str, target: &mut D) -> Result<Rectangle, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let text = truncate(text, N);
+        let old = self.text;
+        let old = core::str::from_utf8(&old[..self.len]).unwrap_or_default();
+
+        let dirty = draw_diff(
+            old,
+            text,
+            self.position,
+            &self.style,
+            self.background,
+            target,
+        )?;
+
+        self.text[..text.len()].copy_from_slice(text.as_bytes());
+        self.len = text.len();
+        self.bounding_box = self
+            .style
+            .measure_string(text, self.position, Baseline::Alphabetic)
+            .bounding_box;
+
+        Ok(dirty)
+    }
+}
+
+/// Redraws the part of a line of text that differs between `old` and `new`.
+///
+/// `old` must be the text that was previously drawn at `position` with the same style.
+/// The common prefix is skipped and, if the changed span keeps its width, so is the
+/// common suffix. The remaining region is cleared with `background` and redrawn.
+/// Returns the redrawn area, which is empty if the strings are equal.
+///
+/// Styles whose glyphs depend on their neighbours, like kerning or ligatures, or that
+/// don't draw left to right redraw the whole text when it changes.
+pub fn draw_diff<C, S, D>(
+    old: &str,
+    new: &str,
+    position: Point,
+    style: &PcfTextStyle<'_, C, S>,
+    background: C,
+    target: &mut D,
+) -> Result<Rectangle, D::Error>
+where
+    C: PixelColor,
+    S: Shaper,
+    D: DrawTarget<Color = C>,
+{
+    if old == new {
+        return Ok(Rectangle::new(position, Size::zero()));
+    }
+    if style.is_contextual(old) || style.is_contextual(new) {
+        return draw_all(old, new, position, style, background, target);
+    }
+
+    let prefix = common_prefix(old, new);
+    let suffix = common_suffix(&old[prefix..], &new[prefix..]);
+
+    // the x offsets of the start and the end of the changed span and of the end of the text,
+    // measured over the whole text so that the letter spacing after a span is included
+    let offsets = |text: &str| {
+        let changed = text[prefix..text.len() - suffix].chars().count();
+        let mut advances = style.measure_chars(text).map(|(_, advance)| advance);
+        let start: i32 = advances.by_ref().take(text[..prefix].chars().count()).sum();
+        let changed_end = start + advances.by_ref().take(changed).sum::<i32>();
+
+        (start, changed_end, changed_end + advances.sum::<i32>())
+    };
+    let (old_start, old_changed_end, old_end) = offsets(old);
+    let (start, new_changed_end, new_end) = offsets(new);
+
+    let (redraw, dirty_start, dirty_end) =
+        if (old_start, old_changed_end) == (start, new_changed_end) {
+            (&new[prefix..new.len() - suffix], start, new_changed_end)
+        } else {
+            (&new[prefix..], start.min(old_start), new_end.max(old_end))
+        };
+
+    let font_box = style.font_box();
+    let dirty = Rectangle::new(
+        Point::new(position.x + dirty_start, position.y + font_box.top_left.y),
+        Size::new(
+            (dirty_end - dirty_start).max(0) as u32,
+            font_box.size.height,
+        ),
+    );
+
+    if dirty.is_zero_sized() {
+        return Ok(dirty);
+    }
+
+    target.fill_solid(&dirty, background)?;
+    style.draw_string(
+        redraw,
+        position + Point::new(start, 0),
+        Baseline::Alphabetic,
+        target,
+    )?;
+
+    Ok(dirty)
+}
+
+/// Clears the area of `old` and draws `new`, returning the redrawn area.
+fn draw_all<C, S, D>(
+    old: &str,
+    new: &str,
+    position: Point,
+    style: &PcfTextStyle<'_, C, S>,
+    background: C,
+    target: &mut D,
+) -> Result<Rectangle, D::Error>
+where
+    C: PixelColor,
+    S: Shaper,
+    D: DrawTarget<Color = C>,
+{
+    let bounding_box = |text| {
+        style
+            .measure_string(text, position, Baseline::Alphabetic)
+            .bounding_box
+    };
+    let (old_box, new_box) = (bounding_box(old), bounding_box(new));
+    let dirty = match (old_box.bottom_right(), new_box.bottom_right()) {
+        (Some(old_corner), Some(new_corner)) => Rectangle::with_corners(
+            old_box.top_left.component_min(new_box.top_left),
+            old_corner.component_max(new_corner),
+        ),
+        (Some(_), None) => old_box,
+        (None, _) => new_box,
+    };
+
+    if dirty.is_zero_sized() {
+        return Ok(dirty);
+    }
+
+    target.fill_solid(&dirty, background)?;
+    style.draw_string(new, position, Baseline::Alphabetic, target)?;
+
+    Ok(dirty)
+}
+
+/// Returns the longest prefix of `text` that fits into `len` bytes.
+fn truncate(text: &str, len: usize) -> &str {
+    if text.len() <= len {
+        return text;
+    }
+
+    let end = (0..=len)
+        .rev()
+        .find(|i| text.is_char_boundary(*i))
+        .unwrap_or(0);
+
+    &text[..end]
+}
+
+/// Returns the byte length of the common prefix of two strings.
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((i, _), _)| i)
+        .unwrap_or_else(|| a.len().min(b.len()))
+}
+
+/// Returns the byte length of the common suffix of two strings.
+fn common_suffix(a: &str, b: &str) -> usize {
+    a.chars()
+        .rev()
+        .zip(b.chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        include_pcf,
+        kerning::{KerningPair, KerningTable},
+        PcfFont,
+    };
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+
+    #[test]
+    fn test_common_prefix_and_suffix() {
+        assert_eq!(common_prefix("12.5", "12.7"), 3);
+        assert_eq!(common_prefix("ab", "abc"), 2);
+        assert_eq!(common_suffix("10°C", "9°C"), "°C".len());
+        assert_eq!(common_suffix("", "abc"), 0);
+    }
+
+    #[test]
+    fn test_truncate_at_char_boundary() {
+        assert_eq!(truncate("°C", 1), "");
+        assert_eq!(truncate("a°C", 3), "a°");
+        assert_eq!(truncate("abc", 8), "abc");
+    }
+
+    #[test]
+    fn it_draws_the_difference() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut display = MockDisplay::new();
+        let position = Point::new(0, 20);
+
+        style
+            .draw_string("42", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        let mut expected = display.clone();
+        display.set_allow_overdraw(true);
+
+        let dirty =
+            draw_diff("42", "43", position, &style, BinaryColor::Off, &mut display).unwrap();
+        assert_eq!(dirty.top_left.x, 6);
+
+        expected.set_allow_overdraw(true);
+        expected.fill_solid(&dirty, BinaryColor::Off).unwrap();
+        style
+            .draw_string("3", Point::new(6, 20), Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn it_only_redraws_changed_region() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut label = Label::<_, 8>::new(style, BinaryColor::Off, Point::new(0, 20));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+
+        let dirty = label.update("123", &mut display).unwrap();
+        assert_eq!(dirty.top_left.x, 0);
+        assert_eq!(dirty.size.width, 18);
+
+        let dirty = label.update("124", &mut display).unwrap();
+        assert_eq!(dirty.top_left.x, 12);
+        assert_eq!(dirty.size.width, 6);
+
+        let dirty = label.update("124", &mut display).unwrap();
+        assert_eq!(dirty.size, Size::zero());
+
+        let dirty = label.update("14", &mut display).unwrap();
+        assert_eq!(dirty.top_left.x, 6);
+        assert_eq!(dirty.size.width, 12);
+        assert_eq!(label.text(), "14");
+    }
+
+    #[test]
+    fn it_measures_the_changed_span_with_letter_spacing() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On).with_letter_spacing(2);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let position = Point::new(0, 20);
+
+        let dirty = draw_diff("4", "42", position, &style, BinaryColor::Off, &mut display);
+        assert_eq!(dirty.unwrap().top_left.x, 6);
+
+        let dirty = draw_diff("42", "43", position, &style, BinaryColor::Off, &mut display);
+        assert_eq!(dirty.unwrap().top_left.x, 8);
+    }
+
+    #[test]
+    fn it_redraws_kerned_text_whole() {
+        const PAIRS: &[KerningPair] = &[KerningPair {
+            left: '4',
+            right: '2',
+            adjustment: -2,
+        }];
+        let style =
+            PcfTextStyle::new(&FONT, BinaryColor::On).with_kerning(KerningTable::new(PAIRS));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let position = Point::new(0, 20);
+
+        style
+            .draw_string("43", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        let dirty =
+            draw_diff("43", "42", position, &style, BinaryColor::Off, &mut display).unwrap();
+        assert_eq!(dirty.top_left.x, 0);
+        assert_eq!(dirty.size.width, 12);
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        expected.fill_solid(&dirty, BinaryColor::Off).unwrap();
+        style
+            .draw_string("42", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+}
@@ -0,0 +1,386 @@
+use embedded_graphics::{
+    prelude::*,
+    primitives::Rectangle,
+    text::{renderer::TextRenderer, Alignment, Baseline},
+};
+
+use crate::{shaping::Shaper, text::PcfTextStyle};
+
+/// The reason a line of text ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BreakKind {
+    /// The line ended with a `\n`.
+    Newline,
+    /// The line was wrapped at whitespace to fit the maximum width.
+    Wrap,
+    /// A word was hyphenated. The hyphen isn't part of the run text, but of its width.
+    Hyphen,
+    /// A word didn't fit the maximum width on its own and was broken between characters.
+    Forced,
+    /// The line is the end of the text.
+    End,
+}
+
+/// A line of laid out text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Run<'t> {
+    /// The text of the line, without the line break and the whitespace it replaced.
+    pub text: &'t str,
+    /// The advance width of the text in pixels.
+    pub width: u32,
+    pub break_kind: BreakKind,
+}
+
+impl Run<'_> {
+    /// Returns the x coordinate the run starts at when it is aligned to `x`.
+    pub fn start(&self, x: i32, alignment: Alignment) -> i32 {
+        aligned_start(x, self.width, alignment)
+    }
+}
+
+fn aligned_start(x: i32, width: u32, alignment: Alignment) -> i32 {
+    match alignment {
+        Alignment::Left => x,
+        Alignment::Center => x - (width / 2) as i32,
+        Alignment::Right => x - width as i32,
+    }
+}
+
+/// A grid of horizontal lines that baselines are snapped to, to keep text blocks in
+/// different fonts vertically aligned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BaselineGrid {
+    /// Distance between grid lines in pixels.
+    pub pitch: u32,
+    /// Y coordinate of one of the grid lines.
+    pub origin: i32,
+}
+
+impl BaselineGrid {
+    /// Creates a grid with lines at multiples of `pitch`. A pitch of 0 is treated as 1.
+    pub const fn new(pitch: u32) -> Self {
+        Self {
+            pitch: if pitch == 0 { 1 } else { pitch },
+            origin: 0,
+        }
+    }
+
+    pub const fn with_origin(mut self, origin: i32) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Returns the first grid line at or below `y`.
+    pub const fn snap(&self, y: i32) -> i32 {
+        let pitch = self.pitch as i32;
+        let offset = (y - self.origin).rem_euclid(pitch);
+
+        if offset == 0 {
+            y
+        } else {
+            y + pitch - offset
+        }
+    }
+}
+
+/// Returns whether a line of `text` may be hyphenated before byte `index`.
+pub type Hyphenator = fn(text: &str, index: usize) -> bool;
+
+const SOFT_HYPHEN: char = '\u{ad}';
+
+/// The default [`Hyphenator`], which allows breaks after soft hyphens (U+00AD).
+///
+/// Soft hyphens in the middle of a line are left in the run text, so fonts without a soft
+/// hyphen glyph should drop them with a [`Shaper`].
+pub fn soft_hyphens(text: &str, index: usize) -> bool {
+    text[..index].ends_with(SOFT_HYPHEN)
+}
+
+/// Splits text into lines that fit `max_width`.
+///
+/// This is a shorthand for [`LineBreaks`] that drops the break kinds, so lines broken at a
+/// hyphenation point lack the `-`.
+pub fn wrap_text<'a, 't: 'a, C: PixelColor, S: Shaper>(
+    style: &'a PcfTextStyle<'a, C, S>,
+    text: &'t str,
+    max_width: u32,
+) -> impl Iterator<Item = &'t str> + 'a {
+    LineBreaks::new(style, text, max_width).map(|run| run.text)
+}
+
+/// Returns the area covered by text wrapped to `max_width`, with the first baseline at
+/// `position` and every line aligned to `position.x`.
+///
+/// Lines are one line height apart and the box spans the font bounding box of the first and
+/// last line, so it covers all glyphs regardless of the characters in the text.
+pub fn measure_text_box<C: PixelColor, S: Shaper>(
+    style: &PcfTextStyle<'_, C, S>,
+    text: &str,
+    max_width: u32,
+    position: Point,
+    alignment: Alignment,
+) -> Rectangle {
+    let (lines, width) = LineBreaks::new(style, text, max_width)
+        .fold((0, 0), |(lines, width), run| {
+            (lines + 1, width.max(run.width))
+        });
+
+    let font_box = style.font_box();
+
+    Rectangle::new(
+        Point::new(
+            aligned_start(position.x, width, alignment),
+            position.y + font_box.top_left.y,
+        ),
+        Size::new(
+            width,
+            (lines - 1) * style.line_height() + font_box.size.height,
+        ),
+    )
+}
+
+/// An iterator that splits text into lines that fit a maximum width.
+///
+/// Nothing is drawn, so widgets can position the runs themselves while sharing the
+/// measurement logic, including kerning and ligatures, with [`PcfTextStyle`]. Runs with
+/// [`BreakKind::Hyphen`] must be followed by a `-`.
+#[derive(Debug, Clone)]
+pub struct LineBreaks<'a, 't, C, S> {
+    style: &'a PcfTextStyle<'a, C, S>,
+    max_width: u32,
+    hyphenate: Hyphenator,
+    rest: Option<&'t str>,
+}
+
+impl<'a, 't, C: PixelColor, S: Shaper> LineBreaks<'a, 't, C, S> {
+    pub fn new(style: &'a PcfTextStyle<'a, C, S>, text: &'t str, max_width: u32) -> Self {
+        Self {
+            style,
+            max_width,
+            hyphenate: soft_hyphens,
+            rest: Some(text),
+        }
+    }
+
+    /// Uses `hyphenate` to find breaks inside words that don't fit, instead of
+    /// [`soft_hyphens`].
+    pub fn with_hyphenation(mut self, hyphenate: Hyphenator) -> Self {
+        self.hyphenate = hyphenate;
+        self
+    }
+
+    /// Positions the runs on consecutive baselines starting at `position`.
+    ///
+    /// Each baseline is one line height below the previous one and snapped down to `grid`,
+    /// including the first one.
+    pub fn positioned(
+        self,
+        position: Point,
+        grid: BaselineGrid,
+    ) -> impl Iterator<Item = (Point, Run<'t>)> + 'a
+    where
+        't: 'a,
+    {
+        let line_height = self.style.line_height() as i32;
+        let mut y = position.y;
+
+        self.map(move |run| {
+            let baseline = grid.snap(y);
+            y = baseline + line_height;
+
+            (Point::new(position.x, baseline), run)
+        })
+    }
+
+    fn width(&self, text: &str) -> i32 {
+        self.style
+            .measure_string(text, Point::zero(), Baseline::Alphabetic)
+            .next_position
+            .x
+    }
+
+    fn run(&self, text: &'t str, break_kind: BreakKind) -> Run<'t> {
+        Run {
+            text,
+            width: self.width(text).max(0) as u32,
+            break_kind,
+        }
+    }
+
+    /// Returns the width of a hyphenated run.
+    fn hyphenated_width(&self, text: &str) -> i32 {
+        self.width(text.trim_end_matches(SOFT_HYPHEN)) + self.width("-")
+    }
+}
+
+impl<'t, C: PixelColor, S: Shaper> Iterator for LineBreaks<'_, 't, C, S> {
+    type Item = Run<'t>;
+
+    fn next(&mut self) -> Option<Run<'t>> {
+        let text = self.rest?;
+
+        let (line, next) = match text.split_once('\n') {
+            Some((line, next)) => (line, Some(next)),
+            None => (text, None),
+        };
+
+        if self.width(line) <= self.max_width as i32 {
+            self.rest = next;
+            let break_kind = next.map_or(BreakKind::End, |_| BreakKind::Newline);
+
+            return Some(self.run(line, break_kind));
+        }
+
+        let mut fits = 0;
+        let mut space = None;
+        let mut hyphen = None;
+        for (i, c) in line.char_indices().skip(1).chain([(line.len(), '\0')]) {
+            if (self.hyphenate)(line, i)
+                && self.hyphenated_width(&line[..i]) <= self.max_width as i32
+            {
+                hyphen = Some(i);
+            }
+            if self.width(&line[..i]) > self.max_width as i32 {
+                break;
+            }
+            fits = i;
+            if c.is_whitespace() {
+                space = Some(i);
+            }
+        }
+
+        let (end, break_kind) = match (space, hyphen) {
+            (space, Some(hyphen)) if space < Some(hyphen) => {
+                let (run, rest) = text.split_at(hyphen);
+                self.rest = (!rest.is_empty()).then_some(rest);
+
+                let run = run.trim_end_matches(SOFT_HYPHEN);
+                return Some(Run {
+                    text: run,
+                    width: self.hyphenated_width(run).max(0) as u32,
+                    break_kind: BreakKind::Hyphen,
+                });
+            }
+            (Some(space), _) => (space, BreakKind::Wrap),
+            // always make progress, even if the first character doesn't fit
+            (None, _) if fits == 0 => (
+                line.chars().next().map_or(0, char::len_utf8),
+                BreakKind::Forced,
+            ),
+            (None, _) => (fits, BreakKind::Forced),
+        };
+
+        let (run, mut rest) = text.split_at(end);
+        if break_kind == BreakKind::Wrap {
+            rest = rest.trim_start_matches(|c: char| c != '\n' && c.is_whitespace());
+        }
+        self.rest = (!rest.is_empty()).then_some(rest);
+
+        Some(self.run(run.trim_end(), break_kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, PcfFont};
+    use embedded_graphics::pixelcolor::BinaryColor;
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+
+    fn lines(
+        text: &str,
+        max_width: u32,
+        hyphenate: Hyphenator,
+    ) -> ([Option<(&str, BreakKind)>; 4], usize) {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut lines = [None; 4];
+        let mut count = 0;
+
+        for (line, run) in lines
+            .iter_mut()
+            .zip(LineBreaks::new(&style, text, max_width).with_hyphenation(hyphenate))
+        {
+            let hyphen = u32::from(run.break_kind == BreakKind::Hyphen);
+            assert_eq!(run.width, 6 * (run.text.chars().count() as u32 + hyphen));
+            *line = Some((run.text, run.break_kind));
+            count += 1;
+        }
+
+        (lines, count)
+    }
+
+    #[test]
+    fn it_wraps_at_whitespace() {
+        let (lines, count) = lines("ab cd ef", 30, soft_hyphens);
+
+        assert_eq!(count, 2);
+        assert_eq!(lines[0], Some(("ab cd", BreakKind::Wrap)));
+        assert_eq!(lines[1], Some(("ef", BreakKind::End)));
+    }
+
+    #[test]
+    fn it_breaks_long_words() {
+        let (lines, count) = lines("abcdefg\nh", 18, soft_hyphens);
+
+        assert_eq!(count, 4);
+        assert_eq!(lines[0], Some(("abc", BreakKind::Forced)));
+        assert_eq!(lines[1], Some(("def", BreakKind::Forced)));
+        assert_eq!(lines[2], Some(("g", BreakKind::Newline)));
+        assert_eq!(lines[3], Some(("h", BreakKind::End)));
+    }
+
+    #[test]
+    fn it_snaps_baselines_to_the_grid() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let grid = BaselineGrid::new(8).with_origin(2);
+        let mut lines = LineBreaks::new(&style, "a\nb", 30).positioned(Point::new(5, 3), grid);
+
+        assert_eq!(grid.snap(-6), -6);
+        assert_eq!(lines.next().unwrap().0, Point::new(5, 10));
+        assert_eq!(lines.next().unwrap().0, Point::new(5, 26));
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn it_measures_wrapped_text() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut lines = wrap_text(&style, "ab cd ef", 30);
+
+        assert_eq!(lines.next(), Some("ab cd"));
+        assert_eq!(lines.next(), Some("ef"));
+        assert_eq!(lines.next(), None);
+
+        let position = Point::new(50, 20);
+        let font_box = FONT.bounding_box;
+        let left = measure_text_box(&style, "ab cd ef", 30, position, Alignment::Left);
+        assert_eq!(left.top_left, position + Point::new(0, font_box.top_left.y));
+        assert_eq!(
+            left.size,
+            Size::new(30, style.line_height() + font_box.size.height)
+        );
+
+        let right = measure_text_box(&style, "ab cd ef", 30, position, Alignment::Right);
+        assert_eq!(right.top_left.x, 20);
+        let center = measure_text_box(&style, "ab cd ef", 30, position, Alignment::Center);
+        assert_eq!(center.top_left.x, 35);
+    }
+
+    #[test]
+    fn it_breaks_at_soft_hyphens() {
+        let (lines, count) = lines("ab cd\u{ad}ef", 36, soft_hyphens);
+
+        assert_eq!(count, 2);
+        assert_eq!(lines[0], Some(("ab cd", BreakKind::Hyphen)));
+        assert_eq!(lines[1], Some(("ef", BreakKind::End)));
+    }
+
+    #[test]
+    fn it_calls_the_hyphenator() {
+        let (lines, count) = lines("abcdef", 30, |text, i| text[..i].ends_with("abc"));
+
+        assert_eq!(count, 2);
+        assert_eq!(lines[0], Some(("abc", BreakKind::Hyphen)));
+        assert_eq!(lines[1], Some(("def", BreakKind::End)));
+    }
+}
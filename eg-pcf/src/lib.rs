@@ -1,73 +1,582 @@
 #![no_std]
 
-use embedded_graphics::{
-    iterator::raw::RawDataSlice,
-    pixelcolor::raw::{LittleEndian, RawU1},
-    prelude::*,
-    primitives::Rectangle,
+#[cfg(any(feature = "alloc", feature = "unicode-bidi"))]
+extern crate alloc;
+
+use codepage::Codepage;
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use metrics::FontMetrics;
+use placement::Rotation;
+use script::ScriptOffsets;
+use size::DesignSize;
+
+pub use data::GlyphData;
+pub use eg_pcf_macros::{
+    include_kerning, include_pcf, include_pcf_family, include_pcf_for_strings,
 };
 
-pub use eg_pcf_macros::include_pcf;
+#[macro_use]
+mod fmt;
 
+pub mod ascii;
+pub mod buf;
+pub mod buffer;
+pub mod codepage;
+pub mod console;
+pub mod cursor;
+mod data;
+pub mod decoration;
+pub mod dirty;
+pub mod effect;
+pub mod family;
+pub mod fit;
+pub mod fixed;
+pub mod framebuffer;
+pub mod glyph_cache;
+pub mod hexbox;
+mod image;
+pub mod kerning;
+pub mod label;
+pub mod layout;
+pub mod ligature;
+pub mod metrics;
+pub mod oriented;
+pub mod placement;
+#[cfg(feature = "raw")]
+pub mod raw;
+pub mod renderer;
+pub mod rotate;
+pub mod scaled;
+pub mod script;
+pub mod shaping;
+pub mod size;
+#[cfg(feature = "test_util")]
+pub mod test_util;
 pub mod text;
+pub mod writer;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PcfFont<'a> {
     pub bounding_box: Rectangle,
     pub replacement_character: usize,
     pub line_height: u32,
+    /// The glyphs sorted by character.
     pub glyphs: &'a [PcfGlyph],
-    pub data: &'a [u8],
+    pub data: GlyphData<'a>,
+    /// The size the font was designed for, if the font has the required properties.
+    pub design_size: Option<DesignSize>,
+    pub script_offsets: ScriptOffsets,
+    pub metrics: FontMetrics,
+    /// The advance of every glyph, if the font is a constant width terminal font. Text in
+    /// these fonts is measured without looking up glyphs.
+    pub monospace_advance: Option<i32>,
+    /// The encoding of the glyph characters.
+    pub codepage: Codepage,
 }
 
 impl<'a> PcfFont<'a> {
+    /// Creates a Unicode font without design size, script offsets and monospace advance, and
+    /// with the metrics of the bounding box.
+    ///
+    /// Fonts are usually created with [`include_pcf!`], but this allows building them from
+    /// other sources in `const` contexts.
+    ///
+    /// `glyphs` must be sorted by character, otherwise lookups may miss glyphs.
+    pub const fn new(
+        bounding_box: Rectangle,
+        replacement_character: usize,
+        line_height: u32,
+        glyphs: &'a [PcfGlyph],
+        data: GlyphData<'a>,
+    ) -> Self {
+        Self {
+            bounding_box,
+            replacement_character,
+            line_height,
+            glyphs,
+            data,
+            design_size: None,
+            script_offsets: ScriptOffsets {
+                superscript: 0,
+                subscript: 0,
+            },
+            metrics: FontMetrics::from_bounding_box(&bounding_box),
+            monospace_advance: None,
+            codepage: Codepage::Unicode,
+        }
+    }
+
+    /// Returns the number of glyphs in the font.
+    pub const fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Returns the size of the glyph bitmap data in bytes.
+    pub const fn data_bytes(&self) -> usize {
+        self.data.len_bytes()
+    }
+
+    /// Returns the memory used by the glyph table and the glyph bitmap data in bytes.
+    ///
+    /// This can be checked at compile time, e.g. `const _: () = assert!(FONT.total_bytes() <= 4096);`.
+    pub const fn total_bytes(&self) -> usize {
+        self.glyph_count() * core::mem::size_of::<PcfGlyph>() + self.data_bytes()
+    }
+
+    /// Returns the characters of all glyphs in ascending order.
+    ///
+    /// The characters are code points of the font [`codepage`](Self::codepage), which are
+    /// only Unicode characters for Unicode fonts.
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        self.glyphs.iter().map(|glyph| glyph.character)
+    }
+
+    /// Returns whether the font has a glyph for `c`, without falling back to the replacement
+    /// character.
+    pub fn contains(&self, c: char) -> bool {
+        self.glyph(c).is_some()
+    }
+
+    /// Returns the font with the glyph for `c` drawn in place of characters the font has no
+    /// glyph for.
+    ///
+    /// The replacement character is left unchanged if the font has no glyph for `c`.
+    pub fn with_replacement(mut self, c: char) -> Self {
+        let index = self
+            .codepage
+            .encode(c)
+            .and_then(char::from_u32)
+            .and_then(|c| self.glyphs.binary_search_by_key(&c, |g| g.character).ok());
+        if let Some(index) = index {
+            self.replacement_character = index;
+        }
+
+        self
+    }
+
     fn get_glyph(&self, c: char) -> &'a PcfGlyph {
-        self.glyphs
-            .iter()
-            .find(|g| g.character == c)
+        self.glyph(c)
             .unwrap_or_else(|| &self.glyphs[self.replacement_character])
     }
+
+    pub(crate) fn glyph(&self, c: char) -> Option<&'a PcfGlyph> {
+        let c = char::from_u32(self.codepage.encode(c)?)?;
+
+        // fonts usually contain a contiguous range of ASCII characters, which can be indexed
+        // directly
+        if c.is_ascii() {
+            let first = self.glyphs.first()?.character;
+            let index = (c as usize).wrapping_sub(first as usize);
+            if let Some(glyph) = self.glyphs.get(index).filter(|g| g.character == c) {
+                return Some(glyph);
+            }
+        }
+
+        let index = self.glyphs.binary_search_by_key(&c, |g| g.character).ok()?;
+        Some(&self.glyphs[index])
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PcfGlyph {
     pub character: char,
     pub bounding_box: Rectangle,
-    pub device_width: u32,
+    pub device_width: i32,
     pub start_index: usize,
 }
 
 impl PcfGlyph {
+    pub const fn new(
+        character: char,
+        bounding_box: Rectangle,
+        device_width: i32,
+        start_index: usize,
+    ) -> Self {
+        Self {
+            character,
+            bounding_box,
+            device_width,
+            start_index,
+        }
+    }
+
+    /// Returns the set pixels of the glyph drawn with its origin at `position`, row by row.
+    ///
+    /// `data` is the glyph data of the font the glyph belongs to. This gives access to the
+    /// glyph shape without a `DrawTarget`, e.g. to draw outlines or shadows, or to write
+    /// into other kinds of buffers.
+    pub fn pixels<'d>(
+        &self,
+        position: Point,
+        data: &'d GlyphData<'d>,
+    ) -> impl Iterator<Item = Point> + 'd {
+        let Rectangle { top_left, size } = self.bounding_box;
+        let start_index = self.start_index;
+
+        (0..size.height).flat_map(move |y| {
+            (0..size.width).filter_map(move |x| {
+                let index = start_index + (y * size.width + x) as usize;
+                data.bit(index)
+                    .then(|| position + top_left + Point::new(x as i32, y as i32))
+            })
+        })
+    }
+
     fn draw<D: DrawTarget>(
         &self,
         position: Point,
+        rotation: Rotation,
         color: D::Color,
-        data: &[u8],
+        data: &GlyphData,
         target: &mut D,
     ) -> Result<(), D::Error> {
-        let mut data_iter = RawDataSlice::<RawU1, LittleEndian>::new(data).into_iter();
+        self.draw_sheared(position, rotation, 0, color, data, target)
+    }
+
+    /// Draws the glyph with rows shifted right by one pixel every `shear` rows above the
+    /// baseline, and left below it. A `shear` of 0 draws the glyph upright.
+    fn draw_sheared<D: DrawTarget>(
+        &self,
+        position: Point,
+        rotation: Rotation,
+        shear: u32,
+        color: D::Color,
+        data: &GlyphData,
+        target: &mut D,
+    ) -> Result<(), D::Error> {
+        let Size { width, height } = self.bounding_box.size;
+        let (width, height) = (width as usize, height as usize);
+
+        // runs of set pixels are filled as rectangles, which most displays can write in a
+        // single transfer instead of addressing every pixel
+        for y in 0..height {
+            let row = self.start_index + y * width;
+            // glyphs without descenders end two rows above the baseline position, their
+            // bottom row isn't shifted
+            let shift = match shear {
+                0 => 0,
+                shear => (-2 - self.bounding_box.top_left.y - y as i32).div_euclid(shear as i32),
+            };
+            let mut x = 0;
+            while x < width {
+                let start = x;
+                while x < width && data.bit(row + x) {
+                    x += 1;
+                }
+                if x == start {
+                    x += 1;
+                    continue;
+                }
 
-        if self.start_index > 0 {
-            data_iter.nth(self.start_index - 1);
+                let run = Rectangle::new(
+                    self.bounding_box.top_left + Point::new(start as i32 + shift, y as i32),
+                    Size::new((x - start) as u32, 1),
+                );
+                target.fill_solid(
+                    &rotation.apply_to_rectangle(&run).translate(position),
+                    color,
+                )?;
+            }
         }
 
-        self.bounding_box
-            .translate(position)
-            .points()
-            .zip(data_iter)
-            .filter(|(_p, c)| *c == RawU1::new(1))
-            .map(|(p, _c)| Pixel(p, color))
-            .draw(target)
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        text::{renderer::TextRenderer, Baseline},
+    };
+    use renderer::BitmapFontRenderer;
+    use text::PcfTextStyle;
 
     #[test]
     fn it_works() {
         let font = include_pcf!("examples/OpenSans-Regular-12.pcf", 'A'..='B');
         assert!(font.line_height == 12);
     }
+
+    #[test]
+    fn it_accounts_for_font_size() {
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9', max_bytes = 4096);
+        const _: () = assert!(FONT.glyph_count() == 10);
+        const _: () = assert!(FONT.total_bytes() <= 4096);
+
+        assert_eq!(FONT.data_bytes(), 75);
+    }
+
+    #[test]
+    fn it_aligns_word_data() {
+        let font = include_pcf!("examples/OpenSans-Regular-12.pcf", 'A'..='C', data = u32);
+
+        assert!(matches!(font.data, GlyphData::Words(_)));
+        assert!(font.glyphs.iter().all(|g| g.start_index % 32 == 0));
+    }
+
+    #[test]
+    fn it_finds_glyphs_in_sorted_fonts() {
+        let font = include_pcf!("examples/6x10.pcf", '\u{e9}' | 'a'..='c' | 'x' | '\u{2588}');
+
+        assert!(font
+            .glyphs
+            .windows(2)
+            .all(|g| g[0].character < g[1].character));
+        for c in ['a', 'b', 'c', 'x', '\u{e9}', '\u{2588}'] {
+            assert_eq!(font.glyph(c).map(|g| g.character), Some(c));
+        }
+        assert!(font.glyph('d').is_none());
+        assert!(font.glyph('\u{d7ff}').is_none());
+    }
+
+    #[test]
+    fn it_lists_included_characters() {
+        let font = include_pcf!("examples/6x10.pcf", 'x' | 'a'..='c' | '\u{2588}');
+
+        assert!(font.chars().eq(['a', 'b', 'c', 'x', '\u{2588}']));
+        assert!(font.contains('b'));
+        assert!(!font.contains('d'));
+    }
+
+    #[test]
+    fn it_uses_a_configurable_replacement_character() {
+        let font = include_pcf!("examples/6x10.pcf", 'a'..='c', default = '?');
+        assert!(font.chars().eq(['?', 'a', 'b', 'c']));
+        assert_eq!(font.get_glyph('z').character, '?');
+
+        // the default character of 6x10 is U+0000
+        let font = include_pcf!("examples/6x10.pcf", '\0' | ' '..='~');
+        assert_eq!(font.get_glyph('\u{E9}').character, '\0');
+
+        let font = font.with_replacement('x');
+        assert_eq!(font.get_glyph('\u{E9}').character, 'x');
+        let font = font.with_replacement('\u{E9}');
+        assert_eq!(font.get_glyph('\u{E9}').character, 'x');
+    }
+
+    #[test]
+    fn it_includes_compressed_fonts() {
+        const FONT: PcfFont = include_pcf!("examples/OpenSans-Regular-12.pcf");
+        let compressed = include_pcf!("../assets/OpenSans-Regular-12.pcf.gz");
+
+        assert_eq!(compressed, FONT);
+    }
+
+    #[test]
+    fn it_includes_the_characters_of_a_string() {
+        let font = include_pcf!("examples/6x10.pcf", chars = "0123456789.:°C%");
+        assert_eq!(font.glyph_count(), 15);
+        assert!(font.glyph('°').is_some());
+        assert!(font.glyph('F').is_none());
+
+        let font = include_pcf!("examples/6x10.pcf", 'A'..='Z', chars = "°%%");
+        assert_eq!(font.glyph_count(), 28);
+    }
+
+    #[test]
+    fn it_includes_the_characters_of_text_files() {
+        let font = include_pcf_for_strings!(
+            "examples/6x10.pcf",
+            "../assets/strings/de.txt",
+            "../assets/strings/en.txt",
+            chars = "?"
+        );
+
+        // "Temperatur: 21 °CEinstellungenÜberSettingsAbout?" without duplicates
+        assert!(font.chars().eq(" 12:?ACESTabegilmnoprstu°Ü".chars()));
+    }
+
+    #[test]
+    fn it_includes_subset_presets() {
+        let font = include_pcf!("examples/6x10.pcf", subset = ascii);
+        assert_eq!(font.glyph_count(), 95);
+
+        let font = include_pcf!("examples/6x10.pcf", subset = latin9);
+        assert!(font.glyph('\u{20AC}').is_some());
+        assert!(font.glyph('\u{A4}').is_none());
+
+        let font = include_pcf!("examples/6x10.pcf", '\u{2588}', subset = ascii | greek);
+        assert!(font.glyph('\u{3BB}').is_some());
+        assert!(font.glyph('\u{2588}').is_some());
+        assert!(font.glyph('\u{E9}').is_none());
+    }
+
+    #[test]
+    fn it_maps_text_to_the_font_codepage() {
+        let font = include_pcf!("examples/6x10.pcf", '\u{DB}', codepage = cp437);
+
+        assert_eq!(font.codepage, Codepage::Cp437);
+        assert_eq!(font.glyph('█').map(|g| g.character), Some('\u{DB}'));
+        assert!(font.glyph('\u{DB}').is_none());
+    }
+
+    static STYLE: PcfTextStyle<BinaryColor> = {
+        const GLYPHS: [PcfGlyph; 1] = [PcfGlyph::new(
+            'x',
+            Rectangle::new(Point::new(0, -2), Size::new(2, 2)),
+            3,
+            0,
+        )];
+        const FONT: PcfFont = PcfFont::new(
+            Rectangle::new(Point::new(0, -2), Size::new(2, 2)),
+            0,
+            3,
+            &GLYPHS,
+            GlyphData::Slice(&[0b1001_0000]),
+        );
+
+        PcfTextStyle::new(&FONT, BinaryColor::On).with_hex_fallback()
+    };
+
+    #[test]
+    fn it_constructs_fonts_in_const_contexts() {
+        let mut display = MockDisplay::new();
+        STYLE
+            .draw_string("x", Point::new(0, 2), Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        display.assert_pattern(&["# ", " #"]);
+    }
+
+    /// A draw target that counts writes, with every single pixel counting as one.
+    #[derive(Default)]
+    struct Fills {
+        count: u32,
+        pixels: u32,
+    }
+
+    impl OriginDimensions for Fills {
+        fn size(&self) -> Size {
+            Size::new(64, 64)
+        }
+    }
+
+    impl DrawTarget for Fills {
+        type Color = BinaryColor;
+        type Error = core::convert::Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            for _ in pixels {
+                self.count += 1;
+                self.pixels += 1;
+            }
+
+            Ok(())
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, _color: BinaryColor) -> Result<(), Self::Error> {
+            self.count += 1;
+            self.pixels += area.size.width * area.size.height;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn it_fills_runs_of_pixels() {
+        let font = include_pcf!("examples/6x10.pcf", '_');
+        let mut fills = Fills::default();
+
+        let glyph = font.glyph('_').unwrap();
+        glyph
+            .draw(
+                Point::zero(),
+                Rotation::None,
+                BinaryColor::On,
+                &font.data,
+                &mut fills,
+            )
+            .unwrap();
+        let size = glyph.bounding_box.size;
+        let set = (0..size.width * size.height)
+            .filter(|i| font.data.bit(glyph.start_index + *i as usize))
+            .count() as u32;
+        // the underscore is a single row
+        assert_eq!(fills.count, 1);
+        assert_eq!(fills.pixels, set);
+
+        // rotated runs are filled as columns
+        let mut fills = Fills::default();
+        glyph
+            .draw(
+                Point::zero(),
+                Rotation::Clockwise90,
+                BinaryColor::On,
+                &font.data,
+                &mut fills,
+            )
+            .unwrap();
+        assert_eq!((fills.count, fills.pixels), (1, set));
+    }
+
+    #[test]
+    fn it_includes_bdf_fonts() {
+        let bdf = include_pcf!("examples/6x10-digits.bdf");
+        let pcf = include_pcf!("examples/6x10.pcf", ' ' | '0'..='9');
+
+        assert_eq!(bdf.glyph_count(), pcf.glyph_count());
+        assert_eq!(bdf.bounding_box, pcf.bounding_box);
+        assert_eq!(bdf.design_size, pcf.design_size);
+
+        let draw = |font| {
+            let mut display = MockDisplay::new();
+            PcfTextStyle::new(font, BinaryColor::On)
+                .draw_string("0 9", Point::new(0, 8), Baseline::Alphabetic, &mut display)
+                .unwrap();
+            display
+        };
+        draw(&bdf).assert_eq(&draw(&pcf));
+    }
+
+    #[test]
+    fn it_scales_glyphs() {
+        let font = include_pcf!("examples/6x10.pcf", '0');
+        let scaled = include_pcf!("examples/6x10.pcf", '0', scale = 2, filter = scale_nx);
+
+        assert_eq!(scaled.line_height, 2 * font.line_height);
+        assert_eq!(scaled.glyphs[0].device_width, 12);
+        assert_eq!(
+            scaled.glyphs[0].bounding_box.size,
+            font.glyphs[0].bounding_box.size * 2
+        );
+    }
+
+    #[test]
+    fn it_scales_glyphs_non_uniformly() {
+        let font = include_pcf!("examples/6x10.pcf", '0');
+        let wide = include_pcf!("examples/6x10.pcf", '0', scale = (2, 1));
+
+        assert_eq!(wide.line_height, font.line_height);
+        assert_eq!(wide.glyphs[0].device_width, 12);
+        assert_eq!(
+            wide.glyphs[0].bounding_box.size,
+            font.glyphs[0]
+                .bounding_box
+                .size
+                .component_mul(Size::new(2, 1))
+        );
+    }
+
+    #[test]
+    fn it_iterates_the_set_pixels_of_a_glyph() {
+        let font = include_pcf!("examples/6x10.pcf", 'A'..='B');
+        let glyph = font.glyph('A').unwrap();
+        let position = Point::new(3, 8);
+
+        let mut expected = MockDisplay::new();
+        font.draw_glyph('A', position, BinaryColor::On, &mut expected)
+            .unwrap();
+        let mut display = MockDisplay::new();
+        for point in glyph.pixels(position, &font.data) {
+            Pixel(point, BinaryColor::On).draw(&mut display).unwrap();
+        }
+
+        display.assert_eq(&expected);
+    }
 }
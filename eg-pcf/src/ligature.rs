@@ -0,0 +1,92 @@
+use crate::PcfFont;
+
+/// A character sequence that is replaced by a single glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ligature<'a> {
+    pub sequence: &'a str,
+    pub replacement: char,
+}
+
+/// A table of ligatures, e.g. `"fi"` → `'ﬁ'` or `"->"` → `'→'`.
+///
+/// Ligatures are only applied if the font contains a glyph for the replacement character.
+/// If several sequences match at the same position, the longest one is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LigatureTable<'a> {
+    ligatures: &'a [Ligature<'a>],
+}
+
+impl<'a> LigatureTable<'a> {
+    pub const fn new(ligatures: &'a [Ligature<'a>]) -> Self {
+        Self { ligatures }
+    }
+
+    /// Returns the ligatures in the table.
+    pub const fn ligatures(&self) -> &'a [Ligature<'a>] {
+        self.ligatures
+    }
+
+    /// Returns the replacement for the ligature at the start of `chars` and the number of
+    /// replaced characters.
+    pub fn find<I>(&self, chars: I, font: &PcfFont<'_>) -> Option<(char, usize)>
+    where
+        I: Iterator<Item = char> + Clone,
+    {
+        self.ligatures
+            .iter()
+            .filter(|l| !l.sequence.is_empty() && starts_with(chars.clone(), l.sequence))
+            .filter(|l| font.glyph(l.replacement).is_some())
+            .max_by_key(|l| l.sequence.len())
+            .map(|l| (l.replacement, l.sequence.chars().count()))
+    }
+}
+
+/// The maximum number of characters a [`CharMapper`] is passed.
+pub const CHAR_MAPPER_LOOKAHEAD: usize = 4;
+
+/// A function that replaces the characters at the start of `chars` by a single character,
+/// returning it and the number of replaced characters, or `None` to keep them, e.g. to map
+/// `"->"` to `'→'` if `font` has a glyph for it.
+///
+/// `chars` holds the next characters after shaping, up to [`CHAR_MAPPER_LOOKAHEAD`] of them.
+/// At least one character is replaced, and no more than were passed.
+pub type CharMapper = fn(chars: &[char], font: &PcfFont<'_>) -> Option<(char, usize)>;
+
+fn starts_with(mut chars: impl Iterator<Item = char>, sequence: &str) -> bool {
+    sequence.chars().all(|c| chars.next() == Some(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::include_pcf;
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'A'..='Z' | '>');
+
+    const TABLE: LigatureTable = LigatureTable::new(&[
+        Ligature {
+            sequence: "-",
+            replacement: 'M',
+        },
+        Ligature {
+            sequence: "->",
+            replacement: '>',
+        },
+        Ligature {
+            sequence: "fi",
+            replacement: 'ﬁ',
+        },
+    ]);
+
+    #[test]
+    fn it_prefers_the_longest_sequence() {
+        assert_eq!(TABLE.find("->x".chars(), &FONT), Some(('>', 2)));
+        assert_eq!(TABLE.find("-x".chars(), &FONT), Some(('M', 1)));
+        assert_eq!(TABLE.find("x->".chars(), &FONT), None);
+    }
+
+    #[test]
+    fn it_requires_the_replacement_glyph() {
+        assert_eq!(TABLE.find("fi".chars(), &FONT), None);
+    }
+}
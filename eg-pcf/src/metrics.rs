@@ -0,0 +1,95 @@
+use embedded_graphics::primitives::Rectangle;
+
+/// The vertical metrics of a font in pixels.
+///
+/// `include_pcf!` takes the ascent and descent from the accelerators table, and the cap
+/// height and x-height from the `CAP_HEIGHT` and `X_HEIGHT` properties or the ink of the `X`
+/// and `x` glyphs, even if they aren't included.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontMetrics {
+    /// Distance from the baseline to the top of the font.
+    pub ascent: u32,
+    /// Distance from the baseline to the bottom of the font.
+    pub descent: u32,
+    /// Height of capital letters, if known.
+    pub cap_height: Option<u32>,
+    /// Height of lowercase letters without ascenders, if known.
+    pub x_height: Option<u32>,
+}
+
+impl FontMetrics {
+    /// Returns the ascent and descent of a font bounding box, without cap height and
+    /// x-height.
+    pub const fn from_bounding_box(bounding_box: &Rectangle) -> Self {
+        // the bounding box starts one row above the top of the font
+        let ascent = -bounding_box.top_left.y - 1;
+        let descent = bounding_box.top_left.y + bounding_box.size.height as i32 + 1;
+
+        Self {
+            ascent: if ascent > 0 { ascent as u32 } else { 0 },
+            descent: if descent > 0 { descent as u32 } else { 0 },
+            cap_height: None,
+            x_height: None,
+        }
+    }
+
+    /// Returns the y coordinate of the position to draw a line of text at so that capital
+    /// letters are centered vertically in `area`. The ascent is centered if the cap height
+    /// is unknown.
+    pub const fn centered_baseline(&self, area: &Rectangle) -> i32 {
+        let height = match self.cap_height {
+            Some(cap_height) => cap_height,
+            None => self.ascent,
+        };
+
+        // glyphs without descenders end two rows above the baseline position
+        area.top_left.y + (area.size.height + height) as i32 / 2 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, text::PcfTextStyle, PcfFont};
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        prelude::*,
+        text::{renderer::TextRenderer, Baseline},
+    };
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'A'..='Z');
+
+    #[test]
+    fn it_takes_metrics_from_the_font() {
+        let metrics = FontMetrics {
+            ascent: 8,
+            descent: 2,
+            cap_height: Some(7),
+            x_height: Some(5),
+        };
+        assert_eq!(FONT.metrics, metrics);
+
+        let from_box = FontMetrics::from_bounding_box(&FONT.bounding_box);
+        assert_eq!((from_box.ascent, from_box.descent), (8, 2));
+
+        let tall = include_pcf!("examples/6x10.pcf", 'A'..='Z', scale = 2);
+        assert_eq!(tall.metrics.cap_height, Some(14));
+    }
+
+    #[test]
+    fn it_centers_capital_letters() {
+        let area = Rectangle::new(Point::new(0, 2), Size::new(6, 11));
+        let y = FONT.metrics.centered_baseline(&area);
+
+        let mut display = MockDisplay::new();
+        PcfTextStyle::new(&FONT, BinaryColor::On)
+            .draw_string("I", Point::new(0, y), Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        // 7 rows of ink with 2 rows above and below
+        let ink = display.affected_area();
+        assert_eq!(ink.top_left.y, area.top_left.y + 2);
+        assert_eq!(ink.size.height, 7);
+    }
+}
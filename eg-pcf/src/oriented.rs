@@ -0,0 +1,176 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::placement::Rotation;
+
+/// The direction text is drawn in, e.g. for displays that are mounted in portrait.
+///
+/// Text is first rotated around its start position and then mirrored horizontally if
+/// `mirrored` is set.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Orientation {
+    pub rotation: Rotation,
+    pub mirrored: bool,
+}
+
+impl Orientation {
+    pub const fn new(rotation: Rotation) -> Self {
+        Self {
+            rotation,
+            mirrored: false,
+        }
+    }
+
+    /// Mirrors the text horizontally after rotating it.
+    pub const fn mirrored(mut self) -> Self {
+        self.mirrored = !self.mirrored;
+        self
+    }
+
+    /// Returns whether text is drawn unchanged.
+    pub const fn is_identity(self) -> bool {
+        matches!(self.rotation, Rotation::None) && !self.mirrored
+    }
+
+    /// Transforms a point relative to the origin, e.g. a pen position.
+    pub const fn apply(self, point: Point) -> Point {
+        let p = self.rotation.apply(point);
+
+        match self.mirrored {
+            true => Point::new(-p.x, p.y),
+            false => p,
+        }
+    }
+
+    /// Transforms the pixel whose top left corner is at `point` relative to the origin and
+    /// returns the top left corner of the transformed pixel.
+    pub const fn apply_to_pixel(self, point: Point) -> Point {
+        let p = self.rotation.apply_to_pixel(point);
+
+        match self.mirrored {
+            true => Point::new(-p.x - 1, p.y),
+            false => p,
+        }
+    }
+
+    /// Transforms a rectangle relative to the origin.
+    pub fn apply_to_rectangle(self, area: &Rectangle) -> Rectangle {
+        corners(area, |p| self.apply(p))
+    }
+
+    /// Undoes the transformation of a point relative to the origin.
+    fn invert(self, point: Point) -> Point {
+        let point = match self.mirrored {
+            true => Point::new(-point.x, point.y),
+            false => point,
+        };
+
+        self.rotation.inverse().apply(point)
+    }
+}
+
+/// Returns the rectangle spanned by the transformed corners of `area`.
+fn corners(area: &Rectangle, transform: impl Fn(Point) -> Point) -> Rectangle {
+    let a = transform(area.top_left);
+    let b = transform(area.top_left + area.size);
+    let top_left = a.component_min(b);
+    let size = a.component_max(b) - top_left;
+
+    Rectangle::new(top_left, Size::new(size.x as u32, size.y as u32))
+}
+
+/// A draw target that rotates and mirrors everything drawn to it around `origin`.
+///
+/// [`PcfTextStyle`](crate::text::PcfTextStyle) draws text with an
+/// [`Orientation`](crate::text::PcfTextStyle::with_orientation) through it, so glyphs,
+/// advances and decorations are all transformed.
+#[derive(Debug)]
+pub struct Oriented<'d, D> {
+    target: &'d mut D,
+    origin: Point,
+    orientation: Orientation,
+}
+
+impl<'d, D: DrawTarget> Oriented<'d, D> {
+    pub fn new(target: &'d mut D, origin: Point, orientation: Orientation) -> Self {
+        Self {
+            target,
+            origin,
+            orientation,
+        }
+    }
+}
+
+impl<D: DrawTarget> Dimensions for Oriented<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        let area = self.target.bounding_box().translate(-self.origin);
+
+        corners(&area, |p| self.orientation.invert(p)).translate(self.origin)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Oriented<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (origin, orientation) = (self.origin, self.orientation);
+
+        self.target
+            .draw_iter(pixels.into_iter().map(|Pixel(point, color)| {
+                Pixel(origin + orientation.apply_to_pixel(point - origin), color)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    #[test]
+    fn it_rotates_and_mirrors_around_the_origin() {
+        let draw = |orientation| {
+            let mut display = MockDisplay::new();
+            let mut oriented = Oriented::new(&mut display, Point::new(2, 2), orientation);
+            let pixels = [Point::new(2, 2), Point::new(3, 2), Point::new(2, 1)];
+            oriented
+                .draw_iter(pixels.map(|p| Pixel(p, BinaryColor::On)))
+                .unwrap();
+
+            display
+        };
+
+        draw(Orientation::new(Rotation::Clockwise90)).assert_pattern(&[
+            "    ", //
+            "    ", " ## ", " #  ",
+        ]);
+        draw(Orientation::default().mirrored()).assert_pattern(&[
+            "    ", //
+            " #  ", "##  ",
+        ]);
+    }
+
+    #[test]
+    fn it_maps_rectangles_both_ways() {
+        let area = Rectangle::new(Point::new(1, -3), Size::new(4, 2));
+
+        for rotation in [
+            Rotation::None,
+            Rotation::Clockwise90,
+            Rotation::Rotate180,
+            Rotation::Counterclockwise90,
+        ] {
+            for orientation in [
+                Orientation::new(rotation),
+                Orientation::new(rotation).mirrored(),
+            ] {
+                let transformed = orientation.apply_to_rectangle(&area);
+
+                assert_eq!(corners(&transformed, |p| orientation.invert(p)), area);
+            }
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use core::f32::consts::PI;
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use micromath::F32Ext;
+
+use crate::{shaping::Shaper, text::PcfTextStyle};
+
+/// A rotation of a glyph around its origin in 90° steps.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rotation {
+    #[default]
+    None,
+    /// Rotated by 90° clockwise.
+    Clockwise90,
+    Rotate180,
+    /// Rotated by 90° counterclockwise.
+    Counterclockwise90,
+}
+
+impl Rotation {
+    /// Returns the rotation closest to `angle`, measured clockwise.
+    pub fn nearest(angle: Angle) -> Self {
+        match F32Ext::round(angle.to_degrees() / 90.0) as i32 & 3 {
+            0 => Self::None,
+            1 => Self::Clockwise90,
+            2 => Self::Rotate180,
+            _ => Self::Counterclockwise90,
+        }
+    }
+
+    /// Returns the rotation that undoes this one.
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::Clockwise90 => Self::Counterclockwise90,
+            Self::Counterclockwise90 => Self::Clockwise90,
+            rotation => rotation,
+        }
+    }
+
+    /// Rotates a point relative to the origin.
+    pub const fn apply(self, point: Point) -> Point {
+        match self {
+            Self::None => point,
+            Self::Clockwise90 => Point::new(-point.y, point.x),
+            Self::Rotate180 => Point::new(-point.x, -point.y),
+            Self::Counterclockwise90 => Point::new(point.y, -point.x),
+        }
+    }
+
+    /// Rotates the pixel whose top left corner is at `point` relative to the origin and
+    /// returns the top left corner of the rotated pixel.
+    pub const fn apply_to_pixel(self, point: Point) -> Point {
+        let p = self.apply(point);
+
+        match self {
+            Self::None => p,
+            Self::Clockwise90 => Point::new(p.x - 1, p.y),
+            Self::Rotate180 => Point::new(p.x - 1, p.y - 1),
+            Self::Counterclockwise90 => Point::new(p.x, p.y - 1),
+        }
+    }
+
+    /// Rotates a rectangle relative to the origin.
+    pub fn apply_to_rectangle(self, area: &Rectangle) -> Rectangle {
+        let a = self.apply(area.top_left);
+        let b = self.apply(area.top_left + area.size);
+        let top_left = a.component_min(b);
+        let size = a.component_max(b) - top_left;
+
+        Rectangle::new(top_left, Size::new(size.x as u32, size.y as u32))
+    }
+}
+
+/// The position and rotation of a single glyph.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GlyphPlacement {
+    /// The position of the glyph origin on the baseline.
+    pub position: Point,
+    pub rotation: Rotation,
+}
+
+impl From<Point> for GlyphPlacement {
+    fn from(position: Point) -> Self {
+        Self {
+            position,
+            rotation: Rotation::None,
+        }
+    }
+}
+
+/// Places the glyphs of `text` clockwise along a circle.
+///
+/// The first glyph origin is at `start`, measured clockwise from the 3 o'clock position,
+/// and each glyph advances along the circle by its advance width. If `rotate` is set,
+/// glyphs are turned to the 90° step closest to the tangent of the circle, so text at the
+/// top of the circle is upright.
+pub fn arc<'t, C, S>(
+    style: &'t PcfTextStyle<'_, C, S>,
+    text: &'t str,
+    center: Point,
+    radius: u32,
+    start: Angle,
+    rotate: bool,
+) -> impl Iterator<Item = GlyphPlacement> + 't
+where
+    C: PixelColor,
+    S: Shaper,
+{
+    let radius = radius.max(1) as f32;
+    let mut angle = start.to_radians();
+
+    style.advances(text).map(move |g| {
+        let position = center
+            + Point::new(
+                F32Ext::round(radius * F32Ext::cos(angle)) as i32,
+                F32Ext::round(radius * F32Ext::sin(angle)) as i32,
+            );
+        let rotation = match rotate {
+            true => Rotation::nearest(Angle::from_radians(angle + PI / 2.0)),
+            false => Rotation::None,
+        };
+
+        angle += g.advance as f32 / radius;
+
+        GlyphPlacement { position, rotation }
+    })
+}
+
+/// Places the glyphs of `text` along a sine wave.
+///
+/// The wave runs horizontally through `position` and has the given `amplitude` and
+/// `wavelength` in pixels. `phase` shifts the wave at the first glyph.
+pub fn wave<'t, C, S>(
+    style: &'t PcfTextStyle<'_, C, S>,
+    text: &'t str,
+    position: Point,
+    amplitude: u32,
+    wavelength: u32,
+    phase: Angle,
+) -> impl Iterator<Item = GlyphPlacement> + 't
+where
+    C: PixelColor,
+    S: Shaper,
+{
+    let amplitude = amplitude as f32;
+    let wavelength = wavelength.max(1) as f32;
+    let phase = phase.to_radians();
+    let mut x = 0;
+
+    style.advances(text).map(move |g| {
+        let y = amplitude * F32Ext::sin(2.0 * PI * x as f32 / wavelength + phase);
+        let placement = GlyphPlacement::from(position + Point::new(x, F32Ext::round(y) as i32));
+
+        x += g.advance;
+
+        placement
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, PcfFont};
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        text::{renderer::TextRenderer, Baseline},
+    };
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+
+    #[test]
+    fn test_nearest_rotation() {
+        assert_eq!(Rotation::nearest(Angle::from_degrees(10.0)), Rotation::None);
+        assert_eq!(
+            Rotation::nearest(Angle::from_degrees(100.0)),
+            Rotation::Clockwise90
+        );
+        assert_eq!(
+            Rotation::nearest(Angle::from_degrees(-90.0)),
+            Rotation::Counterclockwise90
+        );
+        assert_eq!(
+            Rotation::apply(Rotation::Clockwise90, Point::new(1, 0)),
+            Point::new(0, 1)
+        );
+    }
+
+    #[test]
+    fn it_places_glyphs_on_an_arc() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let placements: [_; 2] = {
+            let mut arc = arc(
+                &style,
+                "12",
+                Point::new(50, 50),
+                40,
+                Angle::from_degrees(-90.0),
+                true,
+            );
+            [arc.next().unwrap(), arc.next().unwrap()]
+        };
+
+        assert_eq!(placements[0].position, Point::new(50, 10));
+        assert_eq!(placements[0].rotation, Rotation::None);
+        assert_eq!(placements[1].position, Point::new(56, 10));
+    }
+
+    #[test]
+    fn it_draws_flat_waves_like_strings() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let position = Point::new(0, 10);
+
+        let mut display = MockDisplay::new();
+        let placements = wave(&style, "123", position, 0, 20, Angle::zero());
+        style
+            .draw_glyphs_with("123", placements, &mut display)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        style
+            .draw_string("123", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+}
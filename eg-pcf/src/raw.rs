@@ -0,0 +1,216 @@
+//! Fonts stored as byte tables with a stable layout.
+//!
+//! [`PcfFontRaw`] reads its glyphs from two byte slices instead of a `PcfGlyph` array, so the
+//! tables can be generated offline, e.g. by a build script, and placed anywhere, like
+//! external flash mapped at a fixed address. `include_pcf!` emits the same tables with
+//! `layout = raw`.
+//!
+//! The glyph table holds one [`RECORD_SIZE`] byte record per glyph, sorted by character.
+//! All fields are little endian:
+//!
+//! | Offset | Type  | Field                                             |
+//! |--------|-------|---------------------------------------------------|
+//! | 0      | `u32` | Unicode code point of the character               |
+//! | 4      | `u32` | start of the glyph bitmap in the data, in bits    |
+//! | 8      | `i16` | left edge of the bounding box relative to origin  |
+//! | 10     | `i16` | top edge of the bounding box relative to origin   |
+//! | 12     | `u8`  | bounding box width                                |
+//! | 13     | `u8`  | bounding box height                               |
+//! | 14     | `i16` | device width, the advance to the next glyph       |
+//!
+//! The data holds the glyph bitmaps packed row by row, MSB first, like
+//! [`GlyphData::Slice`]. Bitmaps aren't padded, so a glyph starting at bit `start` has its
+//! pixel `(x, y)` at bit `start + y * width + x`.
+//!
+//! A build script can generate the tables from a font loaded with `buf::PcfFontBuf`, or any
+//! other source of [`PcfGlyph`]s, with [`encode_glyph`].
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::{placement::Rotation, renderer::BitmapFontRenderer, GlyphData, PcfGlyph};
+
+/// The size of a glyph record in bytes.
+pub const RECORD_SIZE: usize = 16;
+
+/// Returns the glyph table record of `glyph`, or `None` if its metrics don't fit the
+/// record fields.
+pub fn encode_glyph(glyph: &PcfGlyph) -> Option<[u8; RECORD_SIZE]> {
+    let Rectangle { top_left, size } = glyph.bounding_box;
+
+    let mut record = [0; RECORD_SIZE];
+    record[0..4].copy_from_slice(&u32::from(glyph.character).to_le_bytes());
+    record[4..8].copy_from_slice(&u32::try_from(glyph.start_index).ok()?.to_le_bytes());
+    record[8..10].copy_from_slice(&i16::try_from(top_left.x).ok()?.to_le_bytes());
+    record[10..12].copy_from_slice(&i16::try_from(top_left.y).ok()?.to_le_bytes());
+    record[12] = u8::try_from(size.width).ok()?;
+    record[13] = u8::try_from(size.height).ok()?;
+    record[14..16].copy_from_slice(&i16::try_from(glyph.device_width).ok()?.to_le_bytes());
+
+    Some(record)
+}
+
+/// Returns the glyph of a glyph table record.
+///
+/// Invalid code points are decoded as REPLACEMENT CHARACTER.
+pub fn decode_glyph(record: &[u8; RECORD_SIZE]) -> PcfGlyph {
+    let u16_at = |i: usize| u16::from_le_bytes([record[i], record[i + 1]]);
+    let u32_at =
+        |i: usize| u32::from_le_bytes([record[i], record[i + 1], record[i + 2], record[i + 3]]);
+
+    PcfGlyph {
+        character: char::from_u32(u32_at(0)).unwrap_or(char::REPLACEMENT_CHARACTER),
+        bounding_box: Rectangle::new(
+            Point::new(i32::from(u16_at(8) as i16), i32::from(u16_at(10) as i16)),
+            Size::new(record[12].into(), record[13].into()),
+        ),
+        device_width: i32::from(u16_at(14) as i16),
+        start_index: u32_at(4) as usize,
+    }
+}
+
+/// A Unicode font read from a glyph table and glyph data with the layout described in the
+/// [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PcfFontRaw<'a> {
+    pub bounding_box: Rectangle,
+    /// The index of the glyph drawn for characters the font has no glyph for.
+    pub replacement_character: usize,
+    pub line_height: u32,
+    /// The glyph records, sorted by character.
+    pub glyphs: &'a [u8],
+    pub data: &'a [u8],
+}
+
+impl<'a> PcfFontRaw<'a> {
+    /// Creates a font from its tables.
+    ///
+    /// `glyphs` must be sorted by character, otherwise lookups may miss glyphs. Trailing
+    /// bytes that don't make up a whole record are ignored.
+    pub const fn new(
+        bounding_box: Rectangle,
+        replacement_character: usize,
+        line_height: u32,
+        glyphs: &'a [u8],
+        data: &'a [u8],
+    ) -> Self {
+        Self {
+            bounding_box,
+            replacement_character,
+            line_height,
+            glyphs,
+            data,
+        }
+    }
+
+    /// Returns the number of glyphs in the font.
+    pub const fn glyph_count(&self) -> usize {
+        self.glyphs.len() / RECORD_SIZE
+    }
+
+    /// Returns the glyph at `index` in the glyph table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn glyph_at(&self, index: usize) -> PcfGlyph {
+        let record = &self.glyphs[index * RECORD_SIZE..][..RECORD_SIZE];
+
+        decode_glyph(record.try_into().unwrap())
+    }
+
+    /// Returns the glyph of `c`, if the font has one.
+    pub fn glyph(&self, c: char) -> Option<PcfGlyph> {
+        let (mut low, mut high) = (0, self.glyph_count());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let glyph = self.glyph_at(middle);
+            match glyph.character.cmp(&c) {
+                core::cmp::Ordering::Less => low = middle + 1,
+                core::cmp::Ordering::Greater => high = middle,
+                core::cmp::Ordering::Equal => return Some(glyph),
+            }
+        }
+
+        None
+    }
+
+    fn get_glyph(&self, c: char) -> PcfGlyph {
+        self.glyph(c)
+            .unwrap_or_else(|| self.glyph_at(self.replacement_character))
+    }
+}
+
+impl BitmapFontRenderer for PcfFontRaw<'_> {
+    fn contains(&self, c: char) -> bool {
+        self.glyph(c).is_some()
+    }
+
+    fn advance(&self, c: char) -> i32 {
+        self.get_glyph(c).device_width
+    }
+
+    fn line_height(&self) -> u32 {
+        self.line_height
+    }
+
+    fn glyph_bounding_box(&self, c: char) -> Rectangle {
+        self.get_glyph(c).bounding_box
+    }
+
+    fn draw_glyph<D: DrawTarget>(
+        &self,
+        c: char,
+        position: Point,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<Point, D::Error> {
+        let glyph = self.get_glyph(c);
+        let data = GlyphData::Slice(self.data);
+        glyph.draw(position, Rotation::None, color, &data, target)?;
+
+        Ok(position + Point::new(glyph.device_width, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, PcfFont};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'a'..='z' | '\u{2588}');
+    const RAW: PcfFontRaw = include_pcf!("examples/6x10.pcf", 'a'..='z' | '\u{2588}', layout = raw);
+
+    #[test]
+    fn it_emits_the_documented_tables() {
+        let mut glyphs = [0; 27 * RECORD_SIZE];
+        for (record, glyph) in glyphs.chunks_exact_mut(RECORD_SIZE).zip(FONT.glyphs) {
+            record.copy_from_slice(&encode_glyph(glyph).unwrap());
+        }
+
+        assert_eq!(RAW.glyphs, glyphs);
+        assert_eq!(GlyphData::Slice(RAW.data), FONT.data);
+        assert_eq!(RAW.bounding_box, FONT.bounding_box);
+        assert_eq!(RAW.replacement_character, FONT.replacement_character);
+        assert_eq!(RAW.line_height, FONT.line_height);
+        for (i, glyph) in FONT.glyphs.iter().enumerate() {
+            assert_eq!(RAW.glyph_at(i), *glyph);
+            assert_eq!(RAW.glyph(glyph.character), Some(*glyph));
+        }
+        assert_eq!(RAW.glyph('A'), None);
+    }
+
+    #[test]
+    fn it_draws_the_same_as_the_glyph_array() {
+        for c in ['a', 'q', '\u{2588}', 'A'] {
+            let mut expected = MockDisplay::<BinaryColor>::new();
+            let next = FONT.draw_glyph(c, Point::new(2, 10), BinaryColor::On, &mut expected);
+            let mut display = MockDisplay::new();
+            let raw_next = RAW.draw_glyph(c, Point::new(2, 10), BinaryColor::On, &mut display);
+
+            display.assert_eq(&expected);
+            assert_eq!(raw_next, next);
+            assert_eq!(RAW.advance(c), FONT.advance(c));
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::{placement::Rotation, PcfFont};
+
+/// The operations text rendering needs from a bitmap font.
+///
+/// Implemented by fonts embedded with [`include_pcf!`](crate::include_pcf) and fonts
+/// loaded at runtime, so code that only draws or measures single glyphs can be generic over
+/// where its font comes from.
+pub trait BitmapFontRenderer {
+    /// Returns whether the font has a glyph for `c`.
+    fn contains(&self, c: char) -> bool;
+
+    /// Returns the advance of `c` in pixels, or the one of the replacement glyph if the
+    /// font has no glyph for `c`. Combining characters can have zero or negative advances.
+    fn advance(&self, c: char) -> i32;
+
+    /// Returns the distance between baselines in pixels.
+    fn line_height(&self) -> u32;
+
+    /// Returns the bounding box of the glyph of `c` relative to its origin.
+    fn glyph_bounding_box(&self, c: char) -> Rectangle;
+
+    /// Draws the glyph of `c` with its origin at `position` and returns the origin of the
+    /// next glyph.
+    fn draw_glyph<D: DrawTarget>(
+        &self,
+        c: char,
+        position: Point,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<Point, D::Error>;
+}
+
+impl BitmapFontRenderer for PcfFont<'_> {
+    fn contains(&self, c: char) -> bool {
+        PcfFont::contains(self, c)
+    }
+
+    fn advance(&self, c: char) -> i32 {
+        self.get_glyph(c).device_width
+    }
+
+    fn line_height(&self) -> u32 {
+        self.line_height
+    }
+
+    fn glyph_bounding_box(&self, c: char) -> Rectangle {
+        self.get_glyph(c).bounding_box
+    }
+
+    fn draw_glyph<D: DrawTarget>(
+        &self,
+        c: char,
+        position: Point,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<Point, D::Error> {
+        let glyph = self.get_glyph(c);
+        glyph.draw(position, Rotation::None, color, &self.data, target)?;
+
+        Ok(position + Point::new(glyph.device_width, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, text::PcfTextStyle};
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        text::{renderer::TextRenderer, Baseline},
+    };
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'a'..='z');
+
+    /// Draws a string one glyph at a time, without depending on the font type.
+    fn draw<F: BitmapFontRenderer>(font: &F, text: &str, display: &mut MockDisplay<BinaryColor>) {
+        let mut position = Point::new(0, font.line_height() as i32);
+        for c in text.chars() {
+            position = font
+                .draw_glyph(c, position, BinaryColor::On, display)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn it_draws_like_the_text_style() {
+        let mut display = MockDisplay::new();
+        draw(&FONT, "abc", &mut display);
+
+        let mut expected = MockDisplay::new();
+        PcfTextStyle::new(&FONT, BinaryColor::On)
+            .draw_string(
+                "abc",
+                Point::new(0, 10),
+                Baseline::Alphabetic,
+                &mut expected,
+            )
+            .unwrap();
+        display.assert_eq(&expected);
+
+        assert!(BitmapFontRenderer::contains(&FONT, 'q'));
+        assert!(!BitmapFontRenderer::contains(&FONT, 'Q'));
+        assert_eq!(FONT.advance('Q'), FONT.advance('a'));
+    }
+}
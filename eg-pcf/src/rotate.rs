@@ -0,0 +1,169 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use micromath::F32Ext;
+
+use crate::{GlyphData, PcfGlyph};
+
+/// A glyph bitmap rotated by an arbitrary angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RotatedGlyph<'b> {
+    bounding_box: Rectangle,
+    bitmap: &'b [u8],
+}
+
+impl RotatedGlyph<'_> {
+    /// Returns the area covered by the rotated bitmap, relative to the glyph origin.
+    pub fn bounding_box(&self) -> Rectangle {
+        self.bounding_box
+    }
+
+    /// Returns whether the pixel at `point`, relative to the glyph origin, is set.
+    pub fn pixel(&self, point: Point) -> bool {
+        if !self.bounding_box.contains(point) {
+            return false;
+        }
+
+        let p = point - self.bounding_box.top_left;
+        let i = p.y as usize * self.bounding_box.size.width as usize + p.x as usize;
+
+        self.bitmap[i / 8] & (0x80 >> (i % 8)) != 0
+    }
+
+    /// Draws the glyph with its origin at `position`.
+    pub fn draw<D: DrawTarget>(
+        &self,
+        position: Point,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<(), D::Error> {
+        self.bounding_box
+            .points()
+            .filter(|p| self.pixel(*p))
+            .map(|p| Pixel(position + p, color))
+            .draw(target)
+    }
+}
+
+impl PcfGlyph {
+    /// Rotates the glyph clockwise around its origin by `angle` with nearest neighbor
+    /// sampling.
+    ///
+    /// The rotated bitmap is stored in `buffer` with one bit per pixel. Returns `None` if the
+    /// buffer is too small; `(w + h + 2)² / 8` bytes are always enough for a `w`x`h` glyph.
+    pub fn rotate<'b>(
+        &self,
+        data: &GlyphData,
+        angle: Angle,
+        buffer: &'b mut [u8],
+    ) -> Option<RotatedGlyph<'b>> {
+        let angle = angle.to_radians();
+        let (sin, cos) = (F32Ext::sin(angle), F32Ext::cos(angle));
+
+        let source = self.bounding_box;
+        let rotate = |x: f32, y: f32| (x * cos - y * sin, x * sin + y * cos);
+
+        let corners = [
+            source.top_left,
+            source.top_left + Point::new(source.size.width as i32, 0),
+            source.top_left + Point::new(0, source.size.height as i32),
+            source.top_left + source.size,
+        ]
+        .map(|p| rotate(p.x as f32, p.y as f32));
+
+        let min = corners
+            .iter()
+            .fold((f32::MAX, f32::MAX), |(x, y), c| (x.min(c.0), y.min(c.1)));
+        let max = corners
+            .iter()
+            .fold((f32::MIN, f32::MIN), |(x, y), c| (x.max(c.0), y.max(c.1)));
+
+        // round corners that are within rounding error of a pixel edge
+        let top_left = Point::new(
+            F32Ext::floor(min.0 + 1e-3) as i32,
+            F32Ext::floor(min.1 + 1e-3) as i32,
+        );
+        let bottom_right = Point::new(
+            F32Ext::ceil(max.0 - 1e-3) as i32,
+            F32Ext::ceil(max.1 - 1e-3) as i32,
+        );
+        let bounding_box = Rectangle::with_corners(top_left, bottom_right - Point::new(1, 1));
+
+        let len = (bounding_box.size.width * bounding_box.size.height) as usize;
+        let bitmap = buffer.get_mut(..len.div_ceil(8))?;
+        bitmap.fill(0);
+
+        for (i, p) in bounding_box.points().enumerate() {
+            // sample the source pixel under the center of the rotated pixel
+            let (x, y) = (p.x as f32 + 0.5, p.y as f32 + 0.5);
+            let (sx, sy) = (x * cos + y * sin, -x * sin + y * cos);
+            let s = Point::new(F32Ext::floor(sx) as i32, F32Ext::floor(sy) as i32);
+
+            if !source.contains(s) {
+                continue;
+            }
+
+            let s = s - source.top_left;
+            let bit = self.start_index + s.y as usize * source.size.width as usize + s.x as usize;
+            if data.bit(bit) {
+                bitmap[i / 8] |= 0x80 >> (i % 8);
+            }
+        }
+
+        Some(RotatedGlyph {
+            bounding_box,
+            bitmap,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, placement::Rotation, PcfFont};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+
+    #[test]
+    fn it_rotates_by_right_angles() {
+        let glyph = FONT.glyph('7').unwrap();
+        let position = Point::new(20, 20);
+        let mut buffer = [0; 32];
+
+        for (degrees, rotation) in [
+            (0.0, Rotation::None),
+            (90.0, Rotation::Clockwise90),
+            (180.0, Rotation::Rotate180),
+        ] {
+            let rotated = glyph
+                .rotate(&FONT.data, Angle::from_degrees(degrees), &mut buffer)
+                .unwrap();
+
+            let mut display = MockDisplay::new();
+            rotated
+                .draw(position, BinaryColor::On, &mut display)
+                .unwrap();
+
+            let mut expected = MockDisplay::new();
+            glyph
+                .draw(
+                    position,
+                    rotation,
+                    BinaryColor::On,
+                    &FONT.data,
+                    &mut expected,
+                )
+                .unwrap();
+
+            display.assert_eq(&expected);
+        }
+    }
+
+    #[test]
+    fn it_needs_a_large_enough_buffer() {
+        let glyph = FONT.glyph('7').unwrap();
+
+        assert!(glyph
+            .rotate(&FONT.data, Angle::from_degrees(30.0), &mut [0; 4])
+            .is_none());
+    }
+}
@@ -0,0 +1,144 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A draw target that scales everything drawn to it by integer factors.
+///
+/// Pixels are scaled around `origin`, so text drawn at `origin` keeps its position while
+/// its glyphs, advances and line height grow, e.g. for double width headlines with fonts
+/// that were included without the `scale` option.
+#[derive(Debug)]
+pub struct Scaled<'d, D> {
+    target: &'d mut D,
+    origin: Point,
+    factor: Size,
+}
+
+impl<'d, D: DrawTarget> Scaled<'d, D> {
+    /// Factors of 0 are treated as 1.
+    pub fn new(target: &'d mut D, origin: Point, factor: Size) -> Self {
+        Self {
+            target,
+            origin,
+            factor: factor.component_max(Size::new(1, 1)),
+        }
+    }
+
+    /// Returns the area of `target` covered by `area` of the scaled target, e.g. to scale
+    /// the result of a text measurement.
+    pub fn scale_rectangle(&self, area: &Rectangle) -> Rectangle {
+        Rectangle::new(
+            self.scale_point(area.top_left),
+            area.size.component_mul(self.factor),
+        )
+    }
+
+    fn scale_point(&self, point: Point) -> Point {
+        self.origin
+            + (point - self.origin).component_mul(Point::new(
+                self.factor.width as i32,
+                self.factor.height as i32,
+            ))
+    }
+}
+
+impl<D: DrawTarget> Dimensions for Scaled<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        let area = self.target.bounding_box();
+        let factor = Point::new(self.factor.width as i32, self.factor.height as i32);
+        let top_left = area.top_left - self.origin;
+        // round the top left corner down and the size up to cover the whole target
+        let top_left = Point::new(
+            top_left.x.div_euclid(factor.x),
+            top_left.y.div_euclid(factor.y),
+        );
+        let size = Size::new(
+            area.size.width.div_ceil(self.factor.width) + 1,
+            area.size.height.div_ceil(self.factor.height) + 1,
+        );
+
+        Rectangle::new(self.origin + top_left, size)
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Scaled<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let block = Rectangle::new(self.scale_point(point), self.factor);
+            self.target.fill_solid(&block, color)?;
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.scale_rectangle(area);
+        self.target.fill_solid(&area, color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, text::PcfTextStyle, PcfFont};
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        text::{renderer::TextRenderer, Baseline},
+    };
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+
+    #[test]
+    fn it_scales_around_the_origin() {
+        let mut display = MockDisplay::new();
+        let mut scaled = Scaled::new(&mut display, Point::new(1, 1), Size::new(2, 1));
+
+        Pixel(Point::new(2, 1), BinaryColor::On)
+            .draw(&mut scaled)
+            .unwrap();
+        scaled
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 2), Size::new(1, 1)),
+                BinaryColor::On,
+            )
+            .unwrap();
+
+        display.assert_pattern(&["     ", "   ##", " ##  "]);
+    }
+
+    #[test]
+    fn it_matches_fonts_scaled_by_the_macro() {
+        let wide = include_pcf!("examples/6x10.pcf", '0'..='9', scale = (2, 1));
+        let position = Point::new(0, 10);
+
+        let mut display = MockDisplay::new();
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut scaled = Scaled::new(&mut display, position, Size::new(2, 1));
+        let next = style
+            .draw_string("12", position, Baseline::Alphabetic, &mut scaled)
+            .unwrap();
+        let metrics = style.measure_string("12", position, Baseline::Alphabetic);
+        let area = scaled.scale_rectangle(&metrics.bounding_box);
+        let next = scaled.scale_point(next);
+
+        let mut expected = MockDisplay::new();
+        let wide_style = PcfTextStyle::new(&wide, BinaryColor::On);
+        wide_style
+            .draw_string("12", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+
+        display.assert_eq(&expected);
+        assert_eq!(next, Point::new(24, 10));
+        assert_eq!(
+            area,
+            wide_style
+                .measure_string("12", position, Baseline::Alphabetic)
+                .bounding_box
+        );
+    }
+}
@@ -0,0 +1,34 @@
+/// The vertical position of text relative to the baseline.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Script {
+    #[default]
+    Normal,
+    /// Raised above the baseline, e.g. the `2` in m².
+    Superscript,
+    /// Lowered below the baseline, e.g. the `2` in H₂O.
+    Subscript,
+}
+
+/// The baseline offsets of superscript and subscript text in pixels.
+///
+/// `include_pcf!` takes them from the `SUPERSCRIPT_Y` and `SUBSCRIPT_Y` properties and falls
+/// back to 40% and 20% of the font ascent if a property is missing or zero.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScriptOffsets {
+    /// Distance the baseline is raised for superscript text.
+    pub superscript: i32,
+    /// Distance the baseline is lowered for subscript text.
+    pub subscript: i32,
+}
+
+impl ScriptOffsets {
+    /// Returns the vertical offset of the baseline for `script`, with positive values
+    /// pointing down.
+    pub const fn baseline_shift(&self, script: Script) -> i32 {
+        match script {
+            Script::Normal => 0,
+            Script::Superscript => -self.superscript,
+            Script::Subscript => self.subscript,
+        }
+    }
+}
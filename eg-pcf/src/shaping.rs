@@ -0,0 +1,211 @@
+/// A glyph produced by a [`Shaper`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShapedGlyph {
+    /// The character whose glyph is drawn.
+    pub character: char,
+}
+
+impl From<char> for ShapedGlyph {
+    fn from(character: char) -> Self {
+        Self { character }
+    }
+}
+
+/// A hook that maps text to glyphs before they are looked up in the font.
+///
+/// Shapers run before ligature substitution and kerning, in both drawing and measurement.
+/// They can be used to implement contextual forms (e.g. Arabic presentation forms) or
+/// custom transliteration without changes to the renderer.
+pub trait Shaper {
+    /// Maps a string to the glyphs that represent it.
+    ///
+    /// The iterator is cloned to look ahead, e.g. for ligatures, so cloning should be cheap.
+    fn map<'t>(&'t self, text: &'t str) -> impl Iterator<Item = ShapedGlyph> + Clone + 't;
+
+    /// Returns the index of the glyph of every character of `text` in the glyphs returned by
+    /// [`map`](Self::map), in the order of the characters, for shapers that reorder them.
+    ///
+    /// Shapers that keep the order of the characters return `None`, the default, and
+    /// positions of characters are measured over prefixes of the text instead.
+    fn visual_indices<'t>(
+        &'t self,
+        _text: &'t str,
+    ) -> Option<impl Iterator<Item = usize> + Clone + 't> {
+        None::<core::iter::Empty<usize>>
+    }
+}
+
+/// A shaper that maps every character to its own glyph.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PassThrough;
+
+impl Shaper for PassThrough {
+    fn map<'t>(&'t self, text: &'t str) -> impl Iterator<Item = ShapedGlyph> + Clone + 't {
+        text.chars().map(ShapedGlyph::from)
+    }
+}
+
+/// A shaper that normalizes text to Unicode Normalization Form C.
+///
+/// Decomposed sequences like `e` followed by U+0301 COMBINING ACUTE ACCENT are drawn with
+/// the precomposed glyph (`é`) the font contains. Long runs of combining marks need a heap
+/// allocation, so this requires a global allocator.
+#[cfg(feature = "unicode-normalization")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Nfc;
+
+#[cfg(feature = "unicode-normalization")]
+impl Shaper for Nfc {
+    fn map<'t>(&'t self, text: &'t str) -> impl Iterator<Item = ShapedGlyph> + Clone + 't {
+        use unicode_normalization::UnicodeNormalization;
+
+        text.nfc().map(ShapedGlyph::from)
+    }
+}
+
+/// Returns whether `c` is a combining mark, which is drawn over the preceding character.
+#[cfg(feature = "unicode-normalization")]
+pub(crate) fn is_combining_mark(c: char) -> bool {
+    unicode_normalization::char::is_combining_mark(c)
+}
+
+#[cfg(not(feature = "unicode-normalization"))]
+pub(crate) fn is_combining_mark(_c: char) -> bool {
+    false
+}
+
+/// Returns the precomposed character of a character followed by a combining mark.
+#[cfg(feature = "unicode-normalization")]
+pub(crate) fn compose(c: char, mark: char) -> Option<char> {
+    unicode_normalization::char::compose(c, mark)
+}
+
+#[cfg(not(feature = "unicode-normalization"))]
+pub(crate) fn compose(_c: char, _mark: char) -> Option<char> {
+    None
+}
+
+/// A shaper that reorders text mixing left-to-right and right-to-left scripts into visual
+/// order with the Unicode Bidirectional Algorithm.
+///
+/// Each paragraph is reordered as a single line, so text must be reordered before it is
+/// wrapped. Mirrored characters like brackets aren't replaced. This requires a global
+/// allocator.
+#[cfg(feature = "unicode-bidi")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bidi;
+
+#[cfg(feature = "unicode-bidi")]
+impl Shaper for Bidi {
+    fn map<'t>(&'t self, text: &'t str) -> impl Iterator<Item = ShapedGlyph> + Clone + 't {
+        use alloc::vec::Vec;
+
+        let chars: Vec<char> = text.chars().collect();
+        let visual: Vec<char> = visual_order(text).iter().map(|i| chars[*i]).collect();
+
+        Shared::new(visual).map(ShapedGlyph::from)
+    }
+
+    fn visual_indices<'t>(
+        &'t self,
+        text: &'t str,
+    ) -> Option<impl Iterator<Item = usize> + Clone + 't> {
+        use alloc::vec;
+
+        let order = visual_order(text);
+        let mut indices = vec![0; order.len()];
+        for (visual, logical) in order.into_iter().enumerate() {
+            indices[logical] = visual;
+        }
+
+        Some(Shared::new(indices))
+    }
+}
+
+/// Returns the char index of every character of `text` in visual order.
+#[cfg(feature = "unicode-bidi")]
+fn visual_order(text: &str) -> alloc::vec::Vec<usize> {
+    use alloc::vec::Vec;
+    use unicode_bidi::BidiInfo;
+
+    let info = BidiInfo::new(text, None);
+    let mut order = Vec::new();
+    for paragraph in &info.paragraphs {
+        // the levels cover the whole text, one per char
+        let levels = info.reordered_levels_per_char(paragraph, paragraph.range.clone());
+        let start = text[..paragraph.range.start].chars().count();
+        let len = text[paragraph.range.clone()].chars().count();
+
+        let reordered = BidiInfo::reorder_visual(&levels[start..start + len]);
+        order.extend(reordered.into_iter().map(|i| start + i));
+    }
+
+    order
+}
+
+/// An iterator over items shared by its clones, so cloning it doesn't copy them.
+#[cfg(feature = "unicode-bidi")]
+#[derive(Debug, Clone)]
+struct Shared<T> {
+    items: alloc::rc::Rc<[T]>,
+    index: usize,
+}
+
+#[cfg(feature = "unicode-bidi")]
+impl<T> Shared<T> {
+    fn new(items: alloc::vec::Vec<T>) -> Self {
+        Self {
+            items: items.into(),
+            index: 0,
+        }
+    }
+}
+
+#[cfg(feature = "unicode-bidi")]
+impl<T: Copy> Iterator for Shared<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let item = self.items.get(self.index).copied();
+        self.index += 1;
+        item
+    }
+}
+
+#[cfg(all(test, any(feature = "unicode-normalization", feature = "unicode-bidi")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "unicode-bidi")]
+    #[test]
+    fn it_reorders_right_to_left_text() {
+        let glyphs = Bidi.map("ab \u{5d0}\u{5d1}\u{5d2} cd");
+
+        assert!(glyphs
+            .map(|g| g.character)
+            .eq("ab \u{5d2}\u{5d1}\u{5d0} cd".chars()));
+        assert!(Bidi
+            .visual_indices("ab \u{5d0}\u{5d1}\u{5d2} cd")
+            .unwrap()
+            .eq([0, 1, 2, 5, 4, 3, 6, 7, 8]));
+        // paragraphs are reordered one by one
+        assert!(Bidi
+            .map("\u{5d0}\u{5d1}\n\u{5d2}")
+            .map(|g| g.character)
+            .eq("\n\u{5d1}\u{5d0}\u{5d2}".chars()));
+        assert!(Bidi
+            .map("ab\nx \u{5d0}1 2")
+            .map(|g| g.character)
+            .eq("ab\nx 2 1\u{5d0}".chars()));
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn it_composes_decomposed_sequences() {
+        let mut glyphs = Nfc.map("e\u{301}!");
+
+        assert_eq!(glyphs.next(), Some(ShapedGlyph::from('é')));
+        assert_eq!(glyphs.next(), Some(ShapedGlyph::from('!')));
+        assert_eq!(glyphs.next(), None);
+    }
+}
@@ -0,0 +1,81 @@
+use embedded_graphics::prelude::*;
+
+use crate::PcfFont;
+
+/// Decipoints per inch.
+const DECIPOINTS_PER_INCH: u32 = 720;
+
+/// The size a font was designed for, from its `POINT_SIZE` and `RESOLUTION_X`/`RESOLUTION_Y`
+/// properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DesignSize {
+    /// Point size in decipoints (1/720 inch).
+    pub point_size: u32,
+    /// Horizontal and vertical resolution in dots per inch.
+    pub resolution: Size,
+}
+
+impl DesignSize {
+    /// Returns the nominal pixel height of the font at its design resolution.
+    pub const fn pixel_size(&self) -> u32 {
+        points_to_pixels(self.point_size, self.resolution.height)
+    }
+}
+
+/// Converts a size in decipoints to pixels on a display with `dpi` dots per inch, rounded
+/// to the nearest pixel.
+pub const fn points_to_pixels(decipoints: u32, dpi: u32) -> u32 {
+    (decipoints * dpi + DECIPOINTS_PER_INCH / 2) / DECIPOINTS_PER_INCH
+}
+
+/// Converts a size in pixels on a display with `dpi` dots per inch to decipoints, rounded
+/// to the nearest decipoint.
+pub const fn pixels_to_points(pixels: u32, dpi: u32) -> u32 {
+    (pixels * DECIPOINTS_PER_INCH + dpi / 2) / dpi
+}
+
+/// Returns the strike whose pixel size is closest to `decipoints` on a display with `dpi`
+/// dots per inch.
+///
+/// Fonts without a design size are skipped.
+pub fn closest_strike<'a>(
+    fonts: &[&'a PcfFont<'a>],
+    decipoints: u32,
+    dpi: u32,
+) -> Option<&'a PcfFont<'a>> {
+    let pixels = points_to_pixels(decipoints, dpi);
+
+    fonts
+        .iter()
+        .filter_map(|font| Some((*font, font.design_size?.pixel_size())))
+        .min_by_key(|(_, size)| size.abs_diff(pixels))
+        .map(|(font, _)| font)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::include_pcf;
+
+    const SMALL: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+    const LARGE: PcfFont = include_pcf!("examples/OpenSans-Regular-12.pcf", '0'..='9');
+
+    #[test]
+    fn test_conversion() {
+        assert_eq!(points_to_pixels(120, 72), 12);
+        assert_eq!(points_to_pixels(120, 96), 16);
+        assert_eq!(pixels_to_points(16, 96), 120);
+    }
+
+    #[test]
+    fn it_picks_the_closest_strike() {
+        let small = SMALL.design_size.unwrap();
+        assert_eq!(LARGE.design_size.unwrap().pixel_size(), 13);
+
+        let fonts = [&SMALL, &LARGE];
+        let strike = |decipoints, dpi| closest_strike(&fonts, decipoints, dpi).unwrap();
+
+        assert_eq!(strike(small.point_size, small.resolution.height), &SMALL);
+        assert_eq!(strike(120, 100), &LARGE);
+    }
+}
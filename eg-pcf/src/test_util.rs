@@ -0,0 +1,203 @@
+//! Helpers for rendering regression tests.
+//!
+//! Text is drawn into a 1bpp [`Frame`], which prints as rows of `#` for set and `.` for
+//! clear pixels. Rendering is compared against an expected picture in the same form, e.g.
+//! checked in next to the tests and loaded with `include_str!`.
+
+use core::{convert::Infallible, fmt};
+
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{renderer::TextRenderer, Baseline},
+};
+
+/// An in-memory 1bpp frame that stores up to `N * 8` pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Frame<const N: usize> {
+    size: Size,
+    bits: [u8; N],
+}
+
+impl<const N: usize> Frame<N> {
+    /// Creates a cleared frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` bytes can't hold `size` pixels.
+    pub fn new(size: Size) -> Self {
+        assert!(
+            (size.width * size.height).div_ceil(8) as usize <= N,
+            "frame too large"
+        );
+
+        Self { size, bits: [0; N] }
+    }
+
+    /// Returns whether the pixel at `point` is set, or `false` if it is outside the frame.
+    pub fn pixel(&self, point: Point) -> bool {
+        self.index(point)
+            .is_some_and(|i| self.bits[i / 8] & (0x80 >> (i % 8)) != 0)
+    }
+
+    /// Draws `text` with its alphabetic baseline at `position`.
+    pub fn draw_text<S>(&mut self, style: &S, text: &str, position: Point)
+    where
+        S: TextRenderer<Color = BinaryColor>,
+    {
+        style
+            .draw_string(text, position, Baseline::Alphabetic, self)
+            .ok();
+    }
+
+    /// Returns whether the frame matches the picture `expected`.
+    ///
+    /// Rows may be indented and don't need trailing clear pixels. Empty lines are ignored,
+    /// so clear rows have to be written as `.`, and rows missing at the bottom are clear.
+    pub fn matches(&self, expected: &str) -> bool {
+        let mut expected = expected
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+
+        (0..self.size.height as i32).all(|y| {
+            let row = expected.next().unwrap_or("");
+            let mut row = row.chars().chain(core::iter::repeat('.'));
+
+            (0..self.size.width as i32).all(|x| row.next() == Some(self.symbol(Point::new(x, y))))
+        }) && expected.next().is_none()
+    }
+
+    /// Panics with both pictures if the frame doesn't match `expected`.
+    ///
+    /// See [`matches`](Self::matches) for the format.
+    #[track_caller]
+    pub fn assert_matches(&self, expected: &str) {
+        assert!(
+            self.matches(expected),
+            "frame doesn't match\nexpected:\n{}\nactual:\n{}",
+            expected,
+            self
+        );
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        let (x, y) = (u32::try_from(point.x).ok()?, u32::try_from(point.y).ok()?);
+
+        (x < self.size.width && y < self.size.height).then_some((y * self.size.width + x) as usize)
+    }
+
+    fn symbol(&self, point: Point) -> char {
+        match self.pixel(point) {
+            true => '#',
+            false => '.',
+        }
+    }
+}
+
+/// Prints the frame without trailing clear pixels and rows, clear rows as `.`.
+impl<const N: usize> fmt::Display for Frame<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let set = |y| (0..self.size.width as i32).rposition(|x| self.pixel(Point::new(x, y)));
+        let rows = (0..self.size.height as i32)
+            .rposition(|y| set(y).is_some())
+            .map_or(0, |y| y + 1);
+
+        for y in 0..rows as i32 {
+            let width = set(y).map_or(1, |x| x + 1);
+            for x in 0..width as i32 {
+                write!(f, "{}", self.symbol(Point::new(x, y)))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> OriginDimensions for Frame<N> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<const N: usize> DrawTarget for Frame<N> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let Some(i) = self.index(point) else {
+                continue;
+            };
+
+            match color {
+                BinaryColor::On => self.bits[i / 8] |= 0x80 >> (i % 8),
+                BinaryColor::Off => self.bits[i / 8] &= !(0x80 >> (i % 8)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{include_pcf, text::PcfTextStyle, PcfFont};
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+
+    #[test]
+    fn it_matches_rendered_text() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut frame = Frame::<32>::new(Size::new(16, 10));
+        frame.draw_text(&style, "1", Point::new(0, 8));
+
+        frame.assert_matches(
+            r"
+            ..#
+            .##
+            #.#
+            ..#
+            ..#
+            ..#
+            #####
+            ",
+        );
+        assert!(!frame.matches("#"));
+    }
+
+    #[test]
+    fn it_prints_compactly() {
+        let mut frame = Frame::<2>::new(Size::new(4, 4));
+        Pixel(Point::new(1, 1), BinaryColor::On)
+            .draw(&mut frame)
+            .unwrap();
+
+        let mut text = [0; 8];
+        let mut cursor = Cursor(&mut text, 0);
+        fmt::write(&mut cursor, format_args!("{}", frame)).unwrap();
+
+        let len = cursor.1;
+
+        assert_eq!(&text[..len], b".\n.#\n");
+    }
+
+    struct Cursor<'b>(&'b mut [u8], usize);
+
+    impl fmt::Write for Cursor<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.1 + s.len();
+            self.0
+                .get_mut(self.1..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.1 = end;
+            Ok(())
+        }
+    }
+}
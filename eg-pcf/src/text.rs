@@ -3,25 +3,956 @@ use embedded_graphics::{
     primitives::Rectangle,
     text::{
         renderer::{CharacterStyle, TextMetrics, TextRenderer},
-        Baseline,
+        Baseline, DecorationColor,
     },
 };
 
-use crate::PcfFont;
+use crate::{
+    decoration::{Decoration, DecorationStyle},
+    dirty::DirtyTracker,
+    effect::TextEffect,
+    fixed::{Fixed, FixedPoint},
+    hexbox::HexBox,
+    kerning::KerningTable,
+    ligature::{CharMapper, LigatureTable, CHAR_MAPPER_LOOKAHEAD},
+    oriented::{Orientation, Oriented},
+    placement::{GlyphPlacement, Rotation},
+    scaled::Scaled,
+    script::Script,
+    shaping::{compose, is_combining_mark, PassThrough, ShapedGlyph, Shaper},
+    PcfFont, PcfGlyph,
+};
+
+// the char mapper is compared by address, so styles may compare unequal if its function is
+// duplicated across codegen units, which is harmless for comparing styles
+#[allow(unpredictable_function_pointer_comparisons)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PcfTextStyle<'a, C, S = PassThrough> {
+    pub(crate) font: &'a PcfFont<'a>,
+    pub(crate) color: C,
+    kerning: Option<KerningTable<'a>>,
+    ligatures: Option<LigatureTable<'a>>,
+    char_mapper: Option<CharMapper>,
+    hex_fallback: bool,
+    script: Script,
+    script_font: Option<&'a PcfFont<'a>>,
+    underline: Option<(Decoration, DecorationColor<C>)>,
+    strikethrough: Option<(Decoration, DecorationColor<C>)>,
+    letter_spacing: i32,
+    tracking: Fixed,
+    word_spacing: i32,
+    scale: u32,
+    orientation: Orientation,
+    tab_width: TabWidth,
+    weight: FontWeightEffect,
+    oblique: u32,
+    effect: Option<TextEffect<C>>,
+    combining_marks: bool,
+    shaper: S,
+}
 
+/// The distance between tab stops.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct PcfTextStyle<'a, C> {
-    font: &'a PcfFont<'a>,
-    color: C,
+pub enum TabWidth {
+    /// A number of space advances of the font.
+    Spaces(u32),
+    Pixels(u32),
+}
+
+/// A synthetic weight applied to glyphs when drawing text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FontWeightEffect {
+    /// Glyphs are drawn as they are.
+    #[default]
+    Regular,
+    /// Glyphs are drawn again shifted right by every offset up to the given number of
+    /// pixels, for fonts without a bold strike. Advances grow by the same amount.
+    Embolden(u32),
+}
+
+/// A glyph of a laid out string.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GlyphAdvance<'a> {
+    /// The character after shaping and ligature substitution.
+    pub character: char,
+    pub glyph: &'a PcfGlyph,
+    /// Set if the glyph is missing and drawn as a [`HexBox`].
+    pub hex_box: Option<HexBox>,
+    /// Distance from the origin of this glyph to the origin of the next one.
+    pub advance: i32,
+    /// Set if the font has no glyph for the character and the replacement glyph is used.
+    pub missing: bool,
+    /// Set for tabs and other control characters, which only move the pen.
+    pub blank: bool,
 }
 
 impl<'a, C: PixelColor> PcfTextStyle<'a, C> {
-    pub fn new(font: &'a PcfFont<'a>, color: C) -> Self {
-        Self { font, color }
+    pub const fn new(font: &'a PcfFont<'a>, color: C) -> Self {
+        Self {
+            font,
+            color,
+            kerning: None,
+            ligatures: None,
+            char_mapper: None,
+            hex_fallback: false,
+            script: Script::Normal,
+            script_font: None,
+            underline: None,
+            strikethrough: None,
+            letter_spacing: 0,
+            tracking: Fixed::ZERO,
+            word_spacing: 0,
+            scale: 1,
+            orientation: Orientation::new(Rotation::None),
+            tab_width: TabWidth::Spaces(8),
+            weight: FontWeightEffect::Regular,
+            oblique: 0,
+            effect: None,
+            combining_marks: false,
+            shaper: PassThrough,
+        }
     }
 }
 
-impl<C: PixelColor> CharacterStyle for PcfTextStyle<'_, C> {
+impl<'a, C: PixelColor, S: Shaper> PcfTextStyle<'a, C, S> {
+    /// Runs text through `shaper` before glyph lookup when drawing and measuring text.
+    pub fn with_shaper<T: Shaper>(self, shaper: T) -> PcfTextStyle<'a, C, T> {
+        PcfTextStyle {
+            font: self.font,
+            color: self.color,
+            kerning: self.kerning,
+            ligatures: self.ligatures,
+            char_mapper: self.char_mapper,
+            hex_fallback: self.hex_fallback,
+            script: self.script,
+            script_font: self.script_font,
+            underline: self.underline,
+            strikethrough: self.strikethrough,
+            letter_spacing: self.letter_spacing,
+            tracking: self.tracking,
+            word_spacing: self.word_spacing,
+            scale: self.scale,
+            orientation: self.orientation,
+            tab_width: self.tab_width,
+            weight: self.weight,
+            oblique: self.oblique,
+            effect: self.effect,
+            combining_marks: self.combining_marks,
+            shaper,
+        }
+    }
+
+    /// Applies the kerning pairs of `table` when drawing and measuring text.
+    pub const fn with_kerning(mut self, table: KerningTable<'a>) -> Self {
+        self.kerning = Some(table);
+        self
+    }
+
+    /// Replaces character sequences from `table` by ligature glyphs when drawing and
+    /// measuring text.
+    pub const fn with_ligatures(mut self, table: LigatureTable<'a>) -> Self {
+        self.ligatures = Some(table);
+        self
+    }
+
+    /// Runs `mapper` over the characters after shaping when drawing and measuring text, to
+    /// replace character sequences by single glyphs. It runs before the
+    /// [ligatures](Self::with_ligatures), which only apply where it keeps the characters.
+    pub const fn with_char_mapper(mut self, mapper: CharMapper) -> Self {
+        self.char_mapper = Some(mapper);
+        self
+    }
+
+    /// Draws characters that are missing from the font as a box containing their hex code
+    /// point instead of the replacement glyph.
+    pub const fn with_hex_fallback(mut self) -> Self {
+        self.hex_fallback = true;
+        self
+    }
+
+    /// Draws text as superscript or subscript by shifting the baseline by the font's
+    /// [`script_offsets`](PcfFont::script_offsets).
+    pub const fn with_script(mut self, script: Script) -> Self {
+        self.script = script;
+        self
+    }
+
+    /// Uses the glyphs of `font`, usually a smaller strike of the same family, for
+    /// superscript and subscript text.
+    pub const fn with_script_font(mut self, font: &'a PcfFont<'a>) -> Self {
+        self.script_font = Some(font);
+        self
+    }
+
+    /// Underlines text with `decoration` in `color`.
+    pub const fn with_underline(mut self, decoration: Decoration, color: C) -> Self {
+        self.underline = Some((decoration, DecorationColor::Custom(color)));
+        self
+    }
+
+    /// Strikes text through with `decoration` in `color`.
+    ///
+    /// See [`strikethrough`](Self::strikethrough) for a decoration at the default position.
+    pub const fn with_strikethrough(mut self, decoration: Decoration, color: C) -> Self {
+        self.strikethrough = Some((decoration, DecorationColor::Custom(color)));
+        self
+    }
+
+    /// Adds `spacing` pixels between characters, or removes them if negative.
+    pub const fn with_letter_spacing(mut self, spacing: i32) -> Self {
+        self.letter_spacing = spacing;
+        self
+    }
+
+    /// Adds a fraction of pixels between characters drawn with
+    /// [`draw_string_fixed`](Self::draw_string_fixed), or removes it if negative, e.g.
+    /// `Fixed::from_ratio(1, 3)` for a pixel every three characters.
+    ///
+    /// The tracking accumulates with the pen position and is scaled like letter spacing.
+    /// Only the fixed point methods apply it, as whole pixel positions can't carry the
+    /// fractions between characters.
+    pub const fn with_tracking(mut self, tracking: Fixed) -> Self {
+        self.tracking = tracking;
+        self
+    }
+
+    /// Adds `spacing` pixels to the advance of spaces, or removes them if negative.
+    pub const fn with_word_spacing(mut self, spacing: i32) -> Self {
+        self.word_spacing = spacing;
+        self
+    }
+
+    /// Draws every pixel of the font as a `scale` by `scale` block, e.g. for a double size
+    /// version of a font without including it twice. Advances, spacing, decorations and the
+    /// line height are scaled as well. A scale of 0 is treated as 1.
+    pub const fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = if scale == 0 { 1 } else { scale };
+        self
+    }
+
+    /// Rotates and mirrors text drawn with [`draw_string`](TextRenderer::draw_string) around
+    /// its start position, e.g. for displays mounted in portrait. Glyphs, advances and
+    /// decorations are transformed, and [`measure_string`](TextRenderer::measure_string)
+    /// returns the transformed metrics.
+    ///
+    /// The line height isn't transformed, so multi-line text should be laid out by drawing
+    /// each line at its own position.
+    pub const fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the distance between the tab stops `\t` advances to, measured from the start of
+    /// the string. Defaults to 8 spaces.
+    pub const fn with_tab_width(mut self, width: TabWidth) -> Self {
+        self.tab_width = width;
+        self
+    }
+
+    /// Emboldens glyphs for emphasis with fonts that lack a bold strike.
+    pub const fn with_weight(mut self, weight: FontWeightEffect) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Slants glyphs for emphasis with fonts that lack an italic strike, by shifting their
+    /// rows one pixel to the right every `rows` rows above the baseline. A value of 0 draws
+    /// glyphs upright.
+    ///
+    /// Advances don't change, but the bounding box returned by
+    /// [`measure_string`](TextRenderer::measure_string) includes the overhang of the last
+    /// glyph. Missing glyphs drawn as hex boxes aren't slanted.
+    pub const fn with_oblique(mut self, rows: u32) -> Self {
+        self.oblique = rows;
+        self
+    }
+
+    /// Draws `effect` behind the text.
+    pub const fn with_effect(mut self, effect: TextEffect<C>) -> Self {
+        self.effect = Some(effect);
+        self
+    }
+
+    /// Draws combining marks, like U+0301 COMBINING ACUTE ACCENT, with the precomposed glyph
+    /// of the preceding character if the font has one, e.g. `é` for `e` followed by U+0301.
+    /// Otherwise the mark is drawn over the preceding glyph without advancing the pen, which
+    /// suits the combining glyphs of character cell fonts.
+    #[cfg(feature = "unicode-normalization")]
+    pub const fn with_combining_marks(mut self) -> Self {
+        self.combining_marks = true;
+        self
+    }
+
+    /// Returns how far the top of the font bounding box is shifted right by the oblique
+    /// slant, in unscaled pixels.
+    fn oblique_overhang(&self) -> i32 {
+        match self.oblique {
+            0 => 0,
+            rows => (-2 - self.glyph_font().bounding_box.top_left.y).max(0) / rows as i32,
+        }
+    }
+
+    /// Returns the number of pixels glyphs are emboldened by.
+    fn embolden(&self) -> u32 {
+        match self.weight {
+            FontWeightEffect::Regular => 0,
+            FontWeightEffect::Embolden(pixels) => pixels,
+        }
+    }
+
+    /// Returns the distance between tab stops in unscaled pixels.
+    fn tab_stop(&self) -> i32 {
+        match self.tab_width {
+            TabWidth::Spaces(spaces) => {
+                let space = self.glyph_font().get_glyph(' ').device_width;
+                spaces as i32 * space
+            }
+            TabWidth::Pixels(pixels) => pixels as i32,
+        }
+    }
+
+    /// Returns whether the glyphs of `text` depend on the characters next to them, or on
+    /// where the text starts, or aren't drawn left to right, so that a changed part of the
+    /// text can't be redrawn on its own.
+    pub(crate) fn is_contextual(&self, text: &str) -> bool {
+        !self.orientation.is_identity()
+            || self.kerning.is_some()
+            || self.ligatures.is_some()
+            || self.char_mapper.is_some()
+            || self.combining_marks
+            // tab stops are relative to the start of the text
+            || text.contains('\t')
+            || !self
+                .shaper
+                .map(text)
+                .map(|glyph| glyph.character)
+                .eq(text.chars())
+    }
+
+    /// Returns the number of shaped glyphs and their unscaled advance without looking up
+    /// glyphs, if the font is monospaced and the style spaces all glyphs alike.
+    fn monospace_glyphs(&self, glyphs: impl Iterator<Item = ShapedGlyph>) -> Option<(i32, i32)> {
+        let advance = self.glyph_font().monospace_advance?;
+        if self.kerning.is_some()
+            || self.ligatures.is_some()
+            || self.char_mapper.is_some()
+            || self.hex_fallback
+            || self.word_spacing != 0
+            || self.combining_marks
+        {
+            return None;
+        }
+
+        let mut count = 0;
+        for glyph in glyphs {
+            // tabs and other control characters have their own advances
+            if glyph.character.is_control() {
+                return None;
+            }
+            count += 1;
+        }
+
+        Some((count, advance + self.embolden() as i32))
+    }
+
+    /// Returns the scaled advance of `count` glyphs of a monospaced string.
+    fn monospace_width(&self, count: i32, advance: i32) -> i32 {
+        let letter_spacing = (count - 1).max(0) * self.letter_spacing;
+
+        (count * advance + letter_spacing) * self.scale as i32
+    }
+
+    /// Returns the bounding box of the font, scaled by the [`scale`](Self::with_scale) around
+    /// the same row as glyphs.
+    pub(crate) fn font_box(&self) -> Rectangle {
+        let font_box = self.font.bounding_box;
+        let origin = Point::new(0, -1);
+
+        Rectangle::new(
+            origin + (font_box.top_left - origin) * self.scale as i32,
+            font_box.size * self.scale,
+        )
+    }
+
+    /// Returns a solid strikethrough through the middle of lowercase letters, which are
+    /// assumed to be two thirds of the font ascent high.
+    pub fn strikethrough(&self) -> Decoration {
+        // glyphs without descenders end two rows above the baseline position
+        let ascent = -self.glyph_font().bounding_box.top_left.y - 1;
+
+        Decoration::new(DecorationStyle::Solid).with_offset(-2 - ascent / 3)
+    }
+
+    /// Returns the font glyphs are taken from.
+    fn glyph_font(&self) -> &'a PcfFont<'a> {
+        match (self.script, self.script_font) {
+            (Script::Superscript | Script::Subscript, Some(font)) => font,
+            _ => self.font,
+        }
+    }
+
+    /// Returns the characters of shaped glyphs after ligature substitution.
+    fn chars<'t>(
+        &'t self,
+        glyphs: impl Iterator<Item = ShapedGlyph> + Clone + 't,
+    ) -> impl Iterator<Item = char> + Clone + 't {
+        let mut chars = glyphs.map(|glyph| glyph.character);
+
+        core::iter::from_fn(move || {
+            let ligature = self
+                .char_mapper
+                .and_then(|mapper| {
+                    let mut lookahead = [' '; CHAR_MAPPER_LOOKAHEAD];
+                    let len = lookahead
+                        .iter_mut()
+                        .zip(chars.clone())
+                        .map(|(slot, c)| *slot = c)
+                        .count();
+                    if len == 0 {
+                        return None;
+                    }
+                    let (c, mapped) = mapper(&lookahead[..len], self.glyph_font())?;
+
+                    Some((c, mapped.clamp(1, len)))
+                })
+                .or_else(|| {
+                    self.ligatures
+                        .and_then(|table| table.find(chars.clone(), self.glyph_font()))
+                });
+
+            match ligature {
+                Some((c, len)) => {
+                    chars.nth(len - 1);
+                    Some(c)
+                }
+                None => chars.next(),
+            }
+        })
+    }
+
+    /// Returns the characters of shaped glyphs in drawing order, with combining marks
+    /// composed with the preceding character if the font has a glyph for the composition,
+    /// and moved in front of it otherwise, so they are drawn at its origin.
+    fn combined_chars<'t>(
+        &'t self,
+        glyphs: impl Iterator<Item = ShapedGlyph> + Clone + 't,
+    ) -> impl Iterator<Item = char> + Clone + 't {
+        let mut chars = self.chars(glyphs).peekable();
+        let font = self.glyph_font();
+        // the base character, held back while the marks that follow it are returned
+        let mut base = None;
+
+        core::iter::from_fn(move || {
+            if !self.combining_marks {
+                return chars.next();
+            }
+
+            let mut c = match base {
+                Some(c) => c,
+                None => {
+                    let mut c = chars.next()?;
+                    while let Some(composed) = chars
+                        .peek()
+                        .and_then(|mark| compose(c, *mark))
+                        .filter(|composed| font.glyph(*composed).is_some())
+                    {
+                        c = composed;
+                        chars.next();
+                    }
+                    c
+                }
+            };
+
+            match chars.next_if(|mark| is_combining_mark(*mark) && !is_combining_mark(c)) {
+                Some(mark) => {
+                    base = Some(c);
+                    c = mark;
+                }
+                None => base = None,
+            }
+
+            Some(c)
+        })
+    }
+
+    /// Returns the glyphs of a string along with their advances.
+    pub(crate) fn advances<'t>(
+        &'t self,
+        text: &'t str,
+    ) -> impl Iterator<Item = GlyphAdvance<'a>> + Clone + 't {
+        self.shaped_advances(self.shaper.map(text))
+    }
+
+    /// Returns the font glyphs of shaped glyphs along with their advances.
+    ///
+    /// Text is shaped once per call, and passes over the same text clone the shaped glyphs,
+    /// as shapers like `Bidi` have to see the whole text.
+    fn shaped_advances<'t>(
+        &'t self,
+        glyphs: impl Iterator<Item = ShapedGlyph> + Clone + 't,
+    ) -> impl Iterator<Item = GlyphAdvance<'a>> + Clone + 't {
+        let mut chars = self.combined_chars(glyphs).peekable();
+        let font = self.glyph_font();
+        let tab_stop = self.tab_stop();
+        // the unscaled pen position, for tab stops
+        let mut x = 0i32;
+
+        core::iter::from_fn(move || {
+            let c = chars.next()?;
+
+            // control characters have no glyphs, tabs move to the next tab stop
+            if c.is_control() {
+                let advance = match c {
+                    '\t' if tab_stop > 0 => tab_stop - x.rem_euclid(tab_stop),
+                    _ => 0,
+                };
+                x += advance;
+
+                return Some(GlyphAdvance {
+                    character: c,
+                    glyph: font.get_glyph(' '),
+                    hex_box: None,
+                    advance: advance * self.scale as i32,
+                    missing: false,
+                    blank: true,
+                });
+            }
+
+            let (glyph, missing) = match font.glyph(c) {
+                Some(glyph) => (glyph, false),
+                None => (font.get_glyph(c), true),
+            };
+
+            // the box sits on the baseline, inside the font bounding box
+            let height = (-font.bounding_box.top_left.y).max(0) as u32;
+            let hex_box = (missing && self.hex_fallback).then(|| HexBox::fitting(c, height));
+
+            // combining marks precede their base character and are drawn at its origin
+            if self.combining_marks && is_combining_mark(c) {
+                return Some(GlyphAdvance {
+                    character: c,
+                    glyph,
+                    hex_box,
+                    advance: 0,
+                    missing,
+                    blank: false,
+                });
+            }
+
+            let kerning = match (self.kerning, chars.peek()) {
+                (Some(table), Some(next)) => table.adjustment(c, *next),
+                _ => 0,
+            };
+            let letter_spacing = match chars.peek() {
+                Some(_) => self.letter_spacing,
+                None => 0,
+            };
+            let word_spacing = match c {
+                ' ' => self.word_spacing,
+                _ => 0,
+            };
+            let width = match hex_box {
+                Some(hex_box) => hex_box.advance(),
+                None => glyph.device_width,
+            } + self.embolden() as i32;
+
+            let advance = width + kerning + letter_spacing + word_spacing;
+            x += advance;
+
+            Some(GlyphAdvance {
+                character: c,
+                glyph,
+                hex_box,
+                advance: advance * self.scale as i32,
+                missing,
+                blank: false,
+            })
+        })
+    }
+
+    /// Returns the glyphs of a string with their advances in 26.6 fixed point, which
+    /// include the [tracking](Self::with_tracking) between characters.
+    fn fixed_advances<'t>(
+        &'t self,
+        glyphs: impl Iterator<Item = ShapedGlyph> + Clone + 't,
+    ) -> impl Iterator<Item = (GlyphAdvance<'a>, Fixed)> + 't {
+        let tracking = Fixed::from_bits(self.tracking.to_bits() * self.scale as i32);
+        let mut glyphs = self.shaped_advances(glyphs).peekable();
+
+        core::iter::from_fn(move || {
+            let g = glyphs.next()?;
+            // like letter spacing, tracking isn't added after the last glyph or to marks
+            let mark = self.combining_marks && is_combining_mark(g.character);
+            let tracking = match glyphs.peek() {
+                Some(_) if !mark => tracking,
+                _ => Fixed::ZERO,
+            };
+
+            Some((g, Fixed::from_int(g.advance) + tracking))
+        })
+    }
+
+    /// Draws a string at a sub-pixel position.
+    ///
+    /// The pen position is accumulated in 26.6 fixed point, including fractional
+    /// [tracking](Self::with_tracking), and only rounded when a glyph is drawn, so text
+    /// that moves by fractional amounts between frames doesn't judder. Returns the position
+    /// following the last glyph.
+    pub fn draw_string_fixed<D>(
+        &self,
+        text: &str,
+        mut position: FixedPoint,
+        target: &mut D,
+    ) -> Result<FixedPoint, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let start = position.round();
+        let glyphs = self.shaper.map(text);
+
+        if self.effect.is_some() {
+            let mut pen = position;
+            for (g, advance) in self.fixed_advances(glyphs.clone()) {
+                self.draw_glyph_effect(&g, pen.round(), Rotation::None, target)?;
+                pen.x += advance;
+            }
+        }
+
+        for (g, advance) in self.fixed_advances(glyphs) {
+            self.draw_glyph(&g, position.round(), Rotation::None, target)?;
+
+            position.x += advance;
+        }
+
+        self.draw_decorations(start, position.round().x, target)?;
+
+        Ok(position)
+    }
+
+    /// Draws each glyph of a string at its own placement.
+    ///
+    /// This is the building block for text that doesn't follow a straight baseline, see
+    /// [`arc`](crate::placement::arc) and [`wave`](crate::placement::wave). Glyphs are
+    /// shaped as usual and drawing stops when either the glyphs or the placements run out.
+    pub fn draw_glyphs_with<I, D>(
+        &self,
+        text: &str,
+        placements: I,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        I: IntoIterator<Item = GlyphPlacement>,
+        D: DrawTarget<Color = C>,
+    {
+        // placements are only iterated once, so effects are drawn glyph by glyph
+        for (g, placement) in self.advances(text).zip(placements) {
+            self.draw_glyph_effect(&g, placement.position, placement.rotation, target)?;
+            self.draw_glyph(&g, placement.position, placement.rotation, target)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn draw_glyph<D>(
+        &self,
+        g: &GlyphAdvance,
+        position: Point,
+        rotation: Rotation,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        self.draw_glyph_layer(g, position, rotation, Point::zero(), self.color, target)
+    }
+
+    /// Draws the [`effect`](Self::with_effect) of a glyph, if any.
+    ///
+    /// Effects of all glyphs of a string are drawn before the glyphs themselves where
+    /// possible.
+    pub(crate) fn draw_glyph_effect<D>(
+        &self,
+        g: &GlyphAdvance,
+        position: Point,
+        rotation: Rotation,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let Some(effect) = self.effect else {
+            return Ok(());
+        };
+
+        for offset in effect.offsets() {
+            self.draw_glyph_layer(g, position, rotation, offset, effect.color(), target)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws a glyph offset by `offset` unscaled pixels in `color`.
+    fn draw_glyph_layer<D>(
+        &self,
+        g: &GlyphAdvance,
+        position: Point,
+        rotation: Rotation,
+        offset: Point,
+        color: C,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if g.blank {
+            return Ok(());
+        }
+
+        match self.scale {
+            1 => self.draw_unscaled_glyph(g, position, rotation, offset, color, target),
+            scale => {
+                let mut scaled = self.scaled(target, position, scale);
+                self.draw_unscaled_glyph(g, position, rotation, offset, color, &mut scaled)
+            }
+        }
+    }
+
+    /// Returns `target` scaled around the row below glyphs without descenders, so that
+    /// glyphs line up with fonts scaled by the `scale` option of `include_pcf!`.
+    fn scaled<'d, D: DrawTarget>(
+        &self,
+        target: &'d mut D,
+        position: Point,
+        scale: u32,
+    ) -> Scaled<'d, D> {
+        Scaled::new(target, position - Point::new(0, 1), Size::new(scale, scale))
+    }
+
+    fn draw_unscaled_glyph<D>(
+        &self,
+        g: &GlyphAdvance,
+        position: Point,
+        rotation: Rotation,
+        offset: Point,
+        color: C,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let shift = self.font.script_offsets.baseline_shift(self.script);
+
+        for dx in 0..=self.embolden() as i32 {
+            let position = position + rotation.apply(offset + Point::new(dx, shift));
+            match g.hex_box {
+                Some(hex_box) => hex_box.draw_rotated(position, rotation, color, target)?,
+                None => g.glyph.draw_sheared(
+                    position,
+                    rotation,
+                    self.oblique,
+                    color,
+                    &self.glyph_font().data,
+                    target,
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draws the decorations of a span of text that starts at `position` and ends at `end`.
+    pub(crate) fn draw_decorations<D>(
+        &self,
+        position: Point,
+        end: i32,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let width = (end - position.x).max(0) as u32;
+
+        for (decoration, color) in [self.underline, self.strikethrough].into_iter().flatten() {
+            let color = match color {
+                DecorationColor::None => continue,
+                DecorationColor::TextColor => self.color,
+                DecorationColor::Custom(color) => color,
+            };
+            match self.scale {
+                1 => decoration.draw(position, width, color, target)?,
+                scale => {
+                    let mut scaled = self.scaled(target, position, scale);
+                    decoration.draw(position, width.div_ceil(scale), color, &mut scaled)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draws a string along a horizontal baseline and returns the position following it.
+    fn draw_line<D>(
+        &self,
+        text: &str,
+        mut position: Point,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        trace!("draw_string: start, {} bytes", text.len());
+        let start = position;
+        let shaped = self.shaper.map(text);
+        let mut glyphs = 0u32;
+        let mut missing = 0u32;
+
+        if self.effect.is_some() {
+            let mut pen = position;
+            for g in self.shaped_advances(shaped.clone()) {
+                self.draw_glyph_effect(&g, pen, Rotation::None, target)?;
+                pen.x += g.advance;
+            }
+        }
+
+        for g in self.shaped_advances(shaped) {
+            self.draw_glyph(&g, position, Rotation::None, target)?;
+
+            position.x += g.advance;
+            glyphs += 1;
+            missing += u32::from(g.missing);
+        }
+
+        self.draw_decorations(start, position.x, target)?;
+
+        trace!("draw_string: end, {} glyphs, {} missing", glyphs, missing);
+
+        Ok(position)
+    }
+
+    /// Draws a string like [`draw_string`](TextRenderer::draw_string) and returns the
+    /// position following it along with the bounding box of the pixels that were drawn.
+    ///
+    /// This lets displays with partial refresh only flush the area the text touched. The
+    /// area is a zero sized rectangle if no pixels were drawn, e.g. for spaces. Wrap the
+    /// target in a [`DirtyTracker`] to track the area of several calls.
+    pub fn draw_string_tracked<D>(
+        &self,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<(Point, Rectangle), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let mut tracker = DirtyTracker::new(target);
+        let next = self.draw_string(text, position, baseline, &mut tracker)?;
+
+        Ok((next, tracker.dirty_area()))
+    }
+
+    /// Returns the characters of a string along with their advances in pixels.
+    ///
+    /// The characters are the ones drawn after shaping and ligature substitution, and the
+    /// advances include kerning, so they add up to the width of the string. This can be
+    /// used to implement cursor movement, selection or column alignment. Combining
+    /// characters can have zero or negative advances.
+    pub fn measure_chars<'t>(&'t self, text: &'t str) -> impl Iterator<Item = (char, i32)> + 't {
+        self.advances(text).map(|g| (g.character, g.advance))
+    }
+
+    /// Returns the x offset of the start of character `index` of `text`, relative to the
+    /// drawing position.
+    ///
+    /// Indices past the end of the text return the width of the whole text, the position of
+    /// a cursor after the last character. With a shaper that reorders characters, like
+    /// `Bidi`, the start of a character is the left edge of its glyph.
+    pub fn position_of_char(&self, text: &str, index: usize) -> i32 {
+        if let Some(mut visual) = self.shaper.visual_indices(text) {
+            let glyphs = self.shaper.map(text);
+            return match visual.nth(index) {
+                Some(visual) => self.width_of(glyphs.take(visual)),
+                None => self.width_of(glyphs),
+            };
+        }
+
+        let end = text
+            .char_indices()
+            .nth(index)
+            .map_or(text.len(), |(i, _)| i);
+        let glyphs = self.shaper.map(&text[..end]);
+
+        if let Some((count, advance)) = self.monospace_glyphs(glyphs.clone()) {
+            return self.monospace_width(count, advance);
+        }
+
+        self.width_of(glyphs)
+    }
+
+    /// Returns the index of the character boundary closest to `x`, relative to the drawing
+    /// position.
+    ///
+    /// The index is in `0..=text.chars().count()`, so a touch on the right half of a
+    /// character places a cursor after it. This is the inverse of
+    /// [`position_of_char`](Self::position_of_char), so with a shaper that reorders
+    /// characters it returns the character whose glyph has the closest left edge.
+    pub fn char_index_at(&self, text: &str, x: i32) -> usize {
+        if let Some(visual) = self.shaper.visual_indices(text) {
+            let glyphs = self.shaper.map(text);
+            let mut start = 0;
+            for glyph in 0..glyphs.clone().count() {
+                let end = self.width_of(glyphs.clone().take(glyph + 1));
+                if x < start + (end - start) / 2 {
+                    // the character drawn as the glyph
+                    return visual.clone().position(|i| i == glyph).unwrap_or(glyph);
+                }
+                start = end;
+            }
+
+            return text.chars().count();
+        }
+
+        // every character has a glyph of the same advance, unless the shaper combines them
+        let monospace = self
+            .monospace_glyphs(self.shaper.map(text))
+            .filter(|(count, _)| *count as usize == text.chars().count());
+
+        let mut start = 0;
+        for (index, (i, c)) in text.char_indices().enumerate() {
+            let end = match monospace {
+                Some((_, advance)) => self.monospace_width(index as i32 + 1, advance),
+                None => self.width_of(self.shaper.map(&text[..i + c.len_utf8()])),
+            };
+            if x < start + (end - start) / 2 {
+                return index;
+            }
+            start = end;
+        }
+
+        text.chars().count()
+    }
+
+    /// Returns the sum of the advances of shaped glyphs.
+    fn width_of(&self, glyphs: impl Iterator<Item = ShapedGlyph> + Clone) -> i32 {
+        self.shaped_advances(glyphs).map(|g| g.advance).sum()
+    }
+
+    /// Returns the advance of a string in 26.6 fixed point, including the
+    /// [tracking](Self::with_tracking) between characters.
+    pub fn measure_string_fixed(&self, text: &str) -> Fixed {
+        let glyphs = self.shaper.map(text);
+        if let Some((count, advance)) = self.monospace_glyphs(glyphs.clone()) {
+            let tracking = self.tracking.to_bits() * self.scale as i32 * (count - 1).max(0);
+            return Fixed::from_int(self.monospace_width(count, advance))
+                + Fixed::from_bits(tracking);
+        }
+
+        self.fixed_advances(glyphs)
+            .fold(Fixed::ZERO, |width, (_, advance)| width + advance)
+    }
+}
+
+impl<C: PixelColor, S: Shaper + Clone> CharacterStyle for PcfTextStyle<'_, C, S> {
     type Color = C;
 
     fn set_text_color(&mut self, text_color: Option<Self::Color>) {
@@ -31,16 +962,35 @@ impl<C: PixelColor> CharacterStyle for PcfTextStyle<'_, C> {
         }
     }
 
-    // TODO: implement additional methods
+    /// Underlines text with a solid line, or keeps the decoration set by
+    /// [`with_underline`](PcfTextStyle::with_underline) and only changes its color.
+    fn set_underline_color(&mut self, underline_color: DecorationColor<Self::Color>) {
+        self.underline = match (underline_color, self.underline) {
+            (DecorationColor::None, _) => None,
+            (color, Some((decoration, _))) => Some((decoration, color)),
+            (color, None) => Some((Decoration::default(), color)),
+        };
+    }
+
+    /// Strikes text through with [`strikethrough`](PcfTextStyle::strikethrough), or keeps
+    /// the decoration set by [`with_strikethrough`](PcfTextStyle::with_strikethrough) and
+    /// only changes its color.
+    fn set_strikethrough_color(&mut self, strikethrough_color: DecorationColor<Self::Color>) {
+        self.strikethrough = match (strikethrough_color, self.strikethrough) {
+            (DecorationColor::None, _) => None,
+            (color, Some((decoration, _))) => Some((decoration, color)),
+            (color, None) => Some((self.strikethrough(), color)),
+        };
+    }
 }
 
-impl<C: PixelColor> TextRenderer for PcfTextStyle<'_, C> {
+impl<C: PixelColor, S: Shaper> TextRenderer for PcfTextStyle<'_, C, S> {
     type Color = C;
 
     fn draw_string<D>(
         &self,
         text: &str,
-        mut position: Point,
+        position: Point,
         _baseline: Baseline,
         target: &mut D,
     ) -> Result<Point, D::Error>
@@ -49,15 +999,14 @@ impl<C: PixelColor> TextRenderer for PcfTextStyle<'_, C> {
     {
         // TODO: handle baseline
 
-        for c in text.chars() {
-            let glyph = self.font.get_glyph(c);
-
-            glyph.draw(position, self.color, self.font.data, target)?;
-
-            position.x += glyph.device_width as i32;
+        if self.orientation.is_identity() {
+            return self.draw_line(text, position, target);
         }
 
-        Ok(position)
+        let mut oriented = Oriented::new(target, position, self.orientation);
+        let end = self.draw_line(text, position, &mut oriented)?;
+
+        Ok(position + self.orientation.apply(end - position))
     }
 
     fn draw_whitespace<D>(
@@ -65,34 +1014,718 @@ impl<C: PixelColor> TextRenderer for PcfTextStyle<'_, C> {
         width: u32,
         position: Point,
         _baseline: Baseline,
-        _target: &mut D,
+        target: &mut D,
     ) -> Result<Point, D::Error>
     where
         D: DrawTarget<Color = Self::Color>,
     {
         // TODO: handle baseline
 
-        Ok(position + Size::new(width, 0))
+        let end = position + Size::new(width, 0);
+        match self.orientation.is_identity() {
+            true => self.draw_decorations(position, end.x, target)?,
+            false => {
+                let mut oriented = Oriented::new(target, position, self.orientation);
+                self.draw_decorations(position, end.x, &mut oriented)?;
+            }
+        }
+
+        Ok(position + self.orientation.apply(end - position))
     }
 
     fn measure_string(&self, text: &str, position: Point, _baseline: Baseline) -> TextMetrics {
-        let glyphs = text.chars().map(|c| self.font.get_glyph(c));
         // TODO: handle baseline
-        let dx = glyphs.clone().map(|g| g.device_width).sum();
 
-        let height = glyphs
-            .map(|g| g.bounding_box.size.height)
-            .max()
-            .unwrap_or(0);
+        // glyphs with negative advances move the pen back, so the text spans from the
+        // leftmost to the rightmost pen position rather than from the start to the end
+        let glyphs = self.shaper.map(text);
+        let (dx, left, right) = match self.monospace_glyphs(glyphs.clone()) {
+            Some((count, advance)) => {
+                let dx = self.monospace_width(count, advance);
+                (dx, dx.min(0), dx.max(0))
+            }
+            None => self
+                .shaped_advances(glyphs)
+                .fold((0, 0, 0), |(dx, left, right), g| {
+                    let dx = dx + g.advance;
+                    (dx, left.min(dx), right.max(dx))
+                }),
+        };
+
+        let overhang = match right - left {
+            0 => 0,
+            _ => self.oblique_overhang() * self.scale as i32,
+        };
 
-        // TODO: validate bounding box
+        // the text spans the advances and the height of the font bounding box, like the
+        // cells of monospaced fonts
+        let font_box = self.font_box();
+        let width = (right - left + overhang) as u32;
+        let bounding_box = Rectangle::new(
+            Point::new(left, font_box.top_left.y),
+            Size::new(width, font_box.size.height),
+        );
         TextMetrics {
-            bounding_box: Rectangle::new(position, Size::new(dx, height)),
-            next_position: position + Size::new(dx, 0),
+            bounding_box: self
+                .orientation
+                .apply_to_rectangle(&bounding_box)
+                .translate(position),
+            next_position: position + self.orientation.apply(Point::new(dx, 0)),
         }
     }
 
     fn line_height(&self) -> u32 {
-        self.font.line_height
+        self.font.line_height * self.scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        decoration::DecorationStyle, include_kerning, include_pcf, ligature::Ligature,
+        shaping::ShapedGlyph,
+    };
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const FONT: PcfFont = include_pcf!("examples/OpenSans-Regular-12.pcf", 'A'..='Z');
+
+    fn width<S: Shaper>(style: PcfTextStyle<'_, BinaryColor, S>, text: &str) -> i32 {
+        style
+            .measure_string(text, Point::zero(), Baseline::Alphabetic)
+            .next_position
+            .x
+    }
+
+    #[test]
+    fn it_applies_kerning() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let kerned = style.with_kerning(include_kerning!("examples/kerning.txt"));
+
+        assert_eq!(width(kerned, "AVA"), width(style, "AVA") - 2);
+        assert_eq!(width(kerned, "AA"), width(style, "AA"));
+    }
+
+    #[derive(Clone)]
+    struct Uppercase;
+
+    impl Shaper for Uppercase {
+        fn map<'t>(&'t self, text: &'t str) -> impl Iterator<Item = ShapedGlyph> + Clone + 't {
+            text.chars()
+                .map(|c| ShapedGlyph::from(c.to_ascii_uppercase()))
+        }
+    }
+
+    #[test]
+    fn it_measures_kerned_chars() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On)
+            .with_kerning(include_kerning!("examples/kerning.txt"));
+        let advance = |c| FONT.glyph(c).unwrap().device_width;
+
+        let mut chars = style.measure_chars("AVA");
+        assert_eq!(chars.next(), Some(('A', advance('A') - 1)));
+        assert_eq!(chars.next(), Some(('V', advance('V') - 1)));
+        assert_eq!(chars.next(), Some(('A', advance('A'))));
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn it_runs_the_shaper_before_ligatures() {
+        let table = LigatureTable::new(&[Ligature {
+            sequence: "AB",
+            replacement: 'W',
+        }]);
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let shaped = style.with_ligatures(table).with_shaper(Uppercase);
+
+        assert_eq!(width(shaped, "abc"), width(style, "WC"));
+    }
+
+    #[test]
+    fn it_draws_missing_glyphs_as_hex_boxes() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On).with_hex_fallback();
+        // the font is too short for two rows of digits
+        let hex_box = HexBox::fitting('a', -FONT.bounding_box.top_left.y as u32);
+        assert_eq!(hex_box.size().height, 9);
+        let advance = hex_box.advance();
+
+        assert_eq!(width(style, "AaA"), 2 * width(style, "A") + advance);
+
+        let mut display = MockDisplay::new();
+        style
+            .draw_string("a", Point::new(0, 15), Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        hex_box
+            .draw(Point::new(0, 15), BinaryColor::On, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn it_raises_superscript_text() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let superscript = style.with_script(Script::Superscript);
+        assert_eq!(FONT.script_offsets.superscript, 4);
+
+        let mut display = MockDisplay::new();
+        superscript
+            .draw_string("A", Point::new(0, 20), Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        style
+            .draw_string("A", Point::new(0, 16), Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn it_underlines_the_advance_width() {
+        let decoration = Decoration::new(DecorationStyle::Wavy);
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let underlined = style.with_underline(decoration, BinaryColor::On);
+        let position = Point::new(0, 12);
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        underlined
+            .draw_string("AB", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        let end = style
+            .draw_string("AB", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        decoration
+            .draw(position, end.x as u32, BinaryColor::On, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn it_strikes_through_lowercase_letters() {
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'x');
+        let mut style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let position = Point::new(0, 12);
+        let strikethrough = style.strikethrough();
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        let end = style
+            .draw_string("x", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        strikethrough
+            .draw(position, end.x as u32, BinaryColor::On, &mut expected)
+            .unwrap();
+
+        style.set_strikethrough_color(DecorationColor::TextColor);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        style
+            .draw_string("x", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        display.assert_eq(&expected);
+
+        let x = FONT.glyph('x').unwrap().bounding_box.translate(position);
+        let row = position.y + strikethrough.offset;
+        assert!((x.top_left.y..=x.bottom_right().unwrap().y).contains(&row));
+
+        style.set_strikethrough_color(DecorationColor::None);
+        let mut plain = MockDisplay::new();
+        style
+            .draw_string("x", position, Baseline::Alphabetic, &mut plain)
+            .unwrap();
+        assert_ne!(plain, expected);
+    }
+
+    #[test]
+    fn it_scales_glyphs_advances_and_line_height() {
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", '0'..='9');
+        let doubled = include_pcf!("examples/6x10.pcf", '0'..='9', scale = (2, 2));
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On).with_scale(2);
+        let expected_style = PcfTextStyle::new(&doubled, BinaryColor::On);
+        let position = Point::new(0, 20);
+
+        let mut display = MockDisplay::new();
+        let end = style
+            .draw_string("12", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        let expected_end = expected_style
+            .draw_string("12", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+
+        display.assert_eq(&expected);
+        assert_eq!(end, expected_end);
+        assert_eq!(style.line_height(), expected_style.line_height());
+        assert_eq!(
+            style.measure_string("12", position, Baseline::Alphabetic),
+            expected_style.measure_string("12", position, Baseline::Alphabetic)
+        );
+    }
+
+    #[test]
+    fn it_draws_rotated_and_mirrored_text() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let orientation = Orientation::new(Rotation::Clockwise90).mirrored();
+        let oriented = style.with_orientation(orientation);
+        let (start, position) = (Point::new(0, 20), Point::new(30, 10));
+
+        let mut upright = MockDisplay::new();
+        let end = style
+            .draw_string("AB", start, Baseline::Alphabetic, &mut upright)
+            .unwrap();
+
+        let mut display = MockDisplay::new();
+        let next = oriented
+            .draw_string("AB", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        let size = upright.bounding_box().size;
+        for p in Rectangle::new(Point::zero(), size).points() {
+            if let Some(color) = upright.get_pixel(p) {
+                let q = position + orientation.apply_to_pixel(p - start);
+                Pixel(q, color).draw(&mut expected).unwrap();
+            }
+        }
+        display.assert_eq(&expected);
+
+        // the text runs down from the start position, mirrored to the left of it
+        assert_eq!(next, position + Point::new(0, end.x - start.x));
+        let metrics = oriented.measure_string("AB", position, Baseline::Alphabetic);
+        assert_eq!(metrics.next_position, next);
+        assert_eq!(metrics.bounding_box.size.height, (end.x - start.x) as u32);
+    }
+
+    #[test]
+    fn it_shapes_text_once_per_call() {
+        #[derive(Clone)]
+        struct Counting<'c>(&'c core::cell::Cell<u32>);
+
+        impl Shaper for Counting<'_> {
+            fn map<'t>(&'t self, text: &'t str) -> impl Iterator<Item = ShapedGlyph> + Clone + 't {
+                self.0.set(self.0.get() + 1);
+                text.chars().map(ShapedGlyph::from)
+            }
+        }
+
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'a'..='z');
+        let calls = core::cell::Cell::new(0);
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On)
+            .with_effect(TextEffect::shadow(BinaryColor::Off))
+            .with_ligatures(LigatureTable::new(&[]))
+            .with_shaper(Counting(&calls));
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        style
+            .draw_string("abc", Point::new(0, 9), Baseline::Alphabetic, &mut display)
+            .unwrap();
+        assert_eq!(calls.replace(0), 1);
+        style.measure_string("abc", Point::zero(), Baseline::Alphabetic);
+        assert_eq!(calls.replace(0), 1);
+        style.position_of_char("abc", 2);
+        assert_eq!(calls.replace(0), 1);
+    }
+
+    #[cfg(feature = "unicode-bidi")]
+    #[test]
+    fn it_maps_char_indices_of_reordered_text_to_their_glyphs() {
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'a'..='z' | '\u{5d0}'..='\u{5d1}');
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On).with_shaper(crate::shaping::Bidi);
+        // drawn as `ab` followed by U+05D1 and U+05D0
+        let text = "ab\u{5d0}\u{5d1}";
+
+        let positions = (0..=4).map(|index| style.position_of_char(text, index));
+        assert!(positions.eq([0, 6, 18, 12, 24]));
+        for (x, index) in [(2, 0), (13, 3), (19, 2), (23, 4)] {
+            assert_eq!(style.char_index_at(text, x), index, "{x}");
+        }
+    }
+
+    #[test]
+    fn it_accumulates_fractional_tracking() {
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'a'..='z');
+        let style =
+            PcfTextStyle::new(&FONT, BinaryColor::On).with_tracking(Fixed::from_ratio(1, 3));
+
+        let mut display = MockDisplay::new();
+        let start = FixedPoint::new(Fixed::ZERO, Fixed::from_int(9));
+        let next = style.draw_string_fixed("abc", start, &mut display).unwrap();
+
+        // rounding the tracking of each glyph would draw `c` at 12
+        let mut expected = MockDisplay::new();
+        for (c, x) in [("a", 0), ("b", 6), ("c", 13)] {
+            style
+                .draw_string(c, Point::new(x, 9), Baseline::Alphabetic, &mut expected)
+                .unwrap();
+        }
+        display.assert_eq(&expected);
+        assert_eq!(next.x, Fixed::from_int(18) + Fixed::from_bits(42));
+        assert_eq!(style.measure_string_fixed("abc"), next.x);
+    }
+
+    #[test]
+    fn it_advances_tabs_to_tab_stops_and_skips_control_characters() {
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+
+        assert_eq!(width(style, "\t"), 48);
+        assert_eq!(width(style, "ab\tc"), 48 + 6);
+        assert_eq!(
+            width(style.with_tab_width(TabWidth::Spaces(2)), "abc\td"),
+            24 + 6
+        );
+        assert_eq!(width(style.with_tab_width(TabWidth::Pixels(10)), "a\t"), 10);
+        assert_eq!(width(style.with_scale(2), "a\t"), 96);
+        assert_eq!(width(style, "a\u{7}b\u{1b}"), width(style, "ab"));
+
+        let position = Point::new(0, 12);
+        let mut display = MockDisplay::new();
+        style
+            .draw_string("a\u{7}b", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        style
+            .draw_string("ab", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn it_applies_letter_and_word_spacing() {
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let spaced = style.with_letter_spacing(2).with_word_spacing(-3);
+
+        assert_eq!(width(style, "ab cd"), 30);
+        // letter spacing between the 5 characters, word spacing for the space
+        assert_eq!(width(spaced, "ab cd"), 30 + 4 * 2 - 3);
+
+        let position = Point::new(0, 12);
+        let mut display = MockDisplay::new();
+        let end = spaced
+            .draw_string("ab", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        assert_eq!(end.x, width(spaced, "ab"));
+
+        let mut expected = MockDisplay::new();
+        style
+            .draw_string("a", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        style
+            .draw_string(
+                "b",
+                position + Point::new(8, 0),
+                Baseline::Alphabetic,
+                &mut expected,
+            )
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn it_measures_ligatures() {
+        let table = LigatureTable::new(&[Ligature {
+            sequence: "AB",
+            replacement: 'W',
+        }]);
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let ligatures = style.with_ligatures(table);
+
+        assert_eq!(width(ligatures, "ABAB"), width(style, "WW"));
+        assert_eq!(width(ligatures, "BA"), width(style, "BA"));
+    }
+
+    #[test]
+    fn it_maps_char_sequences_before_ligatures() {
+        fn arrows(chars: &[char], font: &PcfFont<'_>) -> Option<(char, usize)> {
+            match chars {
+                ['-', '>', ..] if font.contains('W') => Some(('W', 2)),
+                ['-', '>', ..] => Some(('M', 2)),
+                _ => None,
+            }
+        }
+        let table = LigatureTable::new(&[Ligature {
+            sequence: "->A",
+            replacement: 'B',
+        }]);
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mapped = style.with_ligatures(table).with_char_mapper(arrows);
+
+        assert_eq!(width(mapped, "A->A"), width(style, "AWA"));
+        assert_eq!(width(mapped, "-A->"), width(style, "-AW"));
+
+        let mut display = MockDisplay::new();
+        mapped
+            .draw_string("->", Point::new(0, 15), Baseline::Alphabetic, &mut display)
+            .unwrap();
+        let mut expected = MockDisplay::new();
+        style
+            .draw_string("W", Point::new(0, 15), Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn it_maps_between_pixel_positions_and_char_indices() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let advance = |c| FONT.glyph(c).unwrap().device_width;
+        let (a, b) = (advance('A'), advance('B'));
+
+        assert_eq!(style.position_of_char("ABC", 0), 0);
+        assert_eq!(style.position_of_char("ABC", 2), a + b);
+        assert_eq!(style.position_of_char("ABC", 9), width(style, "ABC"));
+
+        assert_eq!(style.char_index_at("ABC", -5), 0);
+        assert_eq!(style.char_index_at("ABC", a / 2 - 1), 0);
+        assert_eq!(style.char_index_at("ABC", a - 1), 1);
+        assert_eq!(style.char_index_at("ABC", a + b / 2), 2);
+        assert_eq!(style.char_index_at("ABC", 1000), 3);
+        for index in 0..=3 {
+            let x = style.position_of_char("ABC", index);
+            assert_eq!(style.char_index_at("ABC", x), index);
+        }
+    }
+
+    #[test]
+    fn it_emboldens_glyphs_and_widens_advances() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let bold = style.with_weight(FontWeightEffect::Embolden(1));
+        let position = Point::new(0, 12);
+
+        assert_eq!(width(bold, "AB"), width(style, "AB") + 2);
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        style
+            .draw_string("I", position, Baseline::Alphabetic, &mut expected)
+            .unwrap();
+        style
+            .draw_string(
+                "I",
+                position + Point::new(1, 0),
+                Baseline::Alphabetic,
+                &mut expected,
+            )
+            .unwrap();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let next = bold
+            .draw_string("I", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+
+        display.assert_eq(&expected);
+        assert_eq!(next.x, width(style, "I") + 1);
+    }
+
+    #[test]
+    fn it_slants_glyphs_above_the_baseline() {
+        let font = include_pcf!("examples/6x10.pcf", 'A'..='Z');
+        let style = PcfTextStyle::new(&font, BinaryColor::On).with_oblique(3);
+        let position = Point::new(1, 9);
+
+        let mut display = MockDisplay::new();
+        style
+            .draw_string("I", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        display.assert_pattern(&[
+            "       ", "    ###", "    #  ", "    #  ", "    #  ", "   #   ", "   #   ", "  ###  ",
+        ]);
+
+        let upright = PcfTextStyle::new(&font, BinaryColor::On);
+        let metrics = style.measure_string("I", Point::zero(), Baseline::Alphabetic);
+        let expected = upright.measure_string("I", Point::zero(), Baseline::Alphabetic);
+        assert_eq!(metrics.next_position, expected.next_position);
+        assert_eq!(
+            metrics.bounding_box.size.width,
+            expected.bounding_box.size.width + 2
+        );
+    }
+
+    #[test]
+    fn it_measures_the_font_bounding_box() {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let position = Point::new(5, 20);
+
+        let metrics = style.measure_string("AB", position, Baseline::Alphabetic);
+        let expected = Rectangle::new(
+            position + Point::new(0, FONT.bounding_box.top_left.y),
+            Size::new(width(style, "AB") as u32, FONT.bounding_box.size.height),
+        );
+        assert_eq!(metrics.bounding_box, expected);
+    }
+
+    #[test]
+    fn it_handles_zero_and_negative_advances() {
+        const BASE: PcfFont = include_pcf!("examples/6x10.pcf", 'A' | '^');
+        const A: PcfGlyph = BASE.glyphs[0];
+        const CIRCUMFLEX: PcfGlyph = BASE.glyphs[1];
+        const GLYPHS: [PcfGlyph; 3] = [
+            PcfGlyph::new('<', Rectangle::new(Point::zero(), Size::zero()), -6, 0),
+            A,
+            // a combining mark drawn over the preceding glyph
+            PcfGlyph::new(
+                '\u{302}',
+                Rectangle::new(
+                    Point::new(-6, CIRCUMFLEX.bounding_box.top_left.y),
+                    CIRCUMFLEX.bounding_box.size,
+                ),
+                0,
+                CIRCUMFLEX.start_index,
+            ),
+        ];
+        let font = PcfFont::new(BASE.bounding_box, 1, BASE.line_height, &GLYPHS, BASE.data);
+        let style = PcfTextStyle::new(&font, BinaryColor::On);
+        let base_style = PcfTextStyle::new(&BASE, BinaryColor::On);
+        let position = Point::new(0, 12);
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let end = style
+            .draw_string("A\u{302}", position, Baseline::Alphabetic, &mut display)
+            .unwrap();
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        for c in ["A", "^"] {
+            base_style
+                .draw_string(c, position, Baseline::Alphabetic, &mut expected)
+                .unwrap();
+        }
+        display.assert_eq(&expected);
+        assert_eq!(end.x, 6);
+
+        let metrics = style.measure_string("A<<", position, Baseline::Alphabetic);
+        assert_eq!(metrics.next_position.x, -6);
+        assert_eq!(metrics.bounding_box.top_left.x, -6);
+        assert_eq!(metrics.bounding_box.size.width, 12);
+        let mut chars = style.measure_chars("A<");
+        assert_eq!(chars.next(), Some(('A', 6)));
+        assert_eq!(chars.next(), Some(('<', -6)));
+    }
+
+    #[test]
+    fn it_measures_monospaced_text_without_glyph_lookups() {
+        const MONO: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+        const PROPORTIONAL: PcfFont = PcfFont {
+            monospace_advance: None,
+            ..MONO
+        };
+        assert_eq!(MONO.monospace_advance, Some(6));
+
+        let styles = |font| {
+            let style = PcfTextStyle::new(font, BinaryColor::On);
+            [
+                style,
+                style.with_letter_spacing(2).with_scale(2),
+                style.with_weight(FontWeightEffect::Embolden(1)),
+                style.with_tracking(Fixed::from_ratio(-1, 3)).with_scale(3),
+            ]
+        };
+        for (mono, proportional) in styles(&MONO).into_iter().zip(styles(&PROPORTIONAL)) {
+            for text in ["", "a", "Hello, world", "a\tb"] {
+                assert_eq!(
+                    mono.measure_string(text, Point::new(3, 5), Baseline::Alphabetic),
+                    proportional.measure_string(text, Point::new(3, 5), Baseline::Alphabetic)
+                );
+                assert_eq!(
+                    mono.measure_string_fixed(text),
+                    proportional.measure_string_fixed(text)
+                );
+                for i in 0..=text.len() + 1 {
+                    assert_eq!(
+                        mono.position_of_char(text, i),
+                        proportional.position_of_char(text, i)
+                    );
+                }
+                for x in -5..80 {
+                    assert_eq!(
+                        mono.char_index_at(text, x),
+                        proportional.char_index_at(text, x)
+                    );
+                }
+            }
+        }
+
+        // the advance is taken from the font rather than the glyphs
+        let wide = PcfFont {
+            monospace_advance: Some(7),
+            ..MONO
+        };
+        let style = PcfTextStyle::new(&wide, BinaryColor::On);
+        assert_eq!(width(style, "abc"), 21);
+        assert_eq!(style.char_index_at("abc", 18), 3);
+        assert_eq!(width(style.with_kerning(KerningTable::new(&[])), "abc"), 18);
+    }
+
+    #[test]
+    fn it_reports_the_area_touched_by_drawing() {
+        const MONO: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+        let mut style = PcfTextStyle::new(&MONO, BinaryColor::On);
+        style.set_underline_color(DecorationColor::TextColor);
+
+        let mut display = MockDisplay::new();
+        let (next, area) = style
+            .draw_string_tracked("Ij", Point::new(2, 9), Baseline::Alphabetic, &mut display)
+            .unwrap();
+        assert_eq!(next, Point::new(14, 9));
+        assert_eq!(area, display.affected_area());
+
+        let mut display = MockDisplay::<BinaryColor>::new();
+        let (_, area) = PcfTextStyle::new(&MONO, BinaryColor::On)
+            .draw_string_tracked("  ", Point::new(2, 9), Baseline::Alphabetic, &mut display)
+            .unwrap();
+        assert_eq!(area.size, Size::zero());
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn it_composes_or_overstrikes_combining_marks() {
+        const FONT: PcfFont = include_pcf!("examples/6x10.pcf", 'a' | 'b' | 'e' | 'é' | '\u{301}');
+        assert_eq!(FONT.glyph_count(), 5);
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On).with_combining_marks();
+        let draw = |texts: &[(&str, i32)]| {
+            let mut display = MockDisplay::new();
+            display.set_allow_overdraw(true);
+            for (text, x) in texts {
+                PcfTextStyle::new(&FONT, BinaryColor::On)
+                    .draw_string(text, Point::new(*x, 9), Baseline::Alphabetic, &mut display)
+                    .unwrap();
+            }
+            display
+        };
+
+        let mut display = MockDisplay::new();
+        let end = style
+            .draw_string(
+                "e\u{301}",
+                Point::new(0, 9),
+                Baseline::Alphabetic,
+                &mut display,
+            )
+            .unwrap();
+        display.assert_eq(&draw(&[("é", 0)]));
+        assert_eq!(end.x, 6);
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let end = style
+            .draw_string(
+                "a\u{301}b",
+                Point::new(0, 9),
+                Baseline::Alphabetic,
+                &mut display,
+            )
+            .unwrap();
+        display.assert_eq(&draw(&[("a", 0), ("\u{301}", 0), ("b", 6)]));
+        assert_eq!(end.x, 12);
+        assert_eq!(width(style, "a\u{301}b"), 12);
+        assert_eq!(width(style.with_letter_spacing(1), "a\u{301}b"), 13);
     }
 }
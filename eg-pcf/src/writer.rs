@@ -0,0 +1,213 @@
+use core::fmt;
+
+use embedded_graphics::{
+    prelude::*,
+    text::{renderer::TextRenderer, Baseline},
+};
+
+use crate::{
+    shaping::{PassThrough, Shaper},
+    text::PcfTextStyle,
+};
+
+/// Draws formatted text into a [`DrawTarget`] at a cursor.
+///
+/// The writer implements [`fmt::Write`], so `write!(writer, "T = {:.1}°C", t)` draws text
+/// without formatting it into a buffer first. `\n` moves the cursor to the start of the
+/// next line. Unlike [`TextConsole`](crate::console::TextConsole) it borrows the target and
+/// doesn't retain text, so it neither clears nor scrolls.
+///
+/// Each written string is drawn a line at a time, so kerning, ligatures and the other
+/// features of the style apply between its characters, but not between separate writes,
+/// like the pieces of a `write!` format string.
+#[derive(Debug)]
+pub struct TextWriter<'a, D, C, S = PassThrough> {
+    target: &'a mut D,
+    style: PcfTextStyle<'a, C, S>,
+    /// The drawing position of the first line.
+    start: Point,
+    position: Point,
+    width: Option<u32>,
+}
+
+impl<'a, D, C, S> TextWriter<'a, D, C, S>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+    S: Shaper,
+{
+    /// Creates a writer that starts drawing at `position`, like
+    /// [`draw_string`](TextRenderer::draw_string), without wrapping lines.
+    pub fn new(target: &'a mut D, style: PcfTextStyle<'a, C, S>, position: Point) -> Self {
+        Self {
+            target,
+            style,
+            start: position,
+            position,
+            width: None,
+        }
+    }
+
+    /// Wraps text that would extend more than `width` pixels past the start position to
+    /// the next line. Characters are wrapped individually, so words may be split.
+    pub fn with_width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Returns the position the next character is drawn at.
+    pub fn position(&self) -> Point {
+        self.position
+    }
+
+    /// Moves the cursor to the start of the next line.
+    pub fn new_line(&mut self) {
+        self.position = Point::new(
+            self.start.x,
+            self.position.y + self.style.line_height() as i32,
+        );
+    }
+
+    fn write_text(&mut self, text: &str) -> Result<(), D::Error> {
+        // the start of the text that is drawn at the cursor as a whole, and its width
+        let mut start = 0;
+        let mut run_width = 0;
+        let mut previous = None;
+
+        for (index, c) in text.char_indices() {
+            if c == '\n' {
+                self.draw(&text[start..index])?;
+                self.new_line();
+                start = index + 1;
+                run_width = 0;
+                previous = None;
+                continue;
+            }
+
+            if let Some(width) = self.width {
+                // measuring the character after the previous one includes their kerning
+                let end = index + c.len_utf8();
+                let pair = previous.unwrap_or(index);
+                let added = self.measure(&text[pair..end]) - self.measure(&text[pair..index]);
+
+                let before = self.position.x - self.start.x + run_width;
+                if before > 0 && before + added > width as i32 {
+                    self.draw(&text[start..index])?;
+                    self.new_line();
+                    start = index;
+                    run_width = self.measure(&text[index..end]);
+                } else {
+                    run_width += added;
+                }
+                previous = Some(index);
+            }
+        }
+
+        self.draw(&text[start..])
+    }
+
+    fn measure(&self, text: &str) -> i32 {
+        self.style
+            .measure_string(text, Point::zero(), Baseline::Alphabetic)
+            .next_position
+            .x
+    }
+
+    fn draw(&mut self, text: &str) -> Result<(), D::Error> {
+        if !text.is_empty() {
+            self.position =
+                self.style
+                    .draw_string(text, self.position, Baseline::Alphabetic, self.target)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<D, C, S> fmt::Write for TextWriter<'_, D, C, S>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+    S: Shaper,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_text(s).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        include_pcf,
+        kerning::{KerningPair, KerningTable},
+        PcfFont,
+    };
+    use core::fmt::Write;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    const FONT: PcfFont = include_pcf!("examples/6x10.pcf", ' '..='~');
+
+    fn draw_lines(lines: &[&str]) -> MockDisplay<BinaryColor> {
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut display = MockDisplay::new();
+        for (row, line) in lines.iter().enumerate() {
+            let position = Point::new(1, 9 + 10 * row as i32);
+            style
+                .draw_string(line, position, Baseline::Alphabetic, &mut display)
+                .unwrap();
+        }
+
+        display
+    }
+
+    #[test]
+    fn it_draws_formatted_text_at_the_cursor() {
+        let mut display = MockDisplay::new();
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut writer = TextWriter::new(&mut display, style, Point::new(1, 9));
+
+        write!(writer, "T = {:.1}", 21.46).unwrap();
+        writer.write_str("\nC").unwrap();
+
+        assert_eq!(writer.position(), Point::new(7, 19));
+        display.assert_eq(&draw_lines(&["T = 21.5", "C"]));
+    }
+
+    #[test]
+    fn it_wraps_at_the_width() {
+        let mut display = MockDisplay::new();
+        let style = PcfTextStyle::new(&FONT, BinaryColor::On);
+        let mut writer = TextWriter::new(&mut display, style, Point::new(1, 9)).with_width(20);
+
+        write!(writer, "abcd\ne").unwrap();
+
+        display.assert_eq(&draw_lines(&["abc", "d", "e"]));
+    }
+
+    #[test]
+    fn it_kerns_the_characters_of_a_write() {
+        const PAIRS: &[KerningPair] = &[KerningPair {
+            left: 'A',
+            right: 'V',
+            adjustment: -2,
+        }];
+        let style =
+            PcfTextStyle::new(&FONT, BinaryColor::On).with_kerning(KerningTable::new(PAIRS));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let mut writer = TextWriter::new(&mut display, style, Point::new(1, 9)).with_width(20);
+
+        writer.write_str("AVAVA").unwrap();
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        for (row, line) in ["AVAV", "A"].into_iter().enumerate() {
+            let position = Point::new(1, 9 + 10 * row as i32);
+            style
+                .draw_string(line, position, Baseline::Alphabetic, &mut expected)
+                .unwrap();
+        }
+        display.assert_eq(&expected);
+    }
+}
@@ -0,0 +1,17 @@
+//! Parses arbitrary bytes as PCF and gzip compressed PCF fonts, which must never panic.
+//!
+//! Run with `cargo +nightly fuzz run parse` in `pcf-parser`, e.g. with the fonts in
+//! `assets` as the initial corpus.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pcf_parser::PcfFont;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(font) = PcfFont::parse(data) {
+        let _ = font.to_bdf();
+    }
+    if let Ok(bytes) = pcf_parser::gunzip(data) {
+        let _ = PcfFont::parse(&bytes);
+    }
+});
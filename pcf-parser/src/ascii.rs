@@ -0,0 +1,80 @@
+use std::fmt;
+
+use crate::Glyph;
+
+/// Prints the bitmap as rows of `#` for set and `·` for clear pixels.
+///
+/// A row of `-` marks the baseline below the pixel row that sits on it, and the last line
+/// marks the origin and the advance with `^`. Space around the bounding box that is needed
+/// to show the markers is left blank.
+impl fmt::Display for Glyph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bounding_box = &self.bounding_box;
+        let (width, height) = (bounding_box.size.x, bounding_box.size.y);
+        let left = bounding_box.offset.x.min(0);
+        let right = (bounding_box.offset.x + width).max(self.shift_x + 1);
+        let top = (bounding_box.offset.y + height - 1).max(0);
+        let bottom = bounding_box.offset.y.min(0);
+
+        // `h` is the height of a row above the baseline
+        for h in (bottom..=top).rev() {
+            let y = bounding_box.offset.y + height - 1 - h;
+            let row: String = (left..right)
+                .map(|x| {
+                    let x = x - bounding_box.offset.x;
+                    match (0..width).contains(&x) && (0..height).contains(&y) {
+                        true if self.pixel(x as usize, y as usize) => '#',
+                        true => '·',
+                        false => ' ',
+                    }
+                })
+                .collect();
+            writeln!(f, "{}", row.trim_end())?;
+
+            if h == 0 {
+                writeln!(f, "{}", "-".repeat((right - left) as usize))?;
+            }
+        }
+
+        let markers: String = (left..right)
+            .map(|x| {
+                if x == 0 || x == self.shift_x {
+                    '^'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        write!(f, "{}", markers.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Attributes, BoundingBox, Coord, Glyph};
+
+    #[test]
+    fn it_marks_the_baseline_and_bearings() {
+        let glyph = Glyph {
+            code_point: 0,
+            encoding: None,
+            bitmap: vec![0b0100_0000, 0b1000_0000, 0b1100_0000],
+            bounding_box: BoundingBox {
+                size: Coord::new(2, 3),
+                offset: Coord::new(1, -1),
+            },
+            ink_bounds: None,
+            shift_x: 4,
+            shift_y: 0,
+            tile_index: 0,
+            attributes: Attributes::default(),
+            name: None,
+            scalable_width: None,
+        };
+
+        assert_eq!(
+            glyph.to_string(),
+            [" ·#", " #·", "-----", " ##", "^   ^"].join("\n")
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! Packing the glyphs of a font into a single bitmap, the form game engines and GPU
+//! renderers expect bitmap fonts in.
+
+use crate::{Coord, Font};
+
+/// A glyph of an [`Atlas`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtlasGlyph {
+    pub code_point: i32,
+    pub encoding: Option<char>,
+    /// The left edge of the glyph in the atlas.
+    pub x: u32,
+    /// The top edge of the glyph in the atlas.
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// The offset of the bottom left corner of the glyph from its origin, with `y` pointing
+    /// up, like [`BoundingBox::offset`](crate::BoundingBox::offset).
+    pub offset: Coord,
+    pub advance: i32,
+}
+
+/// The glyphs of a font packed into one bitmap, see [`Atlas::new`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Atlas {
+    pub width: u32,
+    pub height: u32,
+    /// One byte per pixel, row by row: 255 for set and 0 for clear pixels. This can be
+    /// uploaded as an alpha or single channel texture as it is.
+    pub pixels: Vec<u8>,
+    /// The glyphs in ascending order of code points.
+    pub glyphs: Vec<AtlasGlyph>,
+}
+
+impl Atlas {
+    /// Packs the glyphs of `font` into rows of an atlas `width` pixels wide, with `padding`
+    /// clear pixels around each glyph so texture filtering doesn't bleed between glyphs.
+    ///
+    /// Glyphs are placed from tallest to shortest, which keeps the rows dense. The atlas is
+    /// wider than `width` if a glyph doesn't fit into it.
+    pub fn new<F: Font + ?Sized>(font: &F, width: u32, padding: u32) -> Atlas {
+        let mut glyphs: Vec<_> = font.glyphs().values().collect();
+        glyphs.sort_by_key(|glyph| (-glyph.bounding_box.size.y, glyph.code_point));
+
+        let size = |n: i32| n.max(0) as u32;
+        let width = glyphs
+            .iter()
+            .map(|glyph| size(glyph.bounding_box.size.x) + 2 * padding)
+            .fold(width, u32::max);
+
+        // shelf packing, every row is as tall as its first and tallest glyph
+        let (mut x, mut y, mut row_height) = (0, 0, 0);
+        let mut entries = Vec::with_capacity(glyphs.len());
+        for glyph in &glyphs {
+            let (w, h) = (
+                size(glyph.bounding_box.size.x),
+                size(glyph.bounding_box.size.y),
+            );
+            if x + w + 2 * padding > width {
+                (x, y, row_height) = (0, y + row_height, 0);
+            }
+            row_height = row_height.max(h + 2 * padding);
+
+            entries.push(AtlasGlyph {
+                code_point: glyph.code_point,
+                encoding: glyph.encoding,
+                x: x + padding,
+                y: y + padding,
+                width: w,
+                height: h,
+                offset: glyph.bounding_box.offset,
+                advance: glyph.shift_x,
+            });
+            x += w + 2 * padding;
+        }
+        let height = y + row_height;
+
+        let mut pixels = vec![0; width as usize * height as usize];
+        for (glyph, entry) in glyphs.iter().zip(&entries) {
+            for row in 0..entry.height as usize {
+                for column in 0..entry.width as usize {
+                    if glyph.pixel(column, row) {
+                        let x = entry.x as usize + column;
+                        let y = entry.y as usize + row;
+                        pixels[y * width as usize + x] = 255;
+                    }
+                }
+            }
+        }
+
+        entries.sort_by_key(|entry| entry.code_point);
+
+        Atlas {
+            width,
+            height,
+            pixels,
+            glyphs: entries,
+        }
+    }
+
+    /// Returns the glyph of `c`, if the font has one.
+    pub fn glyph(&self, c: char) -> Option<&AtlasGlyph> {
+        let index = self
+            .glyphs
+            .binary_search_by_key(&(c as i32), |glyph| glyph.code_point)
+            .ok()?;
+
+        self.glyphs
+            .get(index)
+            .filter(|glyph| glyph.encoding == Some(c))
+    }
+
+    /// Encodes the atlas as an 8-bit grayscale PNG image.
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut image = Vec::new();
+        let mut encoder = png::Encoder::new(&mut image, self.width, self.height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.pixels)?;
+
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PcfFont;
+
+    #[test]
+    fn it_packs_all_glyphs_without_overlap() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let font = PcfFont::new(&font[..]);
+        let atlas = Atlas::new(&font, 64, 1);
+
+        assert_eq!(atlas.width, 64);
+        assert_eq!(atlas.glyphs.len(), font.glyphs.len());
+        assert_eq!(atlas.pixels.len(), (atlas.width * atlas.height) as usize);
+
+        let mut covered = vec![false; atlas.pixels.len()];
+        for entry in &atlas.glyphs {
+            let glyph = &font.glyphs[&entry.code_point];
+            assert_eq!(entry.offset, glyph.bounding_box.offset);
+            assert_eq!(entry.advance, glyph.shift_x);
+
+            // the glyph and its padding lie inside the atlas and cover no other glyph
+            for y in entry.y - 1..entry.y + entry.height + 1 {
+                for x in entry.x - 1..entry.x + entry.width + 1 {
+                    let index = (y * atlas.width + x) as usize;
+                    assert!(!covered[index]);
+                    covered[index] = true;
+                }
+            }
+            for y in 0..entry.height {
+                for x in 0..entry.width {
+                    let pixel = atlas.pixels[((entry.y + y) * atlas.width + entry.x + x) as usize];
+                    assert_eq!(pixel == 255, glyph.pixel(x as usize, y as usize));
+                }
+            }
+        }
+
+        let a = atlas.glyph('A').unwrap();
+        assert_eq!(a.encoding, Some('A'));
+        assert_eq!(atlas.glyph('\u{2603}'), None);
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn it_encodes_the_atlas_as_png() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let atlas = Atlas::new(&PcfFont::new(&font[..]), 64, 0);
+
+        let image = atlas.to_png().unwrap();
+        let (info, mut reader) = png::Decoder::new(&image[..]).read_info().unwrap();
+        let mut pixels = vec![0; info.buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((info.width, info.height), (atlas.width, atlas.height));
+        assert_eq!(pixels, atlas.pixels);
+    }
+}
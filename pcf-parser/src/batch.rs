@@ -0,0 +1,167 @@
+//! Conversion of whole directories of fonts, e.g. in asset build scripts.
+//!
+//! Every `.pcf` file is parsed, optionally subset and transformed, and then passed to an
+//! emitter, which writes the converted font in whatever form the application needs. With
+//! the `rayon` feature, files are converted in parallel.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{Glyph, PcfFont};
+
+type Subset = Box<dyn Fn(&Glyph) -> bool + Send + Sync>;
+type Transform = Box<dyn Fn(Glyph) -> Glyph + Send + Sync>;
+type Emit = Box<dyn Fn(&Path, &PcfFont) -> io::Result<()> + Send + Sync>;
+
+/// The steps a font goes through after it is parsed.
+#[derive(Default)]
+pub struct Pipeline {
+    subset: Option<Subset>,
+    transforms: Vec<Transform>,
+    emit: Option<Emit>,
+}
+
+/// The result of converting a single file.
+#[derive(Debug)]
+pub struct Report {
+    pub path: PathBuf,
+    pub result: io::Result<Summary>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Summary {
+    /// The number of glyphs in the font.
+    pub glyphs: usize,
+    /// The number of glyphs kept by the subset.
+    pub kept: usize,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps only the glyphs `keep` returns `true` for.
+    pub fn subset(mut self, keep: impl Fn(&Glyph) -> bool + Send + Sync + 'static) -> Self {
+        self.subset = Some(Box::new(keep));
+        self
+    }
+
+    /// Adds a transformation that is applied to every kept glyph, in the order they were added.
+    pub fn transform(mut self, f: impl Fn(Glyph) -> Glyph + Send + Sync + 'static) -> Self {
+        self.transforms.push(Box::new(f));
+        self
+    }
+
+    /// Sets the function that writes the converted font, which gets the path of the source
+    /// file. Without an emitter, fonts are only parsed and reported.
+    pub fn emit(
+        mut self,
+        f: impl Fn(&Path, &PcfFont) -> io::Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.emit = Some(Box::new(f));
+        self
+    }
+
+    /// Converts a single font file.
+    ///
+    /// Fonts the parser can't handle are reported as [`io::ErrorKind::InvalidData`].
+    pub fn convert_file(&self, path: &Path) -> io::Result<Summary> {
+        let bytes = fs::read(path)?;
+        let mut font = PcfFont::parse(&bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        let glyphs = font.glyphs.len();
+        if let Some(keep) = &self.subset {
+            font.glyphs.retain(|_, glyph| keep(glyph));
+        }
+        let kept = font.glyphs.len();
+
+        font.glyphs = font
+            .glyphs
+            .into_iter()
+            .map(|(code_point, glyph)| {
+                let glyph = self.transforms.iter().fold(glyph, |glyph, f| f(glyph));
+                (code_point, glyph)
+            })
+            .collect();
+        font.recompute_metrics();
+
+        if let Some(emit) = &self.emit {
+            emit(path, &font)?;
+        }
+
+        Ok(Summary { glyphs, kept })
+    }
+
+    /// Converts every `.pcf` file in `dir` and returns a report per file, ordered by path.
+    pub fn convert_dir(&self, dir: impl AsRef<Path>) -> io::Result<Vec<Report>> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "pcf") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        #[cfg(feature = "rayon")]
+        let paths = paths.into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let paths = paths.into_iter();
+
+        Ok(paths
+            .map(|path| Report {
+                result: self.convert_file(&path),
+                path,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn it_converts_a_directory() {
+        static EMITTED: AtomicUsize = AtomicUsize::new(0);
+
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        let reports = Pipeline::new()
+            .subset(|glyph| glyph.encoding.is_some_and(|c| c.is_ascii_digit()))
+            .transform(|glyph| glyph.scaled(2, 2, crate::Filter::Nearest))
+            .emit(|_, font| {
+                assert!(font.glyphs.values().all(|g| g.bounding_box.size.x % 2 == 0));
+                EMITTED.fetch_add(font.glyphs.len(), Ordering::Relaxed);
+                Ok(())
+            })
+            .convert_dir(dir)
+            .unwrap();
+
+        assert!(!reports.is_empty());
+        for report in &reports {
+            let summary = report.result.as_ref().unwrap();
+            assert_eq!(summary.kept, 10);
+            assert!(summary.glyphs > summary.kept);
+        }
+        assert_eq!(EMITTED.load(Ordering::Relaxed), 10 * reports.len());
+    }
+
+    #[test]
+    fn it_reports_malformed_fonts() {
+        let path = std::env::temp_dir().join("pcf-parser-batch-malformed.pcf");
+        fs::write(&path, b"not a font").unwrap();
+
+        let error = Pipeline::new().convert_file(&path).unwrap_err();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
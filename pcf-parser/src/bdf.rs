@@ -0,0 +1,410 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use crate::{Attributes, BoundingBox, Coord, Font, Glyph, PcfFont, Property};
+
+/// A font in the text based Glyph Bitmap Distribution Format.
+///
+/// Glyphs are parsed into the same structures as [`PcfFont`](crate::PcfFont) uses, so both
+/// can be used through the [`Font`] trait.
+#[derive(Debug, Default)]
+pub struct BdfFont {
+    pub glyphs: HashMap<i32, Glyph>,
+    pub bounding_box: BoundingBox,
+    pub properties: HashMap<String, Property>,
+}
+
+impl BdfFont {
+    /// Parses a BDF font. Glyphs without a code point (`ENCODING -1`) are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the font is malformed, see [`parse`](Self::parse).
+    pub fn new(source: &[u8]) -> BdfFont {
+        Self::parse(source).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Parses a BDF font, returning an error with the line of the first value that can't be
+    /// parsed, e.g. a bad number, a short `BBX` or a `BITMAP` row that isn't hex.
+    ///
+    /// Unknown keywords are ignored, like by [`new`](Self::new).
+    pub fn parse(source: &[u8]) -> Result<BdfFont, BdfError> {
+        let source = String::from_utf8_lossy(source);
+        let mut lines = source
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+        let mut font = BdfFont::default();
+
+        while let Some((number, line)) = lines.next() {
+            let (keyword, args) = line.split_once(' ').unwrap_or((line, ""));
+
+            match keyword {
+                "FONTBOUNDINGBOX" => font.bounding_box = parse_bounding_box(number, args)?,
+                "STARTPROPERTIES" => {
+                    for (_, line) in lines
+                        .by_ref()
+                        .take_while(|(_, line)| *line != "ENDPROPERTIES")
+                    {
+                        let (name, value) = line.split_once(' ').unwrap_or((line, ""));
+                        font.properties
+                            .insert(name.to_string(), parse_property(value.trim()));
+                    }
+                }
+                "STARTCHAR" => {
+                    let glyph = parse_glyph(number, args, &mut lines)?;
+                    if glyph.code_point >= 0 {
+                        font.glyphs.insert(glyph.code_point, glyph);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(font)
+    }
+}
+
+/// An error that occurred while parsing a BDF font.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BdfError {
+    /// The line the error occurred on, starting at 1.
+    pub line: usize,
+    /// What couldn't be parsed, e.g. `invalid number`.
+    pub reason: &'static str,
+}
+
+impl BdfError {
+    fn new(line: usize, reason: &'static str) -> Self {
+        Self { line, reason }
+    }
+}
+
+impl fmt::Display for BdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for BdfError {}
+
+impl Font for BdfFont {
+    fn glyphs(&self) -> &HashMap<i32, Glyph> {
+        &self.glyphs
+    }
+
+    fn bounding_box(&self) -> &BoundingBox {
+        &self.bounding_box
+    }
+
+    fn properties(&self) -> &HashMap<String, Property> {
+        &self.properties
+    }
+}
+
+impl PcfFont<'_> {
+    /// Writes the glyphs, metrics and properties of the font as BDF.
+    ///
+    /// Only the glyphs in [`glyphs`](Self::glyphs) are written, so removing glyphs first
+    /// exports a subset of the font, after [`recompute_metrics`](Self::recompute_metrics)
+    /// shrinks the font bounding box to the remaining glyphs. Glyphs without a name are named after their code point,
+    /// and glyphs without a scalable width get one computed from their advance width.
+    pub fn to_bdf(&self) -> String {
+        let mut bdf = String::new();
+        // writing to a String can't fail
+        write_font(&mut bdf, self).unwrap();
+
+        bdf
+    }
+}
+
+fn write_font(bdf: &mut String, font: &impl Font) -> std::fmt::Result {
+    let (resolution_x, resolution_y) = font.resolution().unwrap_or((72, 72));
+    let point_size = font
+        .point_size()
+        .map_or(font.bounding_box().size.y, |size| (size / 10) as i32);
+
+    writeln!(bdf, "STARTFONT 2.1")?;
+    writeln!(
+        bdf,
+        "FONT {}",
+        font.string_property("FONT").unwrap_or("unnamed")
+    )?;
+    writeln!(bdf, "SIZE {point_size} {resolution_x} {resolution_y}")?;
+    writeln!(
+        bdf,
+        "FONTBOUNDINGBOX {}",
+        format_bounding_box(font.bounding_box())
+    )?;
+
+    let mut properties: Vec<_> = font.properties().iter().collect();
+    properties.sort_by_key(|(name, _)| *name);
+    writeln!(bdf, "STARTPROPERTIES {}", properties.len())?;
+    for (name, value) in properties {
+        match value {
+            Property::Integer(value) => writeln!(bdf, "{name} {value}")?,
+            Property::String(value) => writeln!(bdf, "{name} \"{}\"", value.replace('"', "\"\""))?,
+        }
+    }
+    writeln!(bdf, "ENDPROPERTIES")?;
+
+    let mut glyphs: Vec<_> = font.glyphs().values().collect();
+    glyphs.sort_by_key(|glyph| glyph.code_point);
+    writeln!(bdf, "CHARS {}", glyphs.len())?;
+    for glyph in glyphs {
+        write_glyph(bdf, font, glyph)?;
+    }
+
+    writeln!(bdf, "ENDFONT")
+}
+
+fn write_glyph(bdf: &mut String, font: &impl Font, glyph: &Glyph) -> std::fmt::Result {
+    let scalable_width = glyph.scalable_width.unwrap_or_else(|| {
+        // the inverse of Font::scalable_advance
+        let points = font.point_size().unwrap_or_default() as f32 / 10.0;
+        let (resolution, _) = font.resolution().unwrap_or_default();
+        let pixels_per_em = points / 72.0 * resolution as f32;
+
+        match pixels_per_em > 0.0 {
+            true => (glyph.shift_x as f32 * 1000.0 / pixels_per_em).round() as i32,
+            false => 0,
+        }
+    });
+
+    match &glyph.name {
+        Some(name) => writeln!(bdf, "STARTCHAR {name}")?,
+        None => writeln!(bdf, "STARTCHAR char{}", glyph.code_point)?,
+    }
+    writeln!(bdf, "ENCODING {}", glyph.code_point)?;
+    writeln!(bdf, "SWIDTH {scalable_width} 0")?;
+    writeln!(bdf, "DWIDTH {} {}", glyph.shift_x, glyph.shift_y)?;
+    writeln!(bdf, "BBX {}", format_bounding_box(&glyph.bounding_box))?;
+    if glyph.attributes.bits() != 0 {
+        writeln!(bdf, "ATTRIBUTES {:04X}", glyph.attributes.bits())?;
+    }
+
+    writeln!(bdf, "BITMAP")?;
+    let stride = glyph.stride();
+    for y in 0..glyph.bounding_box.size.y.max(0) as usize {
+        for byte in &glyph.bitmap[y * stride..][..stride] {
+            write!(bdf, "{byte:02X}")?;
+        }
+        writeln!(bdf)?;
+    }
+
+    writeln!(bdf, "ENDCHAR")
+}
+
+fn format_bounding_box(b: &BoundingBox) -> String {
+    format!("{} {} {} {}", b.size.x, b.size.y, b.offset.x, b.offset.y)
+}
+
+fn parse_glyph<'s>(
+    start: usize,
+    name: &str,
+    lines: &mut impl Iterator<Item = (usize, &'s str)>,
+) -> Result<Glyph, BdfError> {
+    let mut glyph = Glyph {
+        code_point: -1,
+        encoding: None,
+        bitmap: Vec::new(),
+        bounding_box: BoundingBox::default(),
+        ink_bounds: None,
+        shift_x: 0,
+        shift_y: 0,
+        tile_index: 0,
+        attributes: Attributes::default(),
+        name: (!name.is_empty()).then(|| name.to_string()),
+        scalable_width: None,
+    };
+
+    while let Some((number, line)) = lines.next() {
+        let (keyword, args) = line.split_once(' ').unwrap_or((line, ""));
+        let mut values = numbers(number, args);
+
+        match keyword {
+            "ENCODING" => {
+                let code_point = next_number(number, &mut values, "missing ENCODING")?;
+                glyph.code_point = code_point;
+                glyph.encoding = u32::try_from(code_point).ok().and_then(char::from_u32);
+            }
+            "DWIDTH" => {
+                glyph.shift_x = next_number(number, &mut values, "missing DWIDTH x")?;
+                glyph.shift_y = next_number(number, &mut values, "missing DWIDTH y")?;
+            }
+            "SWIDTH" => glyph.scalable_width = values.next().transpose()?,
+            "BBX" => {
+                glyph.bounding_box = parse_bounding_box(number, args)?;
+                let size = glyph.bounding_box.size;
+                if size.x < 0 || size.y < 0 {
+                    return Err(BdfError::new(number, "negative BBX size"));
+                }
+            }
+            "ATTRIBUTES" => {
+                let attributes = u16::from_str_radix(args.trim(), 16)
+                    .map_err(|_| BdfError::new(number, "invalid ATTRIBUTES"))?;
+                glyph.attributes = Attributes(attributes);
+            }
+            "BITMAP" => {
+                let height = glyph.bounding_box.size.y as usize;
+                let stride = glyph.stride();
+                // clears the bits past the last pixel of a row
+                let mask = match glyph.bounding_box.size.x % 8 {
+                    0 => 0xFF,
+                    bits => 0xFF << (8 - bits),
+                };
+
+                for (number, row) in lines.by_ref().take(height) {
+                    let bytes =
+                        parse_hex(row).ok_or(BdfError::new(number, "invalid BITMAP row"))?;
+
+                    glyph.bitmap.extend((0..stride).map(|i| {
+                        let byte = bytes.get(i).copied().unwrap_or(0);
+                        if i + 1 == stride {
+                            byte & mask
+                        } else {
+                            byte
+                        }
+                    }));
+                }
+            }
+            "ENDCHAR" => break,
+            _ => {}
+        }
+    }
+
+    // a BBX after the BITMAP or a BITMAP cut short by the end of the file
+    if glyph.bitmap.len() != glyph.stride() * glyph.bounding_box.size.y as usize {
+        return Err(BdfError::new(start, "BITMAP doesn't match BBX"));
+    }
+
+    Ok(glyph)
+}
+
+fn parse_bounding_box(line: usize, args: &str) -> Result<BoundingBox, BdfError> {
+    let mut values = numbers(line, args);
+    let mut next = || next_number(line, &mut values, "incomplete bounding box");
+    let size = Coord::new(next()?, next()?);
+    let offset = Coord::new(next()?, next()?);
+
+    Ok(BoundingBox { size, offset })
+}
+
+fn parse_hex(row: &str) -> Option<Vec<u8>> {
+    if !row.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    row.as_bytes()
+        .chunks(2)
+        .map(|digits| u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok())
+        .collect()
+}
+
+fn parse_property(value: &str) -> Property {
+    match value.strip_prefix('"') {
+        Some(value) => {
+            let value = value.strip_suffix('"').unwrap_or(value);
+            Property::String(value.replace("\"\"", "\""))
+        }
+        None => value
+            .parse()
+            .map_or_else(|_| Property::String(value.to_string()), Property::Integer),
+    }
+}
+
+fn numbers(line: usize, args: &str) -> impl Iterator<Item = Result<i32, BdfError>> + '_ {
+    args.split_whitespace().map(move |value| {
+        value
+            .parse()
+            .map_err(|_| BdfError::new(line, "invalid number"))
+    })
+}
+
+fn next_number(
+    line: usize,
+    values: &mut impl Iterator<Item = Result<i32, BdfError>>,
+    missing: &'static str,
+) -> Result<i32, BdfError> {
+    values.next().unwrap_or(Err(BdfError::new(line, missing)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PcfError, PcfFont};
+
+    #[test]
+    fn it_parses_the_same_glyphs_as_pcf() {
+        let bytes = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let mut pcf = PcfFont::new(&bytes[..]);
+        let code_points = ['A', 'g', 'W'].map(|c| c as i32);
+        pcf.glyphs
+            .retain(|code_point, _| code_points.contains(code_point));
+        pcf.properties.insert(
+            "FAMILY_NAME".to_string(),
+            Property::String("Open \"Sans\"".to_string()),
+        );
+
+        let bdf = BdfFont::new(pcf.to_bdf().as_bytes());
+
+        assert_eq!(bdf.glyphs.len(), code_points.len());
+        for code_point in code_points {
+            assert_eq!(bdf.glyphs[&code_point], pcf.glyphs[&code_point]);
+        }
+        assert_eq!(bdf.bounding_box, pcf.bounding_box);
+        assert_eq!(bdf.string_property("FAMILY_NAME"), Some("Open \"Sans\""));
+        assert_eq!(bdf.point_size(), pcf.point_size());
+    }
+
+    #[test]
+    fn it_exports_all_glyphs_and_properties() {
+        let bytes = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(&bytes[..]);
+
+        let bdf = BdfFont::new(pcf.to_bdf().as_bytes());
+
+        assert_eq!(bdf.glyphs, pcf.glyphs);
+        assert_eq!(bdf.properties, pcf.properties);
+        assert!(pcf.to_bdf().starts_with("STARTFONT 2.1\nFONT "));
+    }
+
+    #[test]
+    fn it_returns_errors_with_the_line() {
+        let glyph = |lines: &str| format!("STARTFONT 2.1\nSTARTCHAR A\n{lines}\nENDCHAR\n");
+        let error = |lines: &str| BdfFont::parse(glyph(lines).as_bytes()).unwrap_err();
+
+        assert_eq!(error("ENCODING x"), BdfError::new(3, "invalid number"));
+        assert_eq!(error("ENCODING"), BdfError::new(3, "missing ENCODING"));
+        assert_eq!(error("DWIDTH 6"), BdfError::new(3, "missing DWIDTH y"));
+        assert_eq!(
+            error("\nBBX 6 10 0"),
+            BdfError::new(4, "incomplete bounding box")
+        );
+        assert_eq!(error("BBX -6 1 0 0"), BdfError::new(3, "negative BBX size"));
+        assert_eq!(
+            error("BBX 6 2 0 0\nBITMAP\nF0\nGG"),
+            BdfError::new(6, "invalid BITMAP row")
+        );
+        assert_eq!(
+            error("BBX 6 2 0 0\nBITMAP\nF0\nÄ"),
+            BdfError::new(6, "invalid BITMAP row")
+        );
+        assert_eq!(
+            error("BBX 6 2 0 0"),
+            BdfError::new(2, "BITMAP doesn't match BBX")
+        );
+        assert_eq!(
+            BdfError::new(6, "invalid BITMAP row").to_string(),
+            "line 6: invalid BITMAP row"
+        );
+        assert_eq!(
+            PcfError::from(error("ENCODING x")).to_string(),
+            "invalid BDF font: line 3: invalid number"
+        );
+        assert!(BdfFont::parse(glyph("ENCODING 65\nBBX 6 1 0 0\nBITMAP\nF0").as_bytes()).is_ok());
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use crate::{Attributes, BoundingBox, Coord, Glyph, PcfFont, Property};
+
+/// Builds a font in memory from glyph bitmaps, e.g. hand drawn icons.
+///
+/// The built font can be written with [`PcfWriter`](crate::PcfWriter) or
+/// [`to_bdf`](PcfFont::to_bdf), or used like a parsed font anywhere a [`Font`](crate::Font)
+/// is taken, e.g. by `pcf2rust` to generate an `eg_pcf::PcfFont`.
+///
+/// ```
+/// # use pcf_parser::{FontBuilder, PcfWriter, Property};
+/// let font = FontBuilder::new()
+///     .descent(1)
+///     .property("FAMILY_NAME", Property::String("Icons".into()))
+///     .glyph('\u{E000}', &[".##.", "#..#", "#..#", ".##.", "...."])
+///     .build();
+///
+/// let pcf = PcfWriter::new(&font).to_bytes();
+/// ```
+#[derive(Debug, Default)]
+pub struct FontBuilder {
+    glyphs: HashMap<i32, Glyph>,
+    properties: HashMap<String, Property>,
+    descent: i32,
+    default_char: Option<char>,
+}
+
+impl FontBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of rows of the glyphs added with [`glyph`](Self::glyph) that are
+    /// below the baseline. Defaults to 0.
+    pub fn descent(mut self, descent: i32) -> Self {
+        self.descent = descent;
+        self
+    }
+
+    /// Sets the property `name`, replacing its previous value.
+    pub fn property(mut self, name: impl Into<String>, value: Property) -> Self {
+        self.properties.insert(name.into(), value);
+        self
+    }
+
+    /// Sets the character drawn in place of characters the font has no glyph for.
+    pub fn default_char(mut self, c: char) -> Self {
+        self.default_char = Some(c);
+        self
+    }
+
+    /// Adds a glyph for `c` drawn as rows of text, with `#` for set pixels and any other
+    /// character for clear ones, e.g. `.`.
+    ///
+    /// The glyph is as wide as its longest row, advances by its width and has its last
+    /// [`descent`](Self::descent) rows below the baseline. A previous glyph for `c` is
+    /// replaced.
+    pub fn glyph(self, c: char, rows: &[&str]) -> Self {
+        let width = rows.iter().map(|row| row.chars().count()).max();
+        let mut glyph = Glyph {
+            code_point: c as i32,
+            encoding: Some(c),
+            bitmap: Vec::new(),
+            bounding_box: BoundingBox {
+                size: Coord::new(width.unwrap_or(0) as i32, rows.len() as i32),
+                offset: Coord::new(0, -self.descent),
+            },
+            ink_bounds: None,
+            shift_x: width.unwrap_or(0) as i32,
+            shift_y: 0,
+            tile_index: 0,
+            attributes: Attributes::default(),
+            name: None,
+            scalable_width: None,
+        };
+        glyph.bitmap = glyph.empty_bitmap();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, pixel) in row.chars().enumerate() {
+                glyph.set_pixel(x, y, pixel == '#');
+            }
+        }
+
+        self.insert(glyph)
+    }
+
+    /// Adds a glyph with its own metrics at its [`code_point`](Glyph::code_point),
+    /// replacing a previous glyph with the same code point.
+    ///
+    /// The bitmap must have [`stride`](Glyph::stride) bytes per row, like parsed glyphs.
+    pub fn insert(mut self, glyph: Glyph) -> Self {
+        self.glyphs.insert(glyph.code_point, glyph);
+        self
+    }
+
+    /// Returns the font, with its accelerators and bounding box computed from the glyphs.
+    ///
+    /// The `DEFAULT_CHAR` property is set if a [default character](Self::default_char)
+    /// was given.
+    pub fn build(mut self) -> PcfFont<'static> {
+        if let Some(c) = self.default_char {
+            self.properties
+                .insert("DEFAULT_CHAR".to_string(), Property::Integer(c as i32));
+        }
+
+        let mut font = PcfFont {
+            glyphs: self.glyphs,
+            properties: self.properties,
+            ..Default::default()
+        };
+        font.encoding.default_char = self.default_char.map_or(0, |c| c as usize);
+        // the indices the glyphs get when the font is written with `PcfWriter`
+        let mut code_points: Vec<_> = font
+            .glyphs
+            .keys()
+            .copied()
+            .filter(|code_point| (0..=0xFFFF).contains(code_point))
+            .collect();
+        code_points.sort_unstable();
+        font.glyph_indices = code_points.into_iter().zip(0..).collect();
+        font.recompute_metrics();
+
+        font
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BdfFont, Font, PcfWriter};
+
+    fn icons() -> PcfFont<'static> {
+        FontBuilder::new()
+            .descent(1)
+            .property("FAMILY_NAME", Property::String("Icons".into()))
+            .glyph('\u{E000}', &[".##.", "#..#", "#..#", ".##.", "...."])
+            .glyph('\u{E001}', &["#.", ".#", "#.", "..", ".#"])
+            .default_char('\u{E000}')
+            .build()
+    }
+
+    #[test]
+    fn it_builds_glyphs_from_rows() {
+        let font = icons();
+        let circle = &font.glyphs[&0xE000];
+
+        assert_eq!(
+            circle.to_string(),
+            ["·##·", "#··#", "#··#", "·##·", "-----", "····", "^   ^"].join("\n")
+        );
+        assert_eq!(font.default_char(), Some('\u{E000}'));
+        assert_eq!(font.ascent(), 4);
+        assert_eq!(font.descent(), 1);
+        assert_eq!(
+            font.bounding_box,
+            BoundingBox {
+                size: Coord::new(4, 5),
+                offset: Coord::new(0, -1),
+            }
+        );
+    }
+
+    #[test]
+    fn it_exports_fonts_that_parse_the_same() {
+        let font = icons();
+
+        let pcf = PcfWriter::new(&font).to_bytes();
+        let pcf = PcfFont::parse(&pcf).unwrap();
+        for (code_point, glyph) in &font.glyphs {
+            // the writer fills in empty names and scalable widths
+            let parsed = &pcf.glyphs[code_point];
+            assert_eq!(parsed.bitmap, glyph.bitmap);
+            assert_eq!(parsed.bounding_box, glyph.bounding_box);
+            assert_eq!(parsed.shift_x, glyph.shift_x);
+        }
+        assert_eq!(pcf.properties, font.properties);
+        assert_eq!(pcf.default_char(), Some('\u{E000}'));
+        assert!(pcf.glyph_indices().eq(font.glyph_indices()));
+
+        let bdf = BdfFont::new(font.to_bdf().as_bytes());
+        assert_eq!(bdf.glyphs[&0xE001].bitmap, font.glyphs[&0xE001].bitmap);
+    }
+}
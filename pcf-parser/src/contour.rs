@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::{Coord, Glyph};
+
+/// A closed polyline along pixel edges, without repeating the first point at the end.
+pub type Contour = Vec<Coord>;
+
+impl Glyph {
+    /// Traces the glyph bitmap into closed contours along the pixel edges.
+    ///
+    /// Coordinates are pixel corners relative to the top left of the bitmap with y pointing
+    /// down, so the pixel at `(x, y)` spans from `(x, y)` to `(x + 1, y + 1)`. Outer contours
+    /// run clockwise and holes counterclockwise, which matches the non-zero fill rule.
+    /// Only corners are returned, collinear points are dropped. Pixels that only touch
+    /// diagonally get separate contours.
+    pub fn contours(&self) -> Vec<Contour> {
+        let width = self.bounding_box.size.x;
+        let height = self.bounding_box.size.y;
+        let set = |x: i32, y: i32| {
+            (0..width).contains(&x)
+                && (0..height).contains(&y)
+                && self.pixel(x as usize, y as usize)
+        };
+
+        // directed edges keyed by their start point
+        let mut edges: HashMap<(i32, i32), Vec<(i32, i32)>> = HashMap::new();
+        let mut add = |from: (i32, i32), to| edges.entry(from).or_default().push(to);
+
+        for y in 0..height {
+            for x in 0..width {
+                if !set(x, y) {
+                    continue;
+                }
+                if !set(x, y - 1) {
+                    add((x, y), (x + 1, y));
+                }
+                if !set(x + 1, y) {
+                    add((x + 1, y), (x + 1, y + 1));
+                }
+                if !set(x, y + 1) {
+                    add((x + 1, y + 1), (x, y + 1));
+                }
+                if !set(x - 1, y) {
+                    add((x, y + 1), (x, y));
+                }
+            }
+        }
+
+        let mut starts: Vec<_> = edges.keys().copied().collect();
+        starts.sort_unstable_by_key(|(x, y)| (*y, *x));
+
+        let mut contours = Vec::new();
+        for start in starts {
+            while let Some(next) = take_edge(&mut edges, start, None) {
+                let mut points = vec![start];
+                let mut from = start;
+                let mut to = next;
+
+                while to != start {
+                    let direction = (to.0 - from.0, to.1 - from.1);
+                    let next = take_edge(&mut edges, to, Some(direction))
+                        .expect("pixel edges always form closed contours");
+
+                    points.push(to);
+                    from = to;
+                    to = next;
+                }
+
+                contours.push(simplify(&points));
+            }
+        }
+
+        contours
+    }
+}
+
+/// Removes and returns an edge starting at `point`.
+///
+/// If there are two edges, which happens where pixels touch diagonally, the one turning
+/// right relative to `direction` is taken to keep the pixels in separate contours.
+fn take_edge(
+    edges: &mut HashMap<(i32, i32), Vec<(i32, i32)>>,
+    point: (i32, i32),
+    direction: Option<(i32, i32)>,
+) -> Option<(i32, i32)> {
+    let outgoing = edges.get_mut(&point)?;
+    let index = direction
+        .and_then(|(dx, dy)| {
+            let right = (point.0 - dy, point.1 + dx);
+            outgoing.iter().position(|to| *to == right)
+        })
+        .unwrap_or(0);
+    let to = outgoing.swap_remove(index);
+
+    if outgoing.is_empty() {
+        edges.remove(&point);
+    }
+
+    Some(to)
+}
+
+/// Drops the points of a closed polyline that lie on a straight line between their
+/// neighbours.
+fn simplify(points: &[(i32, i32)]) -> Contour {
+    let len = points.len();
+
+    (0..len)
+        .filter(|i| {
+            let (px, py) = points[(i + len - 1) % len];
+            let (x, y) = points[*i];
+            let (nx, ny) = points[(i + 1) % len];
+
+            (x - px) * (ny - y) != (y - py) * (nx - x)
+        })
+        .map(|i| Coord::new(points[i].0, points[i].1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Attributes, BoundingBox};
+
+    fn glyph(rows: &[&str]) -> Glyph {
+        let mut glyph = Glyph {
+            code_point: 0,
+            encoding: None,
+            bitmap: Vec::new(),
+            bounding_box: BoundingBox {
+                size: Coord::new(rows[0].len() as i32, rows.len() as i32),
+                offset: Coord::default(),
+            },
+            ink_bounds: None,
+            shift_x: 0,
+            shift_y: 0,
+            tile_index: 0,
+            attributes: Attributes::default(),
+            name: None,
+            scalable_width: None,
+        };
+        glyph.bitmap = glyph.empty_bitmap();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, pixel) in row.bytes().enumerate() {
+                glyph.set_pixel(x, y, pixel == b'#');
+            }
+        }
+
+        glyph
+    }
+
+    #[test]
+    fn it_traces_a_rectangle() {
+        let contours = glyph(&["##", "##"]).contours();
+
+        assert_eq!(
+            contours,
+            vec![vec![
+                Coord::new(0, 0),
+                Coord::new(2, 0),
+                Coord::new(2, 2),
+                Coord::new(0, 2),
+            ]]
+        );
+    }
+
+    #[test]
+    fn it_traces_holes_in_reverse() {
+        let contours = glyph(&["###", "# #", "###"]).contours();
+
+        assert_eq!(contours.len(), 2);
+        assert_eq!(contours[0].len(), 4);
+        assert_eq!(
+            contours[1],
+            vec![
+                Coord::new(1, 1),
+                Coord::new(1, 2),
+                Coord::new(2, 2),
+                Coord::new(2, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_separates_diagonal_pixels() {
+        let contours = glyph(&["# ", " #"]).contours();
+
+        assert_eq!(contours.len(), 2);
+        assert!(contours.iter().all(|contour| contour.len() == 4));
+    }
+}
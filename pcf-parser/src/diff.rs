@@ -0,0 +1,182 @@
+use std::{collections::HashSet, fmt};
+
+use crate::{BoundingBox, Coord, Glyph, PcfFont};
+
+/// The differences between two versions of a font, see [`compare`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FontDiff {
+    /// Code points only the new font has, in ascending order.
+    pub added: Vec<i32>,
+    /// Code points only the old font has, in ascending order.
+    pub removed: Vec<i32>,
+    /// Glyphs both fonts have, but with different metrics or bitmaps.
+    pub changed: Vec<GlyphDiff>,
+}
+
+/// The differences between two versions of a glyph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlyphDiff {
+    pub code_point: i32,
+    pub old_bounding_box: BoundingBox,
+    pub new_bounding_box: BoundingBox,
+    pub old_advance: i32,
+    pub new_advance: i32,
+    /// Pixels that differ, ordered top to bottom and left to right.
+    pub pixels: Vec<PixelChange>,
+}
+
+/// A pixel that is only set in one version of a glyph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PixelChange {
+    /// The pixel position relative to the glyph origin, with `y` pointing up and the row
+    /// on the baseline at `y == 0`.
+    pub position: Coord,
+    /// Whether the pixel is set in the new version.
+    pub set: bool,
+}
+
+impl FontDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl GlyphDiff {
+    /// Returns whether the bounding box or advance changed.
+    pub fn metrics_changed(&self) -> bool {
+        self.old_bounding_box != self.new_bounding_box || self.old_advance != self.new_advance
+    }
+}
+
+/// Compares the glyphs of two fonts, e.g. to review the changes of a font upgrade.
+///
+/// Bitmaps are compared relative to the glyph origin, so a glyph whose bounding box grew
+/// without changing any set pixels only differs in its metrics.
+pub fn compare(old: &PcfFont, new: &PcfFont) -> FontDiff {
+    let mut diff = FontDiff::default();
+
+    for (code_point, glyph) in &new.glyphs {
+        match old.glyphs.get(code_point) {
+            Some(old) => diff.changed.extend(compare_glyphs(*code_point, old, glyph)),
+            None => diff.added.push(*code_point),
+        }
+    }
+    diff.removed = old
+        .glyphs
+        .keys()
+        .filter(|code_point| !new.glyphs.contains_key(code_point))
+        .copied()
+        .collect();
+
+    diff.added.sort_unstable();
+    diff.removed.sort_unstable();
+    diff.changed.sort_unstable_by_key(|glyph| glyph.code_point);
+
+    diff
+}
+
+fn compare_glyphs(code_point: i32, old: &Glyph, new: &Glyph) -> Option<GlyphDiff> {
+    let (old_pixels, new_pixels) = (set_pixels(old), set_pixels(new));
+    let mut pixels: Vec<_> = old_pixels
+        .symmetric_difference(&new_pixels)
+        .map(|(x, y)| PixelChange {
+            position: Coord::new(*x, *y),
+            set: new_pixels.contains(&(*x, *y)),
+        })
+        .collect();
+    pixels.sort_unstable_by_key(|pixel| (-pixel.position.y, pixel.position.x));
+
+    let diff = GlyphDiff {
+        code_point,
+        old_bounding_box: old.bounding_box,
+        new_bounding_box: new.bounding_box,
+        old_advance: old.shift_x,
+        new_advance: new.shift_x,
+        pixels,
+    };
+
+    (diff.metrics_changed() || !diff.pixels.is_empty()).then_some(diff)
+}
+
+fn set_pixels(glyph: &Glyph) -> HashSet<(i32, i32)> {
+    let BoundingBox { size, offset } = glyph.bounding_box;
+
+    (0..size.y)
+        .flat_map(|y| (0..size.x).map(move |x| (x, y)))
+        .filter(|(x, y)| glyph.pixel(*x as usize, *y as usize))
+        .map(|(x, y)| (offset.x + x, offset.y + size.y - 1 - y))
+        .collect()
+}
+
+/// Prints one line per added (`+`), removed (`-`) and changed (`~`) glyph.
+impl fmt::Display for FontDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for code_point in &self.added {
+            writeln!(f, "+ U+{code_point:04X}")?;
+        }
+        for code_point in &self.removed {
+            writeln!(f, "- U+{code_point:04X}")?;
+        }
+        for glyph in &self.changed {
+            write!(f, "~ U+{:04X}", glyph.code_point)?;
+            if glyph.metrics_changed() {
+                let (old, new) = (glyph.old_bounding_box, glyph.new_bounding_box);
+                write!(
+                    f,
+                    " metrics {}x{}{:+}{:+} advance {} -> {}x{}{:+}{:+} advance {}",
+                    old.size.x,
+                    old.size.y,
+                    old.offset.x,
+                    old.offset.y,
+                    glyph.old_advance,
+                    new.size.x,
+                    new.size.y,
+                    new.offset.x,
+                    new.offset.y,
+                    glyph.new_advance,
+                )?;
+            }
+            if !glyph.pixels.is_empty() {
+                write!(f, " {} pixels", glyph.pixels.len())?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Filter;
+
+    #[test]
+    fn it_compares_fonts() {
+        let bytes = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let old = PcfFont::new(&bytes[..]);
+        let mut new = PcfFont::new(&bytes[..]);
+
+        assert!(compare(&old, &new).is_empty());
+
+        let a = new.glyphs.remove(&65).unwrap();
+        let b = new.glyphs.remove(&66).unwrap();
+        new.glyphs.insert(65, a.scaled(1, 1, Filter::Nearest));
+        new.glyphs.insert(66, b.scaled(2, 1, Filter::Nearest));
+        new.glyphs.insert(0xE000, a);
+        new.glyphs.remove(&67);
+
+        let diff = compare(&old, &new);
+        assert_eq!(diff.added, [0xE000]);
+        assert_eq!(diff.removed, [67]);
+        assert_eq!(diff.changed.len(), 1);
+
+        let b = &diff.changed[0];
+        assert_eq!(b.code_point, 66);
+        assert!(b.metrics_changed());
+        assert!(b.pixels.iter().any(|pixel| pixel.set));
+        assert!(diff
+            .to_string()
+            .starts_with("+ U+E000\n- U+0043\n~ U+0042 metrics"));
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+use crate::BdfError;
+
+/// An error that occurred while parsing a PCF font.
+///
+/// Errors in table data carry the table and field that couldn't be read and the offset of
+/// the field in the file. Errors of the BDF parser convert into it, so tools loading either
+/// format handle a single error type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PcfError {
+    /// The file doesn't start with the PCF magic number `\x01fcp`.
+    BadMagic,
+    /// A required table is missing.
+    MissingTable { table: &'static str },
+    /// A table uses a format the parser doesn't support.
+    UnsupportedFormat { table: &'static str, format: i32 },
+    /// A field extends past the end of the file.
+    Truncated {
+        table: &'static str,
+        field: &'static str,
+        offset: usize,
+    },
+    /// A field has a value that is out of range, e.g. a negative count.
+    InvalidValue {
+        table: &'static str,
+        field: &'static str,
+        offset: usize,
+    },
+    /// A compressed font isn't valid gzip data.
+    InvalidGzip { reason: &'static str },
+    /// A BDF font has a value that can't be parsed, see [`BdfError`].
+    Bdf { line: usize, reason: &'static str },
+}
+
+impl fmt::Display for PcfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a PCF font"),
+            Self::MissingTable { table } => write!(f, "missing {table} table"),
+            Self::UnsupportedFormat { table, format } => {
+                write!(f, "unsupported {table} table format {format:#x}")
+            }
+            Self::Truncated {
+                table,
+                field,
+                offset,
+            } => write!(f, "truncated {table} table: `{field}` at offset {offset}"),
+            Self::InvalidValue {
+                table,
+                field,
+                offset,
+            } => write!(f, "invalid {table} table: `{field}` at offset {offset}"),
+            Self::InvalidGzip { reason } => write!(f, "invalid gzip data: {reason}"),
+            Self::Bdf { line, reason } => write!(f, "invalid BDF font: line {line}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for PcfError {}
+
+impl From<BdfError> for PcfError {
+    fn from(error: BdfError) -> Self {
+        let BdfError { line, reason } = error;
+        Self::Bdf { line, reason }
+    }
+}
@@ -0,0 +1,78 @@
+//! Decompression of `.pcf.gz` files, the form most Linux distributions ship PCF fonts in.
+
+use crate::{PcfError, PcfFont};
+
+const FHCRC: u8 = 1 << 1;
+const FEXTRA: u8 = 1 << 2;
+const FNAME: u8 = 1 << 3;
+const FCOMMENT: u8 = 1 << 4;
+
+/// Decompresses a gzip file.
+pub fn gunzip(bytes: &[u8]) -> Result<Vec<u8>, PcfError> {
+    let invalid = |reason| PcfError::InvalidGzip { reason };
+    let truncated = invalid("truncated header");
+
+    let header = bytes.get(..10).ok_or(truncated.clone())?;
+    if header[..3] != [0x1f, 0x8b, 8] {
+        return Err(invalid("not a deflate compressed gzip file"));
+    }
+    let flags = header[3];
+
+    let mut cursor = 10;
+    if flags & FEXTRA != 0 {
+        let len = bytes.get(cursor..cursor + 2).ok_or(truncated.clone())?;
+        cursor += 2 + usize::from(u16::from_le_bytes([len[0], len[1]]));
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let rest = bytes.get(cursor..).ok_or(truncated.clone())?;
+            cursor += rest.iter().position(|b| *b == 0).ok_or(truncated.clone())? + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        cursor += 2;
+    }
+
+    let deflated = bytes.get(cursor..).ok_or(truncated)?;
+    let data = miniz_oxide::inflate::decompress_to_vec(deflated)
+        .map_err(|_| invalid("corrupt compressed data"))?;
+
+    // the trailer ends with the size of the data modulo 2^32
+    let size = bytes.len().checked_sub(4).map(|i| &bytes[i..]);
+    if size != Some(&(data.len() as u32).to_le_bytes()[..]) {
+        return Err(invalid("size mismatch"));
+    }
+
+    Ok(data)
+}
+
+impl<'a> PcfFont<'a> {
+    /// Parses a gzip compressed PCF font, decompressing it into `buffer`, which the font
+    /// borrows from.
+    pub fn parse_gz(bytes: &[u8], buffer: &'a mut Vec<u8>) -> Result<PcfFont<'a>, PcfError> {
+        *buffer = gunzip(bytes)?;
+
+        Self::parse(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_compressed_fonts() {
+        let compressed = include_bytes!("../../assets/OpenSans-Regular-12.pcf.gz");
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+
+        let mut buffer = Vec::new();
+        let pcf = PcfFont::parse_gz(compressed, &mut buffer).unwrap();
+        assert_eq!(pcf.glyphs, PcfFont::new(font).glyphs);
+
+        assert!(matches!(
+            gunzip(&compressed[..compressed.len() - 10]),
+            Err(PcfError::InvalidGzip { .. })
+        ));
+        assert!(PcfFont::parse_gz(font, &mut buffer).is_err());
+    }
+}
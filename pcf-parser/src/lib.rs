@@ -2,6 +2,33 @@
 use byteorder::{BigEndian, ByteOrder, LittleEndian};
 use std::collections::HashMap;
 
+mod ascii;
+mod atlas;
+pub mod batch;
+mod bdf;
+mod builder;
+mod contour;
+mod diff;
+mod error;
+#[cfg(feature = "gzip")]
+mod gzip;
+mod read;
+mod scale;
+mod subset;
+mod writer;
+
+pub use atlas::{Atlas, AtlasGlyph};
+pub use bdf::{BdfError, BdfFont};
+pub use builder::FontBuilder;
+pub use contour::Contour;
+pub use diff::{compare, FontDiff, GlyphDiff, PixelChange};
+pub use error::PcfError;
+#[cfg(feature = "gzip")]
+pub use gzip::gunzip;
+pub use scale::Filter;
+pub use subset::{Subset, SubsetGlyph};
+pub use writer::PcfWriter;
+
 // From https://fontforge.org/docs/techref/pcf-format.html
 // type field
 const PCF_PROPERTIES: usize = 1 << 0;
@@ -13,6 +40,9 @@ const PCF_BDF_ENCODINGS: usize = 1 << 5;
 const PCF_SWIDTHS: usize = 1 << 6;
 const PCF_GLYPH_NAMES: usize = 1 << 7;
 const PCF_BDF_ACCELERATORS: usize = 1 << 8;
+const KNOWN_TABLES: usize = (1 << 9) - 1;
+
+const PCF_MAGIC: i32 = i32::from_le_bytes(*b"\x01fcp");
 
 // format field
 const PCF_DEFAULT_FORMAT: i32 = 0x00000000;
@@ -26,6 +56,46 @@ const PCF_BYTE_MASK: i32 = 1 << 2; // If set then Most Sig Byte First
 const PCF_BIT_MASK: i32 = 1 << 3; // If set then Most Sig Bit First
 const PCF_SCAN_UNIT_MASK: i32 = 3 << 4; // See the bitmap table for explanation
 
+/// The byte order of the data in a table, from the [`PCF_BYTE_MASK`] bit of its format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Endian {
+    #[default]
+    Big,
+    Little,
+}
+
+impl Endian {
+    fn of(format: i32) -> Self {
+        if format & PCF_BYTE_MASK != 0 {
+            Self::Big
+        } else {
+            Self::Little
+        }
+    }
+
+    fn read_i16(self, bytes: &[u8]) -> i16 {
+        match self {
+            Self::Big => BigEndian::read_i16(bytes),
+            Self::Little => LittleEndian::read_i16(bytes),
+        }
+    }
+
+    fn read_u16(self, bytes: &[u8]) -> u16 {
+        match self {
+            Self::Big => BigEndian::read_u16(bytes),
+            Self::Little => LittleEndian::read_u16(bytes),
+        }
+    }
+
+    fn read_i32(self, bytes: &[u8]) -> i32 {
+        match self {
+            Self::Big => BigEndian::read_i32(bytes),
+            Self::Little => LittleEndian::read_i32(bytes),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 struct Table {
     format: i32,
@@ -33,14 +103,16 @@ struct Table {
     offset: usize,
 }
 
+/// Glyph metrics as stored in the metrics and accelerators tables.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
-struct UncompressedMetrics {
-    left_side_bearing: i16,
-    right_side_bearing: i16,
-    character_width: i16,
-    character_ascent: i16,
-    character_descent: i16,
-    character_attributes: u16,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UncompressedMetrics {
+    pub left_side_bearing: i16,
+    pub right_side_bearing: i16,
+    pub character_width: i16,
+    pub character_ascent: i16,
+    pub character_descent: i16,
+    pub character_attributes: u16,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
@@ -53,26 +125,129 @@ struct CompressedMetrics {
     character_attributes: i16,
 }
 
-#[derive(Debug, Default, PartialEq)]
-struct Accelerators {
-    no_overlap: u8,
-    constant_metrics: u8,
-    terminal_font: u8,
-    constant_width: u8,
-    ink_inside: u8,
-    ink_metrics: u8,
-    draw_direction: u8,
-    padding: u8,
-    font_ascent: i32,
-    font_descent: i32,
-    max_overlap: i32,
-    minbounds: UncompressedMetrics,
-    maxbounds: UncompressedMetrics,
-    ink_minbounds: UncompressedMetrics,
-    ink_maxbounds: UncompressedMetrics,
+impl UncompressedMetrics {
+    /// Combines every field but the attributes with `f`, e.g. to find bounds.
+    fn combine(&self, other: &Self, f: fn(i16, i16) -> i16) -> Self {
+        Self {
+            left_side_bearing: f(self.left_side_bearing, other.left_side_bearing),
+            right_side_bearing: f(self.right_side_bearing, other.right_side_bearing),
+            character_width: f(self.character_width, other.character_width),
+            character_ascent: f(self.character_ascent, other.character_ascent),
+            character_descent: f(self.character_descent, other.character_descent),
+            character_attributes: 0,
+        }
+    }
+}
+
+impl From<&Glyph> for UncompressedMetrics {
+    fn from(glyph: &Glyph) -> Self {
+        Self::with_bounds(glyph, glyph.bounding_box)
+    }
+}
+
+impl UncompressedMetrics {
+    /// Returns the metrics of `glyph` with other bounds, e.g. its ink bounds.
+    fn with_bounds(glyph: &Glyph, bounds: BoundingBox) -> Self {
+        let BoundingBox { size, offset } = bounds;
+
+        Self {
+            left_side_bearing: offset.x as i16,
+            right_side_bearing: (offset.x + size.x) as i16,
+            character_width: glyph.shift_x as i16,
+            character_ascent: (offset.y + size.y) as i16,
+            character_descent: -offset.y as i16,
+            character_attributes: glyph.attributes.bits(),
+        }
+    }
+}
+
+impl From<UncompressedMetrics> for CompressedMetrics {
+    fn from(metrics: UncompressedMetrics) -> Self {
+        Self {
+            left_side_bearing: metrics.left_side_bearing,
+            right_side_bearing: metrics.right_side_bearing,
+            character_width: metrics.character_width,
+            character_ascent: metrics.character_ascent,
+            character_descent: metrics.character_descent,
+            character_attributes: metrics.character_attributes as i16,
+        }
+    }
+}
+
+impl CompressedMetrics {
+    fn width(&self) -> i32 {
+        i32::from(self.right_side_bearing) - i32::from(self.left_side_bearing)
+    }
+
+    fn height(&self) -> i32 {
+        i32::from(self.character_ascent) + i32::from(self.character_descent)
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox {
+            size: Coord::new(self.width(), self.height()),
+            offset: Coord::new(
+                self.left_side_bearing.into(),
+                -i32::from(self.character_descent),
+            ),
+        }
+    }
+}
+
+/// The contents of an accelerators table, font wide metrics that let renderers skip work.
+///
+/// The flags are stored as bytes, which are nonzero if the flag is set.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Accelerators {
+    /// Set if glyphs never overlap the glyphs next to them.
+    pub no_overlap: u8,
+    /// Set if all glyphs have the same metrics.
+    pub constant_metrics: u8,
+    /// Set if all glyphs have the same metrics and fill their character cell.
+    pub terminal_font: u8,
+    pub constant_width: u8,
+    /// Set if the ink of all glyphs is inside their bounding boxes.
+    pub ink_inside: u8,
+    /// Set if the ink metrics differ from the metrics of some glyphs.
+    pub ink_metrics: u8,
+    /// 0 for left to right, 1 for right to left.
+    pub draw_direction: u8,
+    pub padding: u8,
+    pub font_ascent: i32,
+    pub font_descent: i32,
+    /// The largest distance by which a glyph extends past its advance.
+    pub max_overlap: i32,
+    pub minbounds: UncompressedMetrics,
+    pub maxbounds: UncompressedMetrics,
+    /// The ink bounds, which equal the bounds in tables without them.
+    pub ink_minbounds: UncompressedMetrics,
+    pub ink_maxbounds: UncompressedMetrics,
+}
+
+/// The tables a PCF font can store its accelerators in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AcceleratorsTable {
+    /// `PCF_BDF_ACCELERATORS`, whose bounds only cover encoded glyphs.
+    #[default]
+    Bdf,
+    /// `PCF_ACCELERATORS`, whose bounds cover all glyphs.
+    Pcf,
+}
+
+impl AcceleratorsTable {
+    /// Returns the type of the table in the table of contents.
+    const fn kind(self) -> usize {
+        match self {
+            Self::Bdf => PCF_BDF_ACCELERATORS,
+            Self::Pcf => PCF_ACCELERATORS,
+        }
+    }
 }
 
 #[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Encoding {
     min_byte2: usize,
     max_byte2: usize,
@@ -82,18 +257,66 @@ struct Encoding {
 }
 
 #[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Bitmap {
     glyph_count: usize,
     bitmap_sizes: usize,
+    /// Bytes each glyph row is padded to.
+    glyph_pad: usize,
+    declared_glyph_pad: usize,
+    /// Byte order within each scan unit of a row.
+    endian: Endian,
+    /// Bytes per scan unit.
+    scan_unit: usize,
+    /// Whether the leftmost pixel of a byte is its most significant bit.
+    msb_first: bool,
 }
 
-#[derive(Debug, Default, PartialEq)]
+/// A deviation from the PCF specification that the parser worked around.
+///
+/// Quirks are detected by the inconsistencies they cause rather than by the producer of a
+/// font, so fonts from any tool with the same bug are handled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quirk {
+    /// The glyph padding of the bitmap format doesn't match the bitmap data, e.g. because
+    /// the format was changed without repacking the bitmaps. The padding whose bitmap size
+    /// matches the data is used instead. Paddings are in bytes.
+    GlyphPadding { declared: usize, actual: usize },
+    /// A table extends past the end of the file. Only the data within the file is used,
+    /// which is enough if the declared size includes unused space.
+    TruncatedTable { kind: u32 },
+}
+
+/// A table of a type the parser doesn't know, kept as raw bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RawTable<'a> {
+    /// The type field of the table of contents entry.
+    pub kind: u32,
+    /// The format field of the table of contents entry.
+    pub format: i32,
+    /// The table data, including its leading format field.
+    pub data: &'a [u8],
+}
+
+/// The value of a font property.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Property {
+    Integer(i32),
+    String(String),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoundingBox {
     pub size: Coord,
     pub offset: Coord,
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coord {
     pub x: i32,
     pub y: i32,
@@ -105,17 +328,218 @@ impl Coord {
     }
 }
 
+/// The glyphs and metadata shared by all font formats.
+pub trait Font {
+    /// Returns the glyphs by code point.
+    fn glyphs(&self) -> &HashMap<i32, Glyph>;
+
+    /// Returns the bounding box of all glyphs.
+    fn bounding_box(&self) -> &BoundingBox;
+
+    fn properties(&self) -> &HashMap<String, Property>;
+
+    /// Returns the value of an integer property, e.g. `PIXEL_SIZE`.
+    fn integer_property(&self, name: &str) -> Option<i32> {
+        match self.properties().get(name)? {
+            Property::Integer(value) => Some(*value),
+            Property::String(_) => None,
+        }
+    }
+
+    /// Returns the value of a string property, e.g. `FAMILY_NAME`.
+    fn string_property(&self, name: &str) -> Option<&str> {
+        match self.properties().get(name)? {
+            Property::String(value) => Some(value),
+            Property::Integer(_) => None,
+        }
+    }
+
+    /// Returns the advance width of a glyph in fractional pixels, computed from its scalable
+    /// width, the point size and the horizontal resolution of the font.
+    ///
+    /// Unlike [`Glyph::shift_x`], it isn't rounded, so it can be used to space glyphs that
+    /// are mixed with vector rendering or drawn at fractional scale factors.
+    fn scalable_advance(&self, glyph: &Glyph) -> Option<f32> {
+        let width = glyph.scalable_width? as f32;
+        let point_size = self.point_size()? as f32 / 10.0;
+        let (resolution, _) = self.resolution()?;
+
+        Some(width / 1000.0 * point_size / 72.0 * resolution as f32)
+    }
+
+    /// Returns the characters of all encoded glyphs in ascending order.
+    fn chars(&self) -> std::vec::IntoIter<char> {
+        let mut chars: Vec<char> = self.glyphs().values().filter_map(|g| g.encoding).collect();
+        chars.sort_unstable();
+
+        chars.into_iter()
+    }
+
+    /// Returns whether the font has a glyph for `c`.
+    fn contains(&self, c: char) -> bool {
+        self.glyphs()
+            .get(&(c as i32))
+            .is_some_and(|glyph| glyph.encoding == Some(c))
+    }
+
+    /// Returns the glyph with the PostScript name `name`.
+    fn glyph_by_name(&self, name: &str) -> Option<&Glyph> {
+        self.glyphs()
+            .values()
+            .find(|glyph| glyph.name.as_deref() == Some(name))
+    }
+
+    /// Returns the character the font draws in place of characters it has no glyph for,
+    /// from the `DEFAULT_CHAR` property, if the font has a glyph for it.
+    fn default_char(&self) -> Option<char> {
+        let c = char::from_u32(self.integer_property("DEFAULT_CHAR")?.try_into().ok()?)?;
+
+        self.contains(c).then_some(c)
+    }
+
+    /// Returns the design size of the font in decipoints (1/720 inch) from `POINT_SIZE`.
+    fn point_size(&self) -> Option<u32> {
+        self.integer_property("POINT_SIZE")?.try_into().ok()
+    }
+
+    /// Returns the horizontal and vertical design resolution of the font in dots per inch
+    /// from `RESOLUTION_X` and `RESOLUTION_Y`.
+    fn resolution(&self) -> Option<(u32, u32)> {
+        let x = self.integer_property("RESOLUTION_X")?.try_into().ok()?;
+        let y = self.integer_property("RESOLUTION_Y")?.try_into().ok()?;
+
+        Some((x, y))
+    }
+
+    /// Returns the distance from the baseline to the top of the font in pixels, from
+    /// `FONT_ASCENT` or the bounding box.
+    fn ascent(&self) -> i32 {
+        let bounding_box = self.bounding_box();
+
+        self.integer_property("FONT_ASCENT")
+            .unwrap_or(bounding_box.size.y + bounding_box.offset.y)
+    }
+
+    /// Returns the distance from the baseline to the bottom of the font in pixels, from
+    /// `FONT_DESCENT` or the bounding box.
+    fn descent(&self) -> i32 {
+        self.integer_property("FONT_DESCENT")
+            .unwrap_or(-self.bounding_box().offset.y)
+    }
+
+    /// Returns the height of capital letters in pixels, from `CAP_HEIGHT` or the top of the
+    /// ink of `X`.
+    fn cap_height(&self) -> Option<i32> {
+        self.integer_property("CAP_HEIGHT")
+            .or_else(|| ink_top(self, 'X'))
+    }
+
+    /// Returns the height of lowercase letters in pixels, from `X_HEIGHT` or the top of the
+    /// ink of `x`.
+    fn x_height(&self) -> Option<i32> {
+        self.integer_property("X_HEIGHT")
+            .or_else(|| ink_top(self, 'x'))
+    }
+
+    /// Returns whether all glyphs have the same advance and fit their character cell, from a
+    /// `SPACING` property of `C`.
+    fn is_monospaced(&self) -> bool {
+        self.string_property("SPACING")
+            .is_some_and(|spacing| spacing.eq_ignore_ascii_case("c"))
+    }
+}
+
+/// Returns the distance from the baseline to the top of the ink of `c`, or its bounding box if
+/// it has no ink bounds.
+fn ink_top<F: Font + ?Sized>(font: &F, c: char) -> Option<i32> {
+    let glyph = font.glyphs().get(&(c as i32))?;
+    let bounds = glyph.ink_bounds.unwrap_or(glyph.bounding_box);
+
+    font.contains(c).then_some(bounds.size.y + bounds.offset.y)
+}
+
+impl Font for PcfFont<'_> {
+    fn glyphs(&self) -> &HashMap<i32, Glyph> {
+        &self.glyphs
+    }
+
+    fn bounding_box(&self) -> &BoundingBox {
+        &self.bounding_box
+    }
+
+    fn properties(&self) -> &HashMap<String, Property> {
+        &self.properties
+    }
+
+    /// Returns the default character of the encodings table, if the font has a glyph for it.
+    fn default_char(&self) -> Option<char> {
+        let c = char::from_u32(self.encoding_default_char().into())?;
+
+        self.contains(c).then_some(c)
+    }
+
+    /// Returns the font ascent of the accelerators table.
+    fn ascent(&self) -> i32 {
+        self.accelerators.font_ascent
+    }
+
+    /// Returns the font descent of the accelerators table.
+    fn descent(&self) -> i32 {
+        self.accelerators.font_descent
+    }
+
+    /// Returns whether the accelerators table marks the font as a constant width terminal
+    /// font.
+    fn is_monospaced(&self) -> bool {
+        self.accelerators.constant_width != 0 && self.accelerators.terminal_font != 0
+    }
+}
+
 type Tables = HashMap<usize, Table>;
 
+/// Runs a parse phase, logging its duration when the `log` feature is enabled.
+#[cfg(feature = "log")]
+fn timed<T>(phase: &str, f: impl FnOnce() -> T) -> T {
+    let start = std::time::Instant::now();
+    let result = f();
+    log::debug!("{phase} took {:?}", start.elapsed());
+
+    result
+}
+
+#[cfg(not(feature = "log"))]
+fn timed<T>(_phase: &str, f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+/// A parsed PCF font.
+///
+/// With the `serde` feature the parsed font can be serialized, without the file it was
+/// parsed from. Deserialized fonts have no tables to read on demand, so e.g.
+/// [`table_accelerators`](Self::table_accelerators) finds none. The data of unknown tables
+/// is borrowed, so fonts with them need a format that can borrow bytes, like bincode.
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PcfFont<'a> {
     pub glyphs: HashMap<i32, Glyph>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     tables: Tables,
+    #[cfg_attr(feature = "serde", serde(skip))]
     bytes: &'a [u8],
     accelerators: Accelerators,
+    accelerators_table: AcceleratorsTable,
     encoding: Encoding,
     bitmap: Bitmap,
     pub bounding_box: BoundingBox,
+    pub properties: HashMap<String, Property>,
+    /// Non-standard tables, ordered by type.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub unknown_tables: Vec<RawTable<'a>>,
+    /// Workarounds applied while parsing, in the order they were applied.
+    pub quirks: Vec<Quirk>,
+    /// The glyph index of every encoded code point, sorted by code point.
+    glyph_indices: Vec<(i32, u16)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     metadata: Metadata,
 }
 
@@ -130,68 +554,267 @@ struct Metadata {
     metrics_size: usize,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Glyph {
     pub code_point: i32,
     pub encoding: Option<char>,
+    /// The pixels, packed into rows of [`stride`](Self::stride) bytes with the leftmost
+    /// pixel in the most significant bit. Unused bits at the end of a row are clear.
     pub bitmap: Vec<u8>,
     pub bounding_box: BoundingBox,
+    /// The bounds of the set pixels from the ink metrics table, if the font has one. They can
+    /// be tighter than the bounding box, which may include blank rows and columns, e.g. the
+    /// full character cells of terminal fonts.
+    pub ink_bounds: Option<BoundingBox>,
     pub shift_x: i32,
     pub shift_y: i32,
     pub tile_index: i32,
+    pub attributes: Attributes,
+    /// The PostScript name of the glyph, e.g. `Aacute`, if the font has glyph names.
+    pub name: Option<String>,
+    /// The advance width of the scalable font the glyph was rendered from, in 1/1000 of the
+    /// point size, if the font has scalable widths. See [`Font::scalable_advance`].
+    pub scalable_width: Option<i32>,
+}
+
+/// The `character_attributes` field of the glyph metrics.
+///
+/// The PCF format doesn't assign meanings to the bits, they are left to applications, e.g.
+/// terminal emulators marking line drawing characters. Compressed metrics have no
+/// attributes, so all bits are clear for fonts using them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attributes(pub u16);
+
+impl Attributes {
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns whether bit `index`, counted from the least significant bit, is set.
+    pub const fn bit(self, index: u32) -> bool {
+        index < u16::BITS && self.0 & (1 << index) != 0
+    }
+
+    /// Returns whether all bits of `mask` are set.
+    pub const fn contains(self, mask: u16) -> bool {
+        self.0 & mask == mask
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
 }
 
 impl Glyph {
+    /// Returns the number of bytes per bitmap row.
+    pub fn stride(&self) -> usize {
+        usize::try_from(self.bounding_box.size.x)
+            .expect("pixel width failed")
+            .div_ceil(8)
+    }
+
+    /// Returns a cleared bitmap for the bounding box of the glyph.
+    pub fn empty_bitmap(&self) -> Vec<u8> {
+        let height = usize::try_from(self.bounding_box.size.y).expect("pixel height failed");
+        vec![0; self.stride() * height]
+    }
+
     pub fn pixel(&self, x: usize, y: usize) -> bool {
-        let width = usize::try_from(self.bounding_box.size.x).expect("pixel width failed");
-        self.bitmap[y * width + x] != 0
+        self.bitmap[y * self.stride() + x / 8] & (0x80 >> (x % 8)) != 0
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, set: bool) {
+        let index = y * self.stride() + x / 8;
+        match set {
+            true => self.bitmap[index] |= 0x80 >> (x % 8),
+            false => self.bitmap[index] &= !(0x80 >> (x % 8)),
+        }
     }
 }
 
-impl PcfFont<'_> {
-    pub fn new(font: &[u8]) -> PcfFont {
+impl<'a> PcfFont<'a> {
+    /// Parses a PCF font.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the font is malformed, see [`parse`](Self::parse).
+    pub fn new(font: &'a [u8]) -> PcfFont<'a> {
+        Self::parse(font).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Parses a PCF font, returning an error if it is malformed or uses an unsupported
+    /// format.
+    ///
+    /// Parsing doesn't panic on any input, and glyphs can't take up more memory than the
+    /// bitmap data of the font, so untrusted fonts can be parsed. The `fuzz` directory has a
+    /// cargo-fuzz target checking this.
+    pub fn parse(font: &'a [u8]) -> Result<PcfFont<'a>, PcfError> {
+        Self::parse_glyphs(font, true)
+    }
+
+    /// Parses the encodings and metrics of a PCF font without decoding its bitmaps, for tools
+    /// that only lay out text, e.g. to break lines at build time.
+    ///
+    /// The glyphs have empty [`bitmap`](Glyph::bitmap)s, so they can't be drawn or written.
+    /// Errors are returned like by [`parse`](Self::parse), except for bitmap data missing
+    /// from the file.
+    pub fn parse_metrics_only(font: &'a [u8]) -> Result<PcfFont<'a>, PcfError> {
+        Self::parse_glyphs(font, false)
+    }
+
+    fn parse_glyphs(font: &'a [u8], bitmaps: bool) -> Result<PcfFont<'a>, PcfError> {
         let mut pcf = PcfFont {
             bytes: font,
             ..Default::default()
         };
 
-        pcf.tables = pcf.read_tables();
-        pcf.accelerators = pcf.read_accelerators();
-        pcf.encoding = pcf.read_encoding();
-        pcf.bitmap = pcf.read_bitmap();
+        if pcf.header()? != PCF_MAGIC {
+            return Err(PcfError::BadMagic);
+        }
+
+        pcf.tables = timed("read_tables", || pcf.read_tables())?;
+        pcf.quirks = pcf.truncated_tables();
+        pcf.unknown_tables = pcf.read_unknown_tables();
+        pcf.properties = timed("read_properties", || pcf.read_properties())?;
+        pcf.accelerators_table = match pcf.tables.contains_key(&PCF_BDF_ACCELERATORS) {
+            true => AcceleratorsTable::Bdf,
+            false => AcceleratorsTable::Pcf,
+        };
+        pcf.accelerators = timed("read_accelerators", || {
+            pcf.read_accelerators(pcf.accelerators_table)
+        })?;
+        pcf.encoding = timed("read_encoding", || pcf.read_encoding())?;
+        timed("read_bitmap", || pcf.load_bitmap())?;
         pcf.bounding_box = pcf.get_bounding_box();
-        pcf.metadata = pcf.load_metadata();
-        pcf.load_glyphs();
+        pcf.metadata = pcf.load_metadata()?;
+        timed("load_glyphs", || pcf.load_glyphs(bitmaps))?;
+
+        #[cfg(feature = "log")]
+        for quirk in &pcf.quirks {
+            log::warn!("applied quirk {quirk:?}");
+        }
+        #[cfg(feature = "log")]
+        log::debug!("loaded {} glyphs", pcf.glyphs.len());
+
+        Ok(pcf)
+    }
+
+    /// Returns `len` bytes at `offset`, or an error naming the field if they are out of
+    /// bounds.
+    fn field(
+        &self,
+        offset: usize,
+        len: usize,
+        table: &'static str,
+        field: &'static str,
+    ) -> Result<&'a [u8], PcfError> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(offset..end))
+            .ok_or(PcfError::Truncated {
+                table,
+                field,
+                offset,
+            })
+    }
+
+    fn read_u8(
+        &self,
+        offset: usize,
+        table: &'static str,
+        field: &'static str,
+    ) -> Result<u8, PcfError> {
+        Ok(self.field(offset, 1, table, field)?[0])
+    }
+
+    fn read_i16(
+        &self,
+        endian: Endian,
+        offset: usize,
+        table: &'static str,
+        field: &'static str,
+    ) -> Result<i16, PcfError> {
+        Ok(endian.read_i16(self.field(offset, 2, table, field)?))
+    }
+
+    fn read_u16(
+        &self,
+        endian: Endian,
+        offset: usize,
+        table: &'static str,
+        field: &'static str,
+    ) -> Result<u16, PcfError> {
+        Ok(endian.read_u16(self.field(offset, 2, table, field)?))
+    }
 
-        pcf
+    fn read_i32(
+        &self,
+        endian: Endian,
+        offset: usize,
+        table: &'static str,
+        field: &'static str,
+    ) -> Result<i32, PcfError> {
+        Ok(endian.read_i32(self.field(offset, 4, table, field)?))
+    }
+
+    /// Reads an `i32` that has to be a valid non-negative `usize`.
+    fn read_usize(
+        &self,
+        endian: Endian,
+        offset: usize,
+        table: &'static str,
+        field: &'static str,
+    ) -> Result<usize, PcfError> {
+        usize::try_from(self.read_i32(endian, offset, table, field)?).map_err(|_| {
+            PcfError::InvalidValue {
+                table,
+                field,
+                offset,
+            }
+        })
+    }
+
+    fn table(&self, kind: usize, table: &'static str) -> Result<&Table, PcfError> {
+        self.tables
+            .get(&kind)
+            .ok_or(PcfError::MissingTable { table })
+    }
+
+    /// Returns the byte order of a table from the format at its start, which is always
+    /// little endian.
+    fn endian(&self, table: &Table, name: &'static str) -> Result<Endian, PcfError> {
+        let format = self.read_i32(Endian::Little, table.offset, name, "format")?;
+
+        Ok(Endian::of(format))
     }
 
     // "1fcp"
     // 1, 102, 99, 112
     // 1885562369 lsbi32
-    fn header(&self) -> i32 {
-        LittleEndian::read_i32(&self.bytes[0..4])
+    fn header(&self) -> Result<i32, PcfError> {
+        self.read_i32(Endian::Little, 0, "header", "magic")
     }
 
-    fn table_count(&self) -> i32 {
-        LittleEndian::read_i32(&self.bytes[4..8])
+    fn table_count(&self) -> Result<usize, PcfError> {
+        self.read_usize(Endian::Little, 4, "header", "table_count")
     }
 
     fn tables(&self) -> &Tables {
         &self.tables
     }
 
-    fn read_tables(&self) -> HashMap<usize, Table> {
-        (0..self.table_count())
-            .fold((8, HashMap::new()), |(mut cursor, mut tables), _| {
-                let r#type = LittleEndian::read_i32(&self.bytes[cursor..cursor + 4])
-                    .try_into()
-                    .expect("unable to convert type i32 into usize");
-                let format = LittleEndian::read_i32(&self.bytes[cursor + 4..cursor + 8]);
-                let size = LittleEndian::read_i32(&self.bytes[cursor + 8..cursor + 12]);
-                let offset = LittleEndian::read_i32(&self.bytes[cursor + 12..cursor + 16])
-                    .try_into()
-                    .expect("unable to convert offset i32 into usize");
+    fn read_tables(&self) -> Result<HashMap<usize, Table>, PcfError> {
+        const TOC: &str = "table of contents";
+
+        let entries = (0..self.table_count()?)
+            .map(|i| {
+                let cursor = 8 + 16 * i;
+                let r#type = self.read_usize(Endian::Little, cursor, TOC, "type")?;
+                let format = self.read_i32(Endian::Little, cursor + 4, TOC, "format")?;
+                let size = self.read_i32(Endian::Little, cursor + 8, TOC, "size")?;
+                let offset = self.read_usize(Endian::Little, cursor + 12, TOC, "offset")?;
 
                 let table = Table {
                     format,
@@ -199,60 +822,181 @@ impl PcfFont<'_> {
                     offset,
                 };
 
-                tables.insert(r#type, table);
-                cursor += 16;
+                Ok((r#type, table, cursor + 12))
+            })
+            .collect::<Result<Vec<_>, PcfError>>()?;
 
-                (cursor, tables)
+        // tables have to start within the file and must not overlap. The declared size of
+        // truncated tables may include unused space (see `Quirk::TruncatedTable`), so they
+        // only have to start after the previous table.
+        let mut ranges: Vec<_> = entries
+            .iter()
+            .map(|(_, table, cursor)| {
+                let end = usize::try_from(table.size)
+                    .ok()
+                    .and_then(|size| table.offset.checked_add(size))
+                    .filter(|end| *end <= self.bytes.len())
+                    .unwrap_or(table.offset + 1);
+                (table.offset, end, *cursor)
             })
-            .1
+            .collect();
+        ranges.sort_unstable();
+        let mut previous_end = 0;
+        for (offset, end, cursor) in ranges {
+            if offset < previous_end || offset >= self.bytes.len() {
+                return Err(PcfError::InvalidValue {
+                    table: TOC,
+                    field: "offset",
+                    offset: cursor,
+                });
+            }
+            previous_end = end;
+        }
+
+        Ok(entries
+            .into_iter()
+            .map(|(r#type, table, _)| (r#type, table))
+            .collect())
     }
 
-    fn read_accelerators(&self) -> Accelerators {
-        let accelerators = self
+    fn truncated_tables(&self) -> Vec<Quirk> {
+        let mut kinds: Vec<_> = self
             .tables
-            .get(&PCF_BDF_ACCELERATORS)
-            .or_else(|| self.tables.get(&PCF_ACCELERATORS));
+            .iter()
+            .filter(|(_, table)| {
+                let available = self.bytes.len().saturating_sub(table.offset);
+                usize::try_from(table.size).map_or(true, |size| size > available)
+            })
+            .map(|(kind, _)| *kind as u32)
+            .collect();
+        kinds.sort_unstable();
+
+        kinds
+            .into_iter()
+            .map(|kind| Quirk::TruncatedTable { kind })
+            .collect()
+    }
+
+    fn read_unknown_tables(&self) -> Vec<RawTable<'a>> {
+        let mut tables: Vec<_> = self
+            .tables
+            .iter()
+            .filter(|(kind, _)| **kind & !KNOWN_TABLES != 0)
+            .filter_map(|(kind, table)| {
+                let size = usize::try_from(table.size).ok()?;
+                let end = (table.offset + size).min(self.bytes.len());
+
+                Some(RawTable {
+                    kind: u32::try_from(*kind).ok()?,
+                    format: table.format,
+                    data: self.bytes.get(table.offset..end)?,
+                })
+            })
+            .collect();
+        tables.sort_by_key(|table| table.kind);
 
-        assert!(accelerators.is_some(), "No accelerator table found");
+        tables
+    }
+
+    fn read_properties(&self) -> Result<HashMap<String, Property>, PcfError> {
+        const TABLE: &str = "properties";
 
-        let table = accelerators.unwrap();
+        let Some(table) = self.tables.get(&PCF_PROPERTIES) else {
+            return Ok(HashMap::new());
+        };
 
         let mut cursor = table.offset;
-        let format = LittleEndian::read_i32(&self.bytes[cursor..cursor + 4]);
+        let format = self.read_i32(Endian::Little, cursor, TABLE, "format")?;
+        let endian = Endian::of(format);
+        cursor += 4;
+
+        let count = self.read_usize(endian, cursor, TABLE, "nprops")?;
         cursor += 4;
 
-        assert!(format & PCF_BYTE_MASK != 0, "Only big endian supported");
+        let padding = if count & 3 == 0 { 0 } else { 4 - (count & 3) };
+        let strings = count.saturating_mul(9).saturating_add(cursor + padding + 4);
+        let string = |offset: usize, field| {
+            let start = strings.saturating_add(offset);
+            let len = self
+                .bytes
+                .get(start..)
+                .and_then(|bytes| bytes.iter().position(|b| *b == 0))
+                .ok_or(PcfError::Truncated {
+                    table: TABLE,
+                    field,
+                    offset: start,
+                })?;
+
+            Ok::<_, PcfError>(String::from_utf8_lossy(&self.bytes[start..start + len]).into_owned())
+        };
+
+        (0..count)
+            .map(|i| {
+                let cursor = cursor + 9 * i;
+                let name = self.read_usize(endian, cursor, TABLE, "name_offset")?;
+                let is_string = self.read_u8(cursor + 4, TABLE, "isStringProp")? != 0;
+                let value = self.read_i32(endian, cursor + 5, TABLE, "value")?;
+
+                let value = if is_string {
+                    let offset = usize::try_from(value).map_err(|_| PcfError::InvalidValue {
+                        table: TABLE,
+                        field: "value",
+                        offset: cursor + 5,
+                    })?;
+                    Property::String(string(offset, "value")?)
+                } else {
+                    Property::Integer(value)
+                };
+
+                Ok((string(name, "name_offset")?, value))
+            })
+            .collect()
+    }
+
+    fn read_accelerators(&self, kind: AcceleratorsTable) -> Result<Accelerators, PcfError> {
+        const TABLE: &str = "accelerators";
+
+        let table = self
+            .tables
+            .get(&kind.kind())
+            .ok_or(PcfError::MissingTable { table: TABLE })?;
+
+        let mut cursor = table.offset;
+        let format = self.read_i32(Endian::Little, cursor, TABLE, "format")?;
+        let endian = Endian::of(format);
+        cursor += 4;
 
         let has_inkbounds = format & PCF_ACCEL_W_INKBOUNDS;
 
-        let no_overlap = self.bytes[cursor];
-        let constant_metrics = self.bytes[cursor + 1];
-        let terminal_font = self.bytes[cursor + 2];
-        let constant_width = self.bytes[cursor + 3];
-        let ink_inside = self.bytes[cursor + 4];
-        let ink_metrics = self.bytes[cursor + 5];
-        let draw_direction = self.bytes[cursor + 6];
-        let padding = self.bytes[cursor + 7];
+        let flags = self.field(cursor, 8, TABLE, "flags")?;
+        let no_overlap = flags[0];
+        let constant_metrics = flags[1];
+        let terminal_font = flags[2];
+        let constant_width = flags[3];
+        let ink_inside = flags[4];
+        let ink_metrics = flags[5];
+        let draw_direction = flags[6];
+        let padding = flags[7];
         cursor += 8;
-        let font_ascent = BigEndian::read_i32(&self.bytes[cursor..cursor + 4]);
+        let font_ascent = self.read_i32(endian, cursor, TABLE, "fontAscent")?;
         cursor += 4;
-        let font_descent = BigEndian::read_i32(&self.bytes[cursor..cursor + 4]);
+        let font_descent = self.read_i32(endian, cursor, TABLE, "fontDescent")?;
         cursor += 4;
-        let max_overlap = BigEndian::read_i32(&self.bytes[cursor..cursor + 4]);
+        let max_overlap = self.read_i32(endian, cursor, TABLE, "maxOverlap")?;
         cursor += 4;
 
-        let minbounds = self.read_uncompressed_metrics(&mut cursor);
-        let maxbounds = self.read_uncompressed_metrics(&mut cursor);
+        let minbounds = self.read_uncompressed_metrics(endian, &mut cursor, TABLE)?;
+        let maxbounds = self.read_uncompressed_metrics(endian, &mut cursor, TABLE)?;
         let (ink_minbounds, ink_maxbounds) = if has_inkbounds != 0 {
             (
-                self.read_uncompressed_metrics(&mut cursor),
-                self.read_uncompressed_metrics(&mut cursor),
+                self.read_uncompressed_metrics(endian, &mut cursor, TABLE)?,
+                self.read_uncompressed_metrics(endian, &mut cursor, TABLE)?,
             )
         } else {
             (minbounds, maxbounds)
         };
 
-        Accelerators {
+        Ok(Accelerators {
             no_overlap,
             constant_metrics,
             terminal_font,
@@ -268,121 +1012,276 @@ impl PcfFont<'_> {
             maxbounds,
             ink_minbounds,
             ink_maxbounds,
-        }
+        })
     }
 
-    fn read_uncompressed_metrics(&self, cursor: &mut usize) -> UncompressedMetrics {
-        let left_side_bearing = BigEndian::read_i16(&self.bytes[*cursor..(*cursor + 2)]);
-        let right_side_bearing = BigEndian::read_i16(&self.bytes[(*cursor + 2)..(*cursor + 4)]);
-        let character_width = BigEndian::read_i16(&self.bytes[(*cursor + 4)..(*cursor + 6)]);
-        let character_ascent = BigEndian::read_i16(&self.bytes[(*cursor + 6)..(*cursor + 8)]);
-        let character_descent = BigEndian::read_i16(&self.bytes[(*cursor + 8)..(*cursor + 10)]);
-        let character_attributes = BigEndian::read_u16(&self.bytes[(*cursor + 10)..(*cursor + 12)]);
-
+    fn read_uncompressed_metrics(
+        &self,
+        endian: Endian,
+        cursor: &mut usize,
+        table: &'static str,
+    ) -> Result<UncompressedMetrics, PcfError> {
+        let metrics = self.field(*cursor, 12, table, "metrics")?;
         *cursor += 12;
 
-        UncompressedMetrics {
-            left_side_bearing,
-            right_side_bearing,
-            character_width,
-            character_ascent,
-            character_descent,
-            character_attributes,
-        }
+        Ok(UncompressedMetrics {
+            left_side_bearing: endian.read_i16(&metrics[0..2]),
+            right_side_bearing: endian.read_i16(&metrics[2..4]),
+            character_width: endian.read_i16(&metrics[4..6]),
+            character_ascent: endian.read_i16(&metrics[6..8]),
+            character_descent: endian.read_i16(&metrics[8..10]),
+            character_attributes: endian.read_u16(&metrics[10..12]),
+        })
     }
 
-    fn read_compressed_metrics(&self, cursor: usize) -> CompressedMetrics {
-        let left_side_bearing: i16 = self.bytes[cursor].into();
-        let right_side_bearing: i16 = self.bytes[cursor + 1].into();
-        let character_width: i16 = self.bytes[cursor + 2].into();
-        let character_ascent: i16 = self.bytes[cursor + 3].into();
-        let character_descent: i16 = self.bytes[cursor + 4].into();
-
-        CompressedMetrics {
-            left_side_bearing: left_side_bearing - 0x80,
-            right_side_bearing: right_side_bearing - 0x80,
-            character_width: character_width - 0x80,
-            character_ascent: character_ascent - 0x80,
-            character_descent: character_descent - 0x80,
+    fn read_compressed_metrics(
+        &self,
+        cursor: usize,
+        table: &'static str,
+    ) -> Result<CompressedMetrics, PcfError> {
+        let metrics = self.field(cursor, 5, table, "metrics")?;
+        let field = |i: usize| i16::from(metrics[i]) - 0x80;
+
+        Ok(CompressedMetrics {
+            left_side_bearing: field(0),
+            right_side_bearing: field(1),
+            character_width: field(2),
+            character_ascent: field(3),
+            character_descent: field(4),
             character_attributes: 0,
-        }
+        })
     }
 
-    #[allow(clippy::bad_bit_mask)]
-    fn read_encoding(&self) -> Encoding {
-        let encoding = self.tables.get(&PCF_BDF_ENCODINGS);
-        let table = encoding.expect("No encoding table found");
+    fn read_encoding(&self) -> Result<Encoding, PcfError> {
+        const TABLE: &str = "encodings";
 
-        let mut cursor = table.offset;
-        let format = LittleEndian::read_i32(&self.bytes[cursor..cursor + 4]);
-        cursor += 4;
+        let table = self.table(PCF_BDF_ENCODINGS, TABLE)?;
+        let endian = self.endian(table, TABLE)?;
+        let cursor = table.offset + 4;
 
-        assert!(
-            format & PCF_DEFAULT_FORMAT == 0,
-            "Encoding is not default format"
-        );
+        let read = |i: usize, field| {
+            let offset = cursor + 2 * i;
+            usize::try_from(self.read_i16(endian, offset, TABLE, field)?).map_err(|_| {
+                PcfError::InvalidValue {
+                    table: TABLE,
+                    field,
+                    offset,
+                }
+            })
+        };
 
-        let min_byte2 = BigEndian::read_i16(&self.bytes[cursor..cursor + 2]);
-        cursor += 2;
-        let max_byte2 = BigEndian::read_i16(&self.bytes[cursor..cursor + 2]);
-        cursor += 2;
-        let min_byte1 = BigEndian::read_i16(&self.bytes[cursor..cursor + 2]);
-        cursor += 2;
-        let max_byte1 = BigEndian::read_i16(&self.bytes[cursor..cursor + 2]);
-        cursor += 2;
-        let default_char = BigEndian::read_i16(&self.bytes[cursor..cursor + 2]);
-
-        Encoding {
-            min_byte2: min_byte2.try_into().unwrap(),
-            max_byte2: max_byte2.try_into().unwrap(),
-            min_byte1: min_byte1.try_into().unwrap(),
-            max_byte1: max_byte1.try_into().unwrap(),
-            default_char: default_char.try_into().unwrap(),
+        // both bytes of a code point must be in 0..=255 and the ranges must not be empty
+        let range = |i: usize, field_min, field_max| {
+            let (min, max) = (read(i, field_min)?, read(i + 1, field_max)?);
+            match min <= max && max <= 0xFF {
+                true => Ok((min, max)),
+                false => Err(PcfError::InvalidValue {
+                    table: TABLE,
+                    field: field_max,
+                    offset: cursor + 2 * (i + 1),
+                }),
+            }
+        };
+        let (min_byte2, max_byte2) = range(0, "min_char_or_byte2", "max_char_or_byte2")?;
+        let (min_byte1, max_byte1) = range(2, "min_byte1", "max_byte1")?;
+
+        Ok(Encoding {
+            min_byte2,
+            max_byte2,
+            min_byte1,
+            max_byte1,
+            // 0xFFFF means the font has no default character
+            default_char: self
+                .read_u16(endian, cursor + 8, TABLE, "default_char")?
+                .into(),
+        })
+    }
+
+    /// Reads the header of the bitmaps table, noting a glyph padding that differs from the
+    /// declared one.
+    fn load_bitmap(&mut self) -> Result<(), PcfError> {
+        self.bitmap = self.read_bitmap()?;
+        if self.bitmap.glyph_pad != self.bitmap.declared_glyph_pad {
+            self.quirks.push(Quirk::GlyphPadding {
+                declared: self.bitmap.declared_glyph_pad,
+                actual: self.bitmap.glyph_pad,
+            });
         }
+
+        Ok(())
     }
 
-    #[allow(clippy::bad_bit_mask)]
-    fn read_bitmap(&self) -> Bitmap {
-        let bitmap = self.tables.get(&PCF_BITMAPS);
-        let table = bitmap.expect("No bitmap table found");
+    fn read_bitmap(&self) -> Result<Bitmap, PcfError> {
+        const TABLE: &str = "bitmaps";
+
+        let table = self.table(PCF_BITMAPS, TABLE)?;
 
         let mut cursor = table.offset;
-        let format = LittleEndian::read_i32(&self.bytes[cursor..cursor + 4]);
+        let format = self.read_i32(Endian::Little, cursor, TABLE, "format")?;
+        let endian = Endian::of(format);
         cursor += 4;
 
-        assert!(
-            format & PCF_DEFAULT_FORMAT == 0,
-            "Bitmap is not default format"
-        );
-
-        let glyph_count = BigEndian::read_i32(&self.bytes[cursor..cursor + 4]);
+        let glyph_count = self.read_usize(endian, cursor, TABLE, "glyph_count")?;
         cursor += 4;
-        cursor += (4 * glyph_count) as usize;
+        cursor = glyph_count.saturating_mul(4).saturating_add(cursor);
+
+        let sizes = self.field(cursor, 16, TABLE, "bitmapSizes")?;
+        let sizes: [i32; 4] = std::array::from_fn(|i| endian.read_i32(&sizes[4 * i..]));
+        let invalid_size = PcfError::InvalidValue {
+            table: TABLE,
+            field: "bitmapSizes",
+            offset: cursor,
+        };
+        cursor += 16;
+
+        let declared = (format & PCF_GLYPH_PAD_MASK) as usize;
+        let available = (table.offset + table.size.max(0) as usize).saturating_sub(cursor);
+        let pad = match usize::try_from(sizes[declared]) {
+            Ok(size) if size <= available => declared,
+            _ => sizes
+                .iter()
+                .position(|size| usize::try_from(*size) == Ok(available))
+                .unwrap_or(declared),
+        };
 
-        let one = BigEndian::read_i32(&self.bytes[cursor..cursor + 4]);
-        cursor += 4;
-        let two = BigEndian::read_i32(&self.bytes[cursor..cursor + 4]);
-        cursor += 4;
-        let three = BigEndian::read_i32(&self.bytes[cursor..cursor + 4]);
-        cursor += 4;
-        let four = BigEndian::read_i32(&self.bytes[cursor..cursor + 4]);
+        Ok(Bitmap {
+            glyph_count,
+            bitmap_sizes: sizes[pad].try_into().map_err(|_| invalid_size)?,
+            glyph_pad: 1 << pad,
+            declared_glyph_pad: 1 << declared,
+            endian,
+            // a scan unit can't be wider than the padded row
+            scan_unit: 1 << ((format & PCF_SCAN_UNIT_MASK) >> 4).min(pad as i32),
+            msb_first: format & PCF_BIT_MASK != 0,
+        })
+    }
+
+    /// Returns the default character of the encodings table, even if the font has no glyph
+    /// for it. Two byte encodings store the first byte in the high byte.
+    ///
+    /// [`Font::default_char`] returns the character only if the font has a glyph for it.
+    pub fn encoding_default_char(&self) -> u16 {
+        self.encoding.default_char as u16
+    }
+
+    /// Returns the index of the glyph of `c` in the metrics and bitmap tables, as stored in
+    /// the encodings table, or `None` if `c` isn't encoded.
+    ///
+    /// The indices are those of the parsed file, they don't change when
+    /// [`glyphs`](Self::glyphs) are added or removed.
+    pub fn glyph_index(&self, c: char) -> Option<u16> {
+        let index = self
+            .glyph_indices
+            .binary_search_by_key(&(c as i32), |(code_point, _)| *code_point)
+            .ok()?;
+
+        Some(self.glyph_indices[index].1)
+    }
+
+    /// Returns the code points of the encodings table with their glyph indices, sorted by
+    /// code point, e.g. to find holes in the encoding. See [`glyph_index`](Self::glyph_index).
+    pub fn glyph_indices(&self) -> impl Iterator<Item = (i32, u16)> + '_ {
+        self.glyph_indices.iter().copied()
+    }
+
+    /// Returns the accelerators the font metrics are based on, as read from the
+    /// [`accelerators_table`](Self::accelerators_table) or computed by
+    /// [`recompute_metrics`](Self::recompute_metrics).
+    pub fn accelerators(&self) -> &Accelerators {
+        &self.accelerators
+    }
+
+    /// Returns the table the accelerators were read from. The BDF accelerators are
+    /// preferred if the font has both tables.
+    pub fn accelerators_table(&self) -> AcceleratorsTable {
+        self.accelerators_table
+    }
 
-        let bitmap_sizes = [one, two, three, four][format as usize & 3];
+    /// Returns the accelerators as stored in `table`, or `None` if the font doesn't have the
+    /// table.
+    ///
+    /// This gives access to the table the parser didn't use, e.g. for the
+    /// [`max_overlap`](Accelerators::max_overlap) of all glyphs rather than only the
+    /// encoded ones.
+    pub fn table_accelerators(
+        &self,
+        table: AcceleratorsTable,
+    ) -> Result<Option<Accelerators>, PcfError> {
+        match self.tables.contains_key(&table.kind()) {
+            true => self.read_accelerators(table).map(Some),
+            false => Ok(None),
+        }
+    }
 
-        Bitmap {
-            glyph_count: glyph_count.try_into().unwrap(),
-            bitmap_sizes: bitmap_sizes.try_into().unwrap(),
+    /// Recomputes the font bounds, ascent, descent and bounding box from the current glyphs,
+    /// e.g. after removing or adding glyphs.
+    ///
+    /// The ink bounds are computed from the [ink bounds](Glyph::ink_bounds) of the glyphs,
+    /// or their bounding boxes if they have none. Nothing changes if the font has no glyphs.
+    pub fn recompute_metrics(&mut self) {
+        if let Some(accelerators) = self.accelerators_for(self.glyphs.values()) {
+            self.accelerators = accelerators;
+            self.bounding_box = self.get_bounding_box();
         }
     }
 
+    /// Returns the accelerators with the bounds of `glyphs`, or `None` if there are none.
+    fn accelerators_for<'g>(
+        &self,
+        glyphs: impl IntoIterator<Item = &'g Glyph>,
+    ) -> Option<Accelerators> {
+        let glyphs: Vec<_> = glyphs.into_iter().collect();
+        let metrics: Vec<_> = glyphs.iter().map(|glyph| (*glyph).into()).collect();
+        let ink_metrics: Vec<_> = glyphs
+            .iter()
+            .map(|glyph| {
+                UncompressedMetrics::with_bounds(
+                    glyph,
+                    glyph.ink_bounds.unwrap_or(glyph.bounding_box),
+                )
+            })
+            .collect();
+        let bounds = |metrics: &[UncompressedMetrics]| {
+            let first = metrics.first()?;
+            Some(metrics.iter().fold((*first, *first), |(min, max), m| {
+                (min.combine(m, std::cmp::min), max.combine(m, std::cmp::max))
+            }))
+        };
+
+        let first = metrics.first()?;
+        let (minbounds, maxbounds) = bounds(&metrics)?;
+        let (ink_minbounds, ink_maxbounds) = bounds(&ink_metrics)?;
+        let without_attributes = |m: &UncompressedMetrics| UncompressedMetrics {
+            character_attributes: 0,
+            ..*m
+        };
+
+        Some(Accelerators {
+            minbounds,
+            maxbounds,
+            ink_minbounds,
+            ink_maxbounds,
+            font_ascent: maxbounds.character_ascent.into(),
+            font_descent: maxbounds.character_descent.into(),
+            constant_width: u8::from(minbounds.character_width == maxbounds.character_width),
+            constant_metrics: u8::from(
+                metrics
+                    .iter()
+                    .all(|m| without_attributes(m) == without_attributes(first)),
+            ),
+            ..self.accelerators
+        })
+    }
+
     fn get_bounding_box(&self) -> BoundingBox {
         let minbounds = self.accelerators.ink_minbounds;
         let maxbounds = self.accelerators.ink_maxbounds;
-        let width = maxbounds.right_side_bearing - minbounds.left_side_bearing;
-        let height = maxbounds.character_ascent + maxbounds.character_descent;
+        let width =
+            i32::from(maxbounds.right_side_bearing) - i32::from(minbounds.left_side_bearing);
+        let height = i32::from(maxbounds.character_ascent) + i32::from(maxbounds.character_descent);
 
         BoundingBox {
-            size: Coord::new(width.into(), height.into()),
+            size: Coord::new(width, height),
             offset: Coord::new(
                 minbounds.left_side_bearing.into(),
                 (-maxbounds.character_descent).into(),
@@ -390,18 +1289,25 @@ impl PcfFont<'_> {
         }
     }
 
-    fn load_metadata(&self) -> Metadata {
-        let indices_offset = self.tables[&PCF_BDF_ENCODINGS].offset + 14;
-        let bitmap_offset_offsets = self.tables[&PCF_BITMAPS].offset + 8;
-        let first_bitmap_offset =
-            self.tables[&PCF_BITMAPS].offset + 4 * (6 + self.bitmap.glyph_count);
-        let metrics_compressed_raw = self.tables[&PCF_METRICS].format & PCF_COMPRESSED_METRICS;
+    fn load_metadata(&self) -> Result<Metadata, PcfError> {
+        let encodings = self.table(PCF_BDF_ENCODINGS, "encodings")?;
+        let bitmaps = self.table(PCF_BITMAPS, "bitmaps")?;
+        let metrics = self.table(PCF_METRICS, "metrics")?;
+
+        let indices_offset = encodings.offset + 14;
+        let bitmap_offset_offsets = bitmaps.offset + 8;
+        let first_bitmap_offset = self
+            .bitmap
+            .glyph_count
+            .saturating_add(6)
+            .saturating_mul(4)
+            .saturating_add(bitmaps.offset);
+        let metrics_compressed_raw = metrics.format & PCF_COMPRESSED_METRICS;
         let is_metrics_compressed = metrics_compressed_raw != 0;
-        let first_metric_offset =
-            self.tables[&PCF_METRICS].offset + (if is_metrics_compressed { 6 } else { 8 });
+        let first_metric_offset = metrics.offset + (if is_metrics_compressed { 6 } else { 8 });
         let metrics_size = if is_metrics_compressed { 5 } else { 12 };
 
-        Metadata {
+        Ok(Metadata {
             indices_offset,
             bitmap_offset_offsets,
             first_bitmap_offset,
@@ -409,109 +1315,256 @@ impl PcfFont<'_> {
             is_metrics_compressed,
             first_metric_offset,
             metrics_size,
+        })
+    }
+
+    /// Loads the glyphs, decoding their bitmaps if `bitmaps` is set.
+    fn load_glyphs(&mut self, bitmaps: bool) -> Result<(), PcfError> {
+        let indices = self.load_glyph_indices()?;
+        let all_metrics = self.load_all_metrics(&indices)?;
+        let names = self.read_glyph_names()?;
+        let scalable_widths = self.read_scalable_widths()?;
+        let ink_metrics = self.load_ink_metrics(&indices)?;
+        self.glyph_indices = indices
+            .iter()
+            .map(|(code_point, index)| (*code_point, *index as u16))
+            .collect();
+        self.glyph_indices.sort_unstable();
+        let mut glyphs = self.create_glyphs(&all_metrics);
+        for (code_point, glyph) in &mut glyphs {
+            glyph.ink_bounds = ink_metrics
+                .get(code_point)
+                .map(CompressedMetrics::bounding_box);
+            glyph.name = names.get(indices[code_point]).cloned();
+            glyph.scalable_width = scalable_widths.get(indices[code_point]).copied();
         }
+        self.glyphs = match bitmaps {
+            true => {
+                let available = self
+                    .bytes
+                    .len()
+                    .saturating_sub(self.metadata.first_bitmap_offset);
+                self.check_bitmap_sizes(&indices, &glyphs, available)?;
+                let bitmap_offsets = self.load_bitmap_offsets(&indices)?;
+                self.fill_glyph_bitmaps(glyphs, &bitmap_offsets)?
+            }
+            false => glyphs,
+        };
+
+        Ok(())
     }
 
-    fn load_glyphs(&mut self) {
-        let indices = self.load_glyph_indices();
+    /// Returns the glyph names by glyph index, or nothing if the font has no names table.
+    fn read_glyph_names(&self) -> Result<Vec<String>, PcfError> {
+        const TABLE: &str = "glyph names";
 
-        if !self.metadata.is_metrics_compressed {
-            panic!("uncompressed metrics unimplemented");
-        }
+        let Some(table) = self.tables.get(&PCF_GLYPH_NAMES) else {
+            return Ok(Vec::new());
+        };
 
-        let all_metrics = self.load_all_metrics(&indices);
-        let bitmap_offsets = self.load_bitmap_offsets(&indices);
-        let glyphs = self.create_glyphs(&all_metrics);
-        self.glyphs = self.fill_glyph_bitmaps(glyphs, &bitmap_offsets);
+        let endian = self.endian(table, TABLE)?;
+        let cursor = table.offset + 4;
+        let count = self.read_usize(endian, cursor, TABLE, "glyph_count")?;
+        let strings = count
+            .saturating_add(2)
+            .saturating_mul(4)
+            .saturating_add(cursor);
+
+        (0..count)
+            .map(|i| {
+                let offset = self.read_usize(endian, cursor + 4 * (i + 1), TABLE, "offsets")?;
+                let start = strings.saturating_add(offset);
+                let len = self
+                    .bytes
+                    .get(start..)
+                    .and_then(|bytes| bytes.iter().position(|b| *b == 0))
+                    .ok_or(PcfError::Truncated {
+                        table: TABLE,
+                        field: "string",
+                        offset: start,
+                    })?;
+
+                Ok::<_, PcfError>(
+                    String::from_utf8_lossy(&self.bytes[start..start + len]).into_owned(),
+                )
+            })
+            .collect()
     }
 
-    fn load_glyph_indices(&self) -> HashMap<i32, usize> {
-        (0..=(u16::MAX as i32))
-            .filter_map(|code_point| {
-                let enc1 = ((code_point >> 8) & 0xFF) as usize;
-                let enc2 = (code_point & 0xFF) as usize;
+    /// Returns the scalable widths by glyph index, or nothing if the font has no scalable
+    /// widths table.
+    fn read_scalable_widths(&self) -> Result<Vec<i32>, PcfError> {
+        const TABLE: &str = "scalable widths";
 
-                if enc1 < self.encoding.min_byte1 || enc1 > self.encoding.max_byte1 {
-                    return None;
-                }
+        let Some(table) = self.tables.get(&PCF_SWIDTHS) else {
+            return Ok(Vec::new());
+        };
 
-                if enc2 < self.encoding.min_byte2 || enc2 > self.encoding.max_byte2 {
-                    return None;
-                }
+        let endian = self.endian(table, TABLE)?;
+        let cursor = table.offset + 4;
+        let count = self.read_usize(endian, cursor, TABLE, "glyph_count")?;
+
+        (0..count)
+            .map(|i| self.read_i32(endian, cursor + 4 * (i + 1), TABLE, "swidths"))
+            .collect()
+    }
 
-                let encoding_idx = (enc1 - self.encoding.min_byte1)
-                    * (self.encoding.max_byte2 - self.encoding.min_byte2 + 1)
-                    + enc2
-                    - self.encoding.min_byte2;
+    fn load_glyph_indices(&self) -> Result<HashMap<i32, usize>, PcfError> {
+        let endian = self.endian(self.table(PCF_BDF_ENCODINGS, "encodings")?, "encodings")?;
+
+        let Encoding {
+            min_byte1,
+            max_byte1,
+            min_byte2,
+            max_byte2,
+            ..
+        } = self.encoding;
+        let columns = max_byte2 - min_byte2 + 1;
+
+        // single byte fonts have min_byte1 = max_byte1 = 0, two byte fonts index the rows by
+        // the high byte of the code point
+        (min_byte1..=max_byte1)
+            .flat_map(|byte1| (min_byte2..=max_byte2).map(move |byte2| (byte1, byte2)))
+            .filter_map(|(byte1, byte2)| {
+                let encoding_idx = (byte1 - min_byte1) * columns + byte2 - min_byte2;
+                let code_point = (byte1 << 8 | byte2) as i32;
 
                 let cursor: usize = self.metadata.indices_offset + 2 * encoding_idx;
-                let glyph_idx: usize = BigEndian::read_u16(&self.bytes[cursor..cursor + 2]).into();
-                if glyph_idx != 65535 {
-                    Some((code_point, glyph_idx))
-                } else {
-                    None
+                match self.read_u16(endian, cursor, "encodings", "glyphindeces") {
+                    Ok(65535) => None,
+                    Ok(glyph_idx) => Some(Ok((code_point, glyph_idx.into()))),
+                    Err(error) => Some(Err(error)),
                 }
             })
             .collect()
     }
 
-    fn load_all_metrics(&self, indices: &HashMap<i32, usize>) -> HashMap<i32, CompressedMetrics> {
+    fn load_all_metrics(
+        &self,
+        indices: &HashMap<i32, usize>,
+    ) -> Result<HashMap<i32, CompressedMetrics>, PcfError> {
+        let table = self.table(PCF_METRICS, "metrics")?;
+        let first = self.metadata.first_metric_offset;
+        let compressed = self.metadata.is_metrics_compressed;
+
+        self.load_metrics(table, "metrics", first, compressed, indices)
+    }
+
+    /// Returns the ink metrics by code point, or nothing if the font has no ink metrics
+    /// table.
+    fn load_ink_metrics(
+        &self,
+        indices: &HashMap<i32, usize>,
+    ) -> Result<HashMap<i32, CompressedMetrics>, PcfError> {
+        let Some(table) = self.tables.get(&PCF_INK_METRICS) else {
+            return Ok(HashMap::new());
+        };
+        // the ink metrics table has the same layout as the metrics table
+        let compressed = table.format & PCF_COMPRESSED_METRICS != 0;
+        let first = table.offset + if compressed { 6 } else { 8 };
+
+        self.load_metrics(table, "ink metrics", first, compressed, indices)
+    }
+
+    fn load_metrics(
+        &self,
+        table: &Table,
+        name: &'static str,
+        first: usize,
+        compressed: bool,
+        indices: &HashMap<i32, usize>,
+    ) -> Result<HashMap<i32, CompressedMetrics>, PcfError> {
+        let endian = self.endian(table, name)?;
+        let size = if compressed { 5 } else { 12 };
+
         indices
             .iter()
             .map(|(code_point, index)| {
-                let cursor: usize =
-                    self.metadata.first_metric_offset + self.metadata.metrics_size * index;
-                let metrics = self.read_compressed_metrics(cursor);
+                let mut cursor: usize = first + size * index;
+                let offset = cursor;
+                let metrics = match compressed {
+                    true => self.read_compressed_metrics(cursor, name)?,
+                    false => self
+                        .read_uncompressed_metrics(endian, &mut cursor, name)?
+                        .into(),
+                };
+                if metrics.width() < 0 || metrics.height() < 0 {
+                    return Err(PcfError::InvalidValue {
+                        table: name,
+                        field: "metrics",
+                        offset,
+                    });
+                }
 
-                (*code_point, metrics)
+                Ok((*code_point, metrics))
             })
             .collect()
     }
 
-    fn load_bitmap_offsets(&self, indices: &HashMap<i32, usize>) -> HashMap<i32, usize> {
+    fn load_bitmap_offsets(
+        &self,
+        indices: &HashMap<i32, usize>,
+    ) -> Result<HashMap<i32, usize>, PcfError> {
         indices
             .iter()
             .map(|(code_point, index)| {
                 let cursor: usize = self.metadata.bitmap_offset_offsets + 4 * index;
-                let bitmap_offset: usize = BigEndian::read_u32(&self.bytes[cursor..cursor + 4])
-                    .try_into()
-                    .unwrap();
+                let bitmap_offset =
+                    self.read_usize(self.bitmap.endian, cursor, "bitmaps", "offsets")?;
 
-                (*code_point, bitmap_offset)
+                Ok((*code_point, bitmap_offset))
             })
             .collect()
     }
 
+    /// Checks that the glyph bitmaps fit in the bitmap data, of which `available` bytes are
+    /// in the file, so that a malformed font can't make the glyphs allocate more memory than
+    /// the font takes up.
+    fn check_bitmap_sizes(
+        &self,
+        indices: &HashMap<i32, usize>,
+        glyphs: &HashMap<i32, Glyph>,
+        available: usize,
+    ) -> Result<(), PcfError> {
+        // code points sharing a glyph index share its bitmap data
+        let sizes: HashMap<usize, usize> = glyphs
+            .iter()
+            .map(|(code_point, glyph)| (indices[code_point], self.bitmap_len(glyph)))
+            .collect();
+        let total = sizes
+            .values()
+            .fold(0usize, |total, size| total.saturating_add(*size));
+
+        match total <= self.bitmap.bitmap_sizes.min(available) {
+            true => Ok(()),
+            false => Err(PcfError::InvalidValue {
+                table: "bitmaps",
+                field: "bitmapSizes",
+                offset: self.metadata.first_bitmap_offset.saturating_sub(16),
+            }),
+        }
+    }
+
     fn create_glyphs(&self, all_metrics: &HashMap<i32, CompressedMetrics>) -> HashMap<i32, Glyph> {
         all_metrics
             .iter()
             .map(|(code_point, metrics)| {
-                let width: i32 = (metrics.right_side_bearing - metrics.left_side_bearing)
-                    .try_into()
-                    .unwrap();
-                let height: i32 = (metrics.character_ascent + metrics.character_descent)
-                    .try_into()
-                    .unwrap();
-                let len = (width * height).try_into().expect("width * height failed");
-                let bitmap = vec![0u8; len];
                 let encoding = u32::try_from(*code_point)
                     .ok()
                     .and_then(std::char::from_u32);
 
                 let glyph = Glyph {
-                    bitmap,
+                    bitmap: Vec::new(),
                     code_point: *code_point,
                     encoding,
-                    bounding_box: BoundingBox {
-                        size: Coord::new(width, height),
-                        offset: Coord::new(
-                            metrics.left_side_bearing as i32,
-                            -(metrics.character_descent as i32),
-                        ),
-                    },
+                    bounding_box: metrics.bounding_box(),
+                    ink_bounds: None,
                     shift_x: metrics.character_width as i32,
                     shift_y: 0,
                     tile_index: 0,
+                    attributes: Attributes(metrics.character_attributes as u16),
+                    name: None,
+                    scalable_width: None,
                 };
 
                 (*code_point, glyph)
@@ -523,36 +1576,61 @@ impl PcfFont<'_> {
         &self,
         glyphs: HashMap<i32, Glyph>,
         bitmap_offsets: &HashMap<i32, usize>,
-    ) -> HashMap<i32, Glyph> {
+    ) -> Result<HashMap<i32, Glyph>, PcfError> {
         glyphs
             .into_iter()
             .map(|(code_point, mut glyph)| {
-                let offset = self.metadata.first_bitmap_offset + bitmap_offsets[&code_point];
-                let width = glyph.bounding_box.size.x as usize;
-                let height = glyph.bounding_box.size.y as usize;
-                let words_per_row = (width + 31) / 32;
-                let bytes_per_row = 4 * words_per_row;
-                for y in 0..height {
-                    let start = offset + bytes_per_row * y;
-                    let end = start + bytes_per_row;
-                    let row = &self.bytes[start..end];
-                    for x in 0..width {
-                        let idx = x / 8;
-                        let byte = row[idx];
-                        let mask = 128 >> (x % 8);
-                        let masked = byte & mask;
-                        let on = masked != 0;
-
-                        if on {
-                            glyph.bitmap[y * width + x] = 1;
-                        }
-                    }
-                }
+                let offset =
+                    bitmap_offsets[&code_point].saturating_add(self.metadata.first_bitmap_offset);
+                // the bitmap is only allocated once the data is known to be there
+                let data = self.field(offset, self.bitmap_len(&glyph), "bitmaps", "bitmap_data")?;
+                self.decode_bitmap(&mut glyph, data);
 
-                (code_point, glyph)
+                Ok((code_point, glyph))
             })
             .collect()
     }
+
+    /// Returns the number of bytes of the padded rows of a glyph in the bitmap data.
+    fn bitmap_len(&self, glyph: &Glyph) -> usize {
+        let width = glyph.bounding_box.size.x as usize;
+        let height = glyph.bounding_box.size.y as usize;
+        let pad = self.bitmap.glyph_pad;
+
+        (pad * width.div_ceil(8 * pad)).saturating_mul(height)
+    }
+
+    /// Decodes the bitmap of a glyph from its padded rows in the bitmap data.
+    fn decode_bitmap(&self, glyph: &mut Glyph, data: &[u8]) {
+        let width = glyph.bounding_box.size.x as usize;
+        let height = glyph.bounding_box.size.y as usize;
+        let pad = self.bitmap.glyph_pad;
+        let unit = self.bitmap.scan_unit;
+        let bytes_per_row = pad * width.div_ceil(8 * pad);
+        glyph.bitmap = glyph.empty_bitmap();
+        for y in 0..height {
+            let row = &data[bytes_per_row * y..][..bytes_per_row];
+            for x in 0..width {
+                let mut idx = x / 8;
+                if (self.bitmap.endian == Endian::Big) != self.bitmap.msb_first {
+                    // bytes are reversed within each scan unit when the byte order
+                    // differs from the bit order, like in FreeType and libXfont
+                    idx = idx - idx % unit + unit - 1 - idx % unit;
+                }
+                let byte = row[idx];
+                let mask = match self.bitmap.msb_first {
+                    true => 128 >> (x % 8),
+                    false => 1 << (x % 8),
+                };
+                let masked = byte & mask;
+                let on = masked != 0;
+
+                if on {
+                    glyph.set_pixel(x, y, true);
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -567,14 +1645,14 @@ mod tests {
     fn it_parses_header() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        assert_eq!(1885562369, pcf.header());
+        assert_eq!(Ok(1885562369), pcf.header());
     }
 
     #[test]
     fn it_parses_table_count() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        assert_eq!(8, pcf.table_count());
+        assert_eq!(Ok(8), pcf.table_count());
     }
 
     #[test]
@@ -710,11 +1788,122 @@ mod tests {
         assert_eq!(encoding, pcf.encoding);
     }
 
+    #[test]
+    fn it_has_a_default_char_if_the_font_has_a_glyph_for_it() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let mut font = PcfFont::new(&font[..]);
+        assert_eq!(font.encoding_default_char(), 1);
+        assert_eq!(font.default_char(), None);
+
+        font.encoding.default_char = UPPERCASE_A as usize;
+        let written = PcfWriter::new(&font).to_bytes();
+        assert_eq!(PcfFont::parse(&written).unwrap().default_char(), Some('A'));
+    }
+
+    #[test]
+    fn it_has_font_wide_metrics() {
+        let font = include_bytes!("../../eg-pcf/examples/6x10.pcf");
+        let font = PcfFont::new(&font[..]);
+
+        assert_eq!((font.ascent(), font.descent()), (8, 2));
+        assert_eq!(font.cap_height(), Some(7));
+        assert_eq!(font.x_height(), Some(5));
+        assert!(font.is_monospaced());
+        assert!(
+            !PcfFont::new(include_bytes!("../../assets/OpenSans-Regular-12.pcf")).is_monospaced()
+        );
+    }
+
+    #[test]
+    fn it_exposes_both_accelerators_tables() {
+        let pcf = PcfFont::new(include_bytes!("../../assets/OpenSans-Regular-12.pcf"));
+        assert_eq!(pcf.accelerators_table(), AcceleratorsTable::Bdf);
+
+        let bdf = pcf.table_accelerators(AcceleratorsTable::Bdf).unwrap();
+        assert_eq!(bdf.as_ref(), Some(pcf.accelerators()));
+        let all = pcf
+            .table_accelerators(AcceleratorsTable::Pcf)
+            .unwrap()
+            .unwrap();
+        assert_eq!(all.max_overlap, 1);
+
+        // hide the BDF accelerators as an unknown table
+        let mut font = include_bytes!("../../assets/OpenSans-Regular-12.pcf").to_vec();
+        for entry in font[8..].chunks_exact_mut(16).take(pcf.tables.len()) {
+            if LittleEndian::read_i32(entry) == PCF_BDF_ACCELERATORS as i32 {
+                LittleEndian::write_i32(entry, 1 << 9);
+            }
+        }
+        let pcf = PcfFont::new(&font);
+        assert_eq!(pcf.accelerators_table(), AcceleratorsTable::Pcf);
+        assert_eq!(pcf.accelerators(), &all);
+        assert_eq!(pcf.table_accelerators(AcceleratorsTable::Bdf), Ok(None));
+    }
+
+    #[test]
+    fn it_parses_ink_metrics() {
+        let font = include_bytes!("../../eg-pcf/examples/6x10.pcf");
+        let pcf = PcfFont::new(&font[..]);
+
+        // the bitmaps of terminal fonts cover the whole character cell
+        let glyph = &pcf.glyphs[&UPPERCASE_A];
+        assert_eq!(glyph.bounding_box.size, Coord::new(6, 10));
+        assert_eq!(
+            glyph.ink_bounds,
+            Some(BoundingBox {
+                size: Coord::new(5, 7),
+                offset: Coord::new(0, 0),
+            })
+        );
+        assert!(
+            PcfFont::new(include_bytes!("../../assets/OpenSans-Regular-12.pcf"))
+                .glyphs
+                .values()
+                .all(|glyph| glyph.ink_bounds.is_none())
+        );
+
+        let written = PcfWriter::new(&pcf).to_bytes();
+        let parsed = PcfFont::parse(&written).unwrap();
+        assert_eq!(parsed.glyphs, pcf.glyphs);
+        assert_eq!(parsed.accelerators, pcf.accelerators);
+    }
+
+    #[test]
+    fn it_parses_two_byte_encodings() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let mut font = PcfFont::new(&font[..]);
+
+        // CJK fonts spread their glyphs over many rows of the encoding table
+        let glyph = font.glyphs[&UPPERCASE_A].clone();
+        for code_point in [0x3042, 0x4E00, 0x4E01, 0x9FA5, 0xFF21] {
+            let glyph = Glyph {
+                code_point,
+                encoding: char::from_u32(code_point as u32),
+                name: Some(format!("uni{code_point:04X}")),
+                ..glyph.clone()
+            };
+            font.glyphs.insert(code_point, glyph);
+        }
+
+        let written = PcfWriter::new(&font).to_bytes();
+        let pcf = PcfFont::parse(&written).unwrap();
+
+        assert_eq!((pcf.encoding.min_byte1, pcf.encoding.max_byte1), (0, 0xFF));
+        assert_eq!((pcf.encoding.min_byte2, pcf.encoding.max_byte2), (0, 0xA5));
+        assert_eq!(pcf.glyphs, font.glyphs);
+        assert_eq!(pcf.glyphs[&0x9FA5].encoding, Some('\u{9FA5}'));
+    }
+
     #[test]
     fn it_parses_bitmap_correctly() {
         let bitmap = Bitmap {
             glyph_count: 97,
             bitmap_sizes: 2988,
+            glyph_pad: 4,
+            declared_glyph_pad: 4,
+            endian: Endian::Big,
+            scan_unit: 1,
+            msb_first: true,
         };
 
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
@@ -734,6 +1923,185 @@ mod tests {
         assert_eq!(bounding_box, pcf.bounding_box);
     }
 
+    #[test]
+    fn it_parses_properties() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(&font[..]);
+
+        assert_eq!(pcf.string_property("FAMILY_NAME"), Some("Open Sans"));
+        assert_eq!(pcf.integer_property("FAMILY_NAME"), None);
+        assert_eq!(pcf.point_size(), Some(120));
+        assert_eq!(pcf.resolution(), Some((75, 75)));
+    }
+
+    #[test]
+    fn test_attribute_bits() {
+        let attributes = Attributes(0b1010);
+
+        assert!(attributes.bit(1) && attributes.bit(3));
+        assert!(!attributes.bit(0) && !attributes.bit(16));
+        assert!(attributes.contains(0b1000) && !attributes.contains(0b1001));
+        assert!(Attributes::default().is_empty());
+    }
+
+    #[test]
+    fn it_works_around_wrong_glyph_padding() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let mut bytes = font.to_vec();
+        let offset = PcfFont::new(font).tables[&PCF_BITMAPS].offset;
+        // declare 8 byte padding for bitmaps padded to 4 bytes
+        bytes[offset] |= 3;
+
+        let pcf = PcfFont::new(&bytes);
+        let quirk = Quirk::GlyphPadding {
+            declared: 8,
+            actual: 4,
+        };
+        assert!(pcf.quirks.contains(&quirk));
+        assert_eq!(pcf.glyphs, PcfFont::new(font).glyphs);
+        assert!(!PcfFont::new(font).quirks.contains(&quirk));
+    }
+
+    #[test]
+    fn it_rejects_malformed_fonts() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+
+        assert_eq!(
+            PcfFont::parse(b"not a font").err(),
+            Some(PcfError::BadMagic)
+        );
+        assert_eq!(
+            PcfFont::parse(b"\x01fcp\0\0\0\0").err(),
+            Some(PcfError::MissingTable {
+                table: "accelerators"
+            })
+        );
+        assert!(matches!(
+            PcfFont::parse(&font[..100]),
+            Err(PcfError::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn it_rejects_overlapping_tables() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let mut bytes = font.to_vec();
+
+        // point the second table of contents entry at the first table
+        bytes.copy_within(20..24, 36);
+        assert!(matches!(
+            PcfFont::parse(&bytes),
+            Err(PcfError::InvalidValue {
+                table: "table of contents",
+                field: "offset",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn it_never_panics_on_malformed_fonts() {
+        let fonts: [&[u8]; 3] = [
+            include_bytes!("../../assets/OpenSans-Regular-12.pcf"),
+            include_bytes!("../../assets/OpenSans-Regular-12-lsb.pcf"),
+            include_bytes!("../../assets/OpenSans-Regular-12-uncompressed.pcf"),
+        ];
+        // xorshift, so failures are reproducible
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut random = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+
+        for i in 0..3000 {
+            let mut bytes = fonts[i % fonts.len()].to_vec();
+            for _ in 0..1 + random() % 8 {
+                // most fields that can cause trouble are in the table of contents and the
+                // table headers at the start of the file
+                let offset = random() % if i % 2 == 0 { 256 } else { bytes.len() };
+                bytes[offset] = [0x00, 0x7F, 0x80, 0xFF, random() as u8][random() % 5];
+            }
+            if i % 10 == 0 {
+                bytes.truncate(random() % bytes.len());
+            }
+
+            let _ = PcfFont::parse(&bytes);
+        }
+    }
+
+    #[test]
+    fn it_reports_truncated_tables() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(&font[..]);
+
+        // the accelerators table of the fixture ends 52 bytes past the end of the file
+        assert_eq!(
+            pcf.quirks,
+            [Quirk::TruncatedTable {
+                kind: PCF_BDF_ACCELERATORS as u32
+            }]
+        );
+    }
+
+    #[test]
+    fn it_recomputes_metrics() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let mut pcf = PcfFont::new(&font[..]);
+        let original = pcf.bounding_box;
+
+        pcf.recompute_metrics();
+        assert_eq!(pcf.bounding_box, original);
+
+        pcf.glyphs
+            .retain(|_, glyph| glyph.encoding.is_some_and(|c| c.is_ascii_digit()));
+        pcf.recompute_metrics();
+
+        let digit = &pcf.glyphs[&('0' as i32)];
+        assert!(pcf.bounding_box.size.y < original.size.y);
+        assert_eq!(pcf.bounding_box.offset.y, 0);
+        assert_eq!(pcf.accelerators.constant_width, 1);
+        assert_eq!(
+            pcf.accelerators.maxbounds.character_width as i32,
+            digit.shift_x
+        );
+    }
+
+    #[test]
+    fn it_keeps_unknown_tables() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let count = LittleEndian::read_i32(&font[4..8]) as usize;
+        let toc = 8 + 16 * count;
+
+        // insert a table of contents entry for a table appended to the file
+        let mut bytes = font[..8].to_vec();
+        LittleEndian::write_i32(&mut bytes[4..8], count as i32 + 1);
+        for entry in font[8..toc].chunks(16) {
+            let offset = LittleEndian::read_i32(&entry[12..16]) + 16;
+            bytes.extend_from_slice(&entry[..12]);
+            bytes.extend_from_slice(&offset.to_le_bytes());
+        }
+        let offset = font.len() as i32 + 16;
+        for field in [1 << 12, 0, 6, offset] {
+            bytes.extend_from_slice(&i32::to_le_bytes(field));
+        }
+        bytes.extend_from_slice(&font[toc..]);
+        bytes.extend_from_slice(b"extra!");
+
+        let pcf = PcfFont::new(&bytes);
+        assert_eq!(
+            pcf.unknown_tables,
+            [RawTable {
+                kind: 1 << 12,
+                format: 0,
+                data: b"extra!",
+            }]
+        );
+        assert_eq!(pcf.glyphs.len(), PcfFont::new(font).glyphs.len());
+        assert!(PcfFont::new(font).unknown_tables.is_empty());
+    }
+
     #[test]
     fn it_loads_metadata() {
         let metadata = Metadata {
@@ -752,32 +2120,187 @@ mod tests {
         assert_eq!(metadata, pcf.metadata);
     }
 
+    #[test]
+    fn it_loads_uncompressed_metrics() {
+        // the same font as the other fixture, with the metrics table stored uncompressed
+        let font = include_bytes!("../../assets/OpenSans-Regular-12-uncompressed.pcf");
+        let pcf = PcfFont::new(&font[..]);
+
+        assert!(!pcf.metadata.is_metrics_compressed);
+        assert_eq!(pcf.metadata.metrics_size, 12);
+        assert_eq!(pcf.metadata.first_metric_offset, 1508);
+
+        let compressed = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        assert_eq!(pcf.glyphs, PcfFont::new(&compressed[..]).glyphs);
+    }
+
+    #[test]
+    fn it_parses_little_endian_tables() {
+        // the same font as the other fixture, with all tables in LSByte and LSBit first order
+        // and bitmaps in 4 byte scan units
+        let font = include_bytes!("../../assets/OpenSans-Regular-12-lsb.pcf");
+        let pcf = PcfFont::new(&font[..]);
+
+        assert_eq!(pcf.bitmap.endian, Endian::Little);
+        assert_eq!(pcf.bitmap.scan_unit, 4);
+        assert!(!pcf.bitmap.msb_first);
+
+        let msb = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let msb = PcfFont::new(&msb[..]);
+        assert_eq!(pcf.properties, msb.properties);
+        assert_eq!(pcf.accelerators, msb.accelerators);
+        assert_eq!(pcf.encoding, msb.encoding);
+        assert_eq!(pcf.glyphs, msb.glyphs);
+    }
+
+    #[test]
+    fn it_decodes_bitmap_padding_and_bit_order_variants() {
+        let msb = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let msb = PcfFont::new(&msb[..]);
+
+        // (font, glyph_pad, scan_unit, msb_first) with the bitmaps re-encoded, like bdftopcf
+        // does with -p, -u, -m/-l and -M/-L
+        let variants: [(&[u8], usize, usize, bool); 3] = [
+            (
+                include_bytes!("../../assets/OpenSans-Regular-12-p1.pcf"),
+                1,
+                1,
+                true,
+            ),
+            (
+                include_bytes!("../../assets/OpenSans-Regular-12-p2-u2-lsbit.pcf"),
+                2,
+                2,
+                false,
+            ),
+            (
+                include_bytes!("../../assets/OpenSans-Regular-12-p8-u4-lsbyte.pcf"),
+                8,
+                4,
+                true,
+            ),
+        ];
+        for (font, glyph_pad, scan_unit, msb_first) in variants {
+            let pcf = PcfFont::new(font);
+
+            assert_eq!(pcf.bitmap.glyph_pad, glyph_pad);
+            assert_eq!(pcf.bitmap.scan_unit, scan_unit);
+            assert_eq!(pcf.bitmap.msb_first, msb_first);
+            assert_eq!(pcf.glyphs, msb.glyphs);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_round_trips_through_serde() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(&font[..]);
+
+        let json = serde_json::to_string(&pcf).unwrap();
+        let cached: PcfFont = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(cached.glyphs, pcf.glyphs);
+        assert_eq!(cached.properties, pcf.properties);
+        assert_eq!(cached.bounding_box, pcf.bounding_box);
+        assert_eq!(cached.accelerators(), pcf.accelerators());
+        assert_eq!(cached.is_monospaced(), pcf.is_monospaced());
+        assert_eq!(cached.table_accelerators(AcceleratorsTable::Pcf), Ok(None));
+    }
+
+    #[test]
+    fn it_parses_metrics_without_bitmaps() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(&font[..]);
+        let metrics = PcfFont::parse_metrics_only(&font[..]).unwrap();
+
+        assert_eq!(metrics.glyphs.len(), pcf.glyphs.len());
+        for (code_point, glyph) in metrics.glyphs {
+            assert!(glyph.bitmap.is_empty());
+            let parsed = Glyph {
+                bitmap: Vec::new(),
+                ..pcf.glyphs[&code_point].clone()
+            };
+            assert_eq!(glyph, parsed);
+        }
+        assert_eq!(metrics.bounding_box, pcf.bounding_box);
+        assert_eq!(metrics.accelerators, pcf.accelerators);
+    }
+
+    #[test]
+    fn it_parses_glyph_names() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(&font[..]);
+
+        assert_eq!(pcf.glyphs[&('$' as i32)].name.as_deref(), Some("dollar"));
+        assert_eq!(pcf.glyph_by_name("space").map(|g| g.code_point), Some(32));
+        assert!(pcf.glyph_by_name("Aacute").is_none());
+    }
+
+    #[test]
+    fn it_lists_encoded_characters() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(&font[..]);
+        let chars: Vec<char> = pcf.chars().collect();
+
+        assert_eq!(chars.len(), pcf.glyphs.len());
+        assert!(chars.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(chars.first(), Some(&'\0'));
+        assert!(pcf.contains('A'));
+        assert!(!pcf.contains('\u{2603}'));
+    }
+
+    #[test]
+    fn it_computes_scalable_advances() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(&font[..]);
+        let glyph = &pcf.glyphs[&UPPERCASE_A];
+
+        let (resolution, _) = pcf.resolution().unwrap();
+        let expected = 0.632 * pcf.point_size().unwrap() as f32 / 720.0 * resolution as f32;
+        assert!((pcf.scalable_advance(glyph).unwrap() - expected).abs() < 1e-4);
+        assert_eq!(expected.round() as i32, glyph.shift_x);
+    }
+
+    #[test]
+    fn it_exposes_glyph_indices() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(&font[..]);
+
+        assert_eq!(pcf.glyph_index('A'), Some(35));
+        assert_eq!(pcf.glyph_index('\u{1}'), None);
+
+        let indices: Vec<_> = pcf.glyph_indices().collect();
+        assert_eq!(indices.len(), pcf.glyphs.len());
+        assert!(indices.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(indices.contains(&(UPPERCASE_A, 35)));
+    }
+
     #[test]
     fn it_loads_indices_for_uppercase_a() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        assert_eq!(35, pcf.load_glyph_indices()[&UPPERCASE_A]);
+        assert_eq!(35, pcf.load_glyph_indices().unwrap()[&UPPERCASE_A]);
     }
 
     #[test]
     fn it_loads_indices_for_uppercase_j() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        assert_eq!(44, pcf.load_glyph_indices()[&UPPERCASE_J]);
+        assert_eq!(44, pcf.load_glyph_indices().unwrap()[&UPPERCASE_J]);
     }
 
     #[test]
     fn it_loads_indices_for_uppercase_w() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        assert_eq!(57, pcf.load_glyph_indices()[&UPPERCASE_W]);
+        assert_eq!(57, pcf.load_glyph_indices().unwrap()[&UPPERCASE_W]);
     }
 
     #[test]
     fn it_loads_all_metrics_for_uppercase_a() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        let indices = pcf.load_glyph_indices();
+        let indices = pcf.load_glyph_indices().unwrap();
         let compressed_metrics = CompressedMetrics {
             left_side_bearing: 0,
             right_side_bearing: 7,
@@ -789,7 +2312,7 @@ mod tests {
 
         assert_eq!(
             compressed_metrics,
-            pcf.load_all_metrics(&indices)[&UPPERCASE_A]
+            pcf.load_all_metrics(&indices).unwrap()[&UPPERCASE_A]
         );
     }
 
@@ -797,7 +2320,7 @@ mod tests {
     fn it_loads_all_metrics_for_uppercase_j() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        let indices = pcf.load_glyph_indices();
+        let indices = pcf.load_glyph_indices().unwrap();
         let compressed_metrics = CompressedMetrics {
             left_side_bearing: -1,
             right_side_bearing: 2,
@@ -809,7 +2332,7 @@ mod tests {
 
         assert_eq!(
             compressed_metrics,
-            pcf.load_all_metrics(&indices)[&UPPERCASE_J]
+            pcf.load_all_metrics(&indices).unwrap()[&UPPERCASE_J]
         );
     }
 
@@ -817,7 +2340,7 @@ mod tests {
     fn it_loads_all_metrics_for_uppercase_w() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        let indices = pcf.load_glyph_indices();
+        let indices = pcf.load_glyph_indices().unwrap();
         let compressed_metrics = CompressedMetrics {
             left_side_bearing: 0,
             right_side_bearing: 11,
@@ -829,7 +2352,7 @@ mod tests {
 
         assert_eq!(
             compressed_metrics,
-            pcf.load_all_metrics(&indices)[&UPPERCASE_W]
+            pcf.load_all_metrics(&indices).unwrap()[&UPPERCASE_W]
         );
     }
 
@@ -837,27 +2360,36 @@ mod tests {
     fn it_loads_bitmap_offsets_for_uppercase_a() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        let indices = pcf.load_glyph_indices();
+        let indices = pcf.load_glyph_indices().unwrap();
 
-        assert_eq!(960, pcf.load_bitmap_offsets(&indices)[&UPPERCASE_A]);
+        assert_eq!(
+            960,
+            pcf.load_bitmap_offsets(&indices).unwrap()[&UPPERCASE_A]
+        );
     }
 
     #[test]
     fn it_loads_bitmap_offsets_for_uppercase_j() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        let indices = pcf.load_glyph_indices();
+        let indices = pcf.load_glyph_indices().unwrap();
 
-        assert_eq!(1284, pcf.load_bitmap_offsets(&indices)[&UPPERCASE_J]);
+        assert_eq!(
+            1284,
+            pcf.load_bitmap_offsets(&indices).unwrap()[&UPPERCASE_J]
+        );
     }
 
     #[test]
     fn it_loads_bitmap_offsets_for_uppercase_w() {
         let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
         let pcf = PcfFont::new(&font[..]);
-        let indices = pcf.load_glyph_indices();
+        let indices = pcf.load_glyph_indices().unwrap();
 
-        assert_eq!(1768, pcf.load_bitmap_offsets(&indices)[&UPPERCASE_W]);
+        assert_eq!(
+            1768,
+            pcf.load_bitmap_offsets(&indices).unwrap()[&UPPERCASE_W]
+        );
     }
 
     #[test]
@@ -869,23 +2401,27 @@ mod tests {
             code_point: UPPERCASE_A,
             encoding: Some('A'),
             bitmap: vec![
-                0, 0, 0, 1, 0, 0, 0,
-                0, 0, 0, 1, 1, 0, 0,
-                0, 0, 1, 0, 1, 0, 0,
-                0, 0, 1, 0, 0, 1, 0,
-                0, 0, 1, 0, 0, 1, 0,
-                0, 1, 1, 1, 1, 1, 0,
-                0, 1, 0, 0, 0, 0, 1,
-                0, 1, 0, 0, 0, 0, 1,
-                1, 0, 0, 0, 0, 0, 1,
+                0b0001_0000,
+                0b0001_1000,
+                0b0010_1000,
+                0b0010_0100,
+                0b0010_0100,
+                0b0111_1100,
+                0b0100_0010,
+                0b0100_0010,
+                0b1000_0010,
             ],
             bounding_box: BoundingBox {
                 size: Coord::new(7, 9),
                 offset: Coord::new(0, 0),
             },
+            ink_bounds: None,
             shift_x: 8,
             shift_y: 0,
             tile_index: 0,
+            attributes: Attributes::default(),
+            name: Some("A".to_string()),
+            scalable_width: Some(632),
         };
         let glyph = &pcf.glyphs[&UPPERCASE_A];
         assert_eq!(expected, *glyph);
@@ -900,25 +2436,29 @@ mod tests {
             code_point: UPPERCASE_J,
             encoding: Some('J'),
             bitmap: vec![
-                0, 0, 1,
-                0, 0, 1,
-                0, 0, 1,
-                0, 0, 1,
-                0, 0, 1,
-                0, 0, 1,
-                0, 0, 1,
-                0, 0, 1,
-                0, 0, 1,
-                0, 0, 1,
-                1, 1, 0,
+                0b0010_0000,
+                0b0010_0000,
+                0b0010_0000,
+                0b0010_0000,
+                0b0010_0000,
+                0b0010_0000,
+                0b0010_0000,
+                0b0010_0000,
+                0b0010_0000,
+                0b0010_0000,
+                0b1100_0000,
             ],
             bounding_box: BoundingBox {
                 size: Coord { x: 3, y: 11 },
                 offset: Coord { x: -1, y: -2 },
             },
+            ink_bounds: None,
             shift_x: 3,
             shift_y: 0,
             tile_index: 0,
+            attributes: Attributes::default(),
+            name: Some("J".to_string()),
+            scalable_width: Some(268),
         };
         let glyph = &pcf.glyphs[&UPPERCASE_J];
         assert_eq!(expected, *glyph);
@@ -933,23 +2473,27 @@ mod tests {
             code_point: UPPERCASE_W,
             encoding: Some('W'),
             bitmap: vec![
-                1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1,
-                0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 1,
-                0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 0,
-                0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 0,
-                0, 1, 0, 0, 1, 0, 1, 0, 0, 1, 0,
-                0, 0, 1, 1, 0, 0, 0, 1, 0, 1, 0,
-                0, 0, 1, 1, 0, 0, 0, 1, 1, 0, 0,
-                0, 0, 1, 1, 0, 0, 0, 1, 1, 0, 0,
-                0, 0, 1, 1, 0, 0, 0, 1, 1, 0, 0,
+                0b1000_0100, 0b0010_0000,
+                0b0100_0100, 0b0110_0000,
+                0b0100_1010, 0b0100_0000,
+                0b0100_1010, 0b0100_0000,
+                0b0100_1010, 0b0100_0000,
+                0b0011_0001, 0b0100_0000,
+                0b0011_0001, 0b1000_0000,
+                0b0011_0001, 0b1000_0000,
+                0b0011_0001, 0b1000_0000,
             ],
             bounding_box: BoundingBox {
                 size: Coord { x: 11, y: 9 },
                 offset: Coord { x: 0, y: 0 }
             },
+            ink_bounds: None,
             shift_x: 11,
             shift_y: 0,
             tile_index: 0,
+            attributes: Attributes::default(),
+            name: Some("W".to_string()),
+            scalable_width: Some(923),
         };
         let glyph = &pcf.glyphs[&UPPERCASE_W];
         assert_eq!(expected, *glyph);
@@ -0,0 +1,300 @@
+//! Reading fonts from files and other seekable readers without loading the whole file.
+
+use std::{
+    collections::HashMap,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{Endian, PcfError, PcfFont, Quirk, KNOWN_TABLES, PCF_BITMAPS, PCF_MAGIC};
+
+/// A table of contents entry of a table the parser reads.
+struct Entry {
+    kind: i32,
+    format: i32,
+    size: i32,
+    offset: u64,
+}
+
+impl<'a> PcfFont<'a> {
+    /// Reads a PCF font from `reader` into `buffer`, which the font borrows from.
+    ///
+    /// Only the tables the parser knows are read, seeking past unknown tables and unused
+    /// space between tables, so the font has no
+    /// [`unknown_tables`](PcfFont::unknown_tables). The bitmap data, which makes up most of
+    /// a font, isn't buffered: the bitmap of each glyph is read from `reader` straight into
+    /// the glyph, so `buffer` only holds the metrics, encodings and other small tables.
+    ///
+    /// The tables are stored back to back in `buffer`, so offsets in errors refer to
+    /// `buffer` rather than the file, except for errors in the bitmap data. Malformed fonts
+    /// are reported as [`io::ErrorKind::InvalidData`] errors with the [`PcfError`] as the
+    /// source.
+    ///
+    /// ```no_run
+    /// # use pcf_parser::PcfFont;
+    /// let file = std::io::BufReader::new(std::fs::File::open("font.pcf")?);
+    /// let mut buffer = Vec::new();
+    /// let font = PcfFont::from_reader(file, &mut buffer)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_reader<R: Read + Seek>(
+        mut reader: R,
+        buffer: &'a mut Vec<u8>,
+    ) -> io::Result<PcfFont<'a>> {
+        let truncated = |field, offset| {
+            invalid(PcfError::Truncated {
+                table: "table of contents",
+                field,
+                offset,
+            })
+        };
+
+        let mut header = Vec::new();
+        reader.by_ref().take(8).read_to_end(&mut header)?;
+        if header.len() < 4 || LittleEndian::read_i32(&header) != PCF_MAGIC {
+            return Err(invalid(PcfError::BadMagic));
+        }
+        let count = header
+            .get(4..)
+            .filter(|count| count.len() == 4)
+            .ok_or(truncated("table_count", 4))?;
+        let count = u64::try_from(LittleEndian::read_i32(count)).map_err(|_| {
+            invalid(PcfError::InvalidValue {
+                table: "header",
+                field: "table_count",
+                offset: 4,
+            })
+        })?;
+
+        // the table of contents is read incrementally, so a bogus count can't allocate much
+        let mut toc = Vec::new();
+        reader.by_ref().take(16 * count).read_to_end(&mut toc)?;
+        if (toc.len() as u64) < 16 * count {
+            return Err(truncated("entries", 8 + toc.len()));
+        }
+        let mut entries: Vec<_> = toc
+            .chunks_exact(16)
+            .map(|entry| {
+                let field = |i: usize| LittleEndian::read_i32(&entry[4 * i..]);
+                Entry {
+                    kind: field(0),
+                    format: field(1),
+                    size: field(2),
+                    offset: u64::from(field(3) as u32),
+                }
+            })
+            .filter(|entry| entry.kind as usize & !KNOWN_TABLES == 0)
+            .collect();
+        entries.sort_by_key(|entry| entry.offset);
+        let file_len = reader.seek(SeekFrom::End(0))?;
+
+        let mut data = Vec::new();
+        let mut offset = 8 + 16 * entries.len();
+        // the declared size and file offset of the bitmaps table, and its header length
+        let mut bitmaps = None;
+        for entry in &entries {
+            reader.seek(SeekFrom::Start(entry.offset))?;
+            let start = data.len();
+            let mut size = entry.size;
+            match entry.kind as usize == PCF_BITMAPS {
+                true => {
+                    let declared = u64::try_from(size).unwrap_or_default();
+                    let len = read_bitmaps_header(&mut reader, declared, &mut data)?;
+                    bitmaps = Some((size, entry.offset, len));
+                    // the buffered table ends after its header
+                    size = len as i32;
+                }
+                false => {
+                    let size = u64::try_from(size).unwrap_or_default();
+                    reader.by_ref().take(size).read_to_end(&mut data)?;
+                }
+            }
+
+            // the declared size of other tables is kept, so truncated tables are still detected
+            for field in [entry.kind, entry.format, size, offset as i32] {
+                header.extend(field.to_le_bytes());
+            }
+            offset += data.len() - start;
+        }
+        LittleEndian::write_i32(&mut header[4..8], entries.len() as i32);
+        header.extend(data);
+        *buffer = header;
+
+        let mut font = Self::parse_metrics_only(buffer).map_err(invalid)?;
+        // parsing fails without a bitmaps table
+        let (size, file_offset, header_len) = bitmaps.unwrap_or_default();
+        font.restore_bitmaps_size(size, file_offset, file_len)
+            .map_err(invalid)?;
+        font.read_glyph_bitmaps(&mut reader, file_offset + header_len, file_len)?;
+
+        Ok(font)
+    }
+
+    /// Sets the size of the bitmaps table, which is only buffered up to the bitmap data, to
+    /// its `size` in the file, and reads its header again, as the glyph padding is chosen
+    /// by the size of the bitmap data.
+    fn restore_bitmaps_size(
+        &mut self,
+        size: i32,
+        file_offset: u64,
+        file_len: u64,
+    ) -> Result<(), PcfError> {
+        if let Some(table) = self.tables.get_mut(&PCF_BITMAPS) {
+            table.size = size;
+        }
+        self.quirks
+            .retain(|quirk| !matches!(quirk, Quirk::GlyphPadding { .. }));
+        // truncated tables come first, by kind
+        if file_offset.saturating_add(u64::try_from(size).unwrap_or(u64::MAX)) > file_len {
+            let index = self
+                .quirks
+                .iter()
+                .position(|quirk| {
+                    !matches!(quirk, Quirk::TruncatedTable { kind } if (*kind as usize) < PCF_BITMAPS)
+                })
+                .unwrap_or(self.quirks.len());
+            self.quirks.insert(
+                index,
+                Quirk::TruncatedTable {
+                    kind: PCF_BITMAPS as u32,
+                },
+            );
+        }
+
+        self.load_bitmap()
+    }
+
+    /// Reads the bitmaps of the glyphs from the bitmap data starting at file offset `start`,
+    /// checking them like [`parse`](Self::parse) does.
+    fn read_glyph_bitmaps<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        start: u64,
+        file_len: u64,
+    ) -> io::Result<()> {
+        let indices: HashMap<i32, usize> = self
+            .glyph_indices
+            .iter()
+            .map(|(code_point, index)| (*code_point, usize::from(*index)))
+            .collect();
+        let available = usize::try_from(file_len.saturating_sub(start)).unwrap_or(usize::MAX);
+        self.check_bitmap_sizes(&indices, &self.glyphs, available)
+            .map_err(invalid)?;
+        let offsets = self.load_bitmap_offsets(&indices).map_err(invalid)?;
+
+        let mut glyphs = std::mem::take(&mut self.glyphs);
+        // reading in file order only seeks over gaps between bitmaps
+        let mut order: Vec<_> = glyphs.values_mut().collect();
+        order.sort_by_key(|glyph| offsets[&glyph.code_point]);
+        let mut position = None;
+        let mut data = Vec::new();
+        for glyph in order {
+            let offset = start.saturating_add(offsets[&glyph.code_point] as u64);
+            if position != Some(offset) {
+                reader.seek(SeekFrom::Start(offset))?;
+            }
+            let len = self.bitmap_len(glyph);
+            data.clear();
+            reader.by_ref().take(len as u64).read_to_end(&mut data)?;
+            if data.len() < len {
+                return Err(invalid(PcfError::Truncated {
+                    table: "bitmaps",
+                    field: "bitmap_data",
+                    offset: offset as usize,
+                }));
+            }
+            self.decode_bitmap(glyph, &data);
+            position = Some(offset + len as u64);
+        }
+        self.glyphs = glyphs;
+
+        Ok(())
+    }
+}
+
+/// Reads the format, offsets and sizes at the start of the bitmaps table, up to `size`
+/// bytes, into `data` and returns how many bytes were read.
+fn read_bitmaps_header<R: Read>(reader: &mut R, size: u64, data: &mut Vec<u8>) -> io::Result<u64> {
+    let start = data.len();
+    reader.by_ref().take(size.min(8)).read_to_end(data)?;
+    let header = &data[start..];
+    let count = match header.len() {
+        8 => Endian::of(LittleEndian::read_i32(header)).read_i32(&header[4..]),
+        _ => 0,
+    };
+    // the glyph offsets and the 4 bitmap sizes
+    let rest = (u64::try_from(count).unwrap_or_default() + 4) * 4;
+    reader
+        .by_ref()
+        .take(rest.min(size.saturating_sub(8)))
+        .read_to_end(data)?;
+
+    Ok((data.len() - start) as u64)
+}
+
+fn invalid(error: PcfError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn it_reads_the_same_font_as_parse() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let pcf = PcfFont::new(font);
+
+        let mut buffer = Vec::new();
+        let read = PcfFont::from_reader(Cursor::new(font), &mut buffer).unwrap();
+        assert_eq!(read.glyphs, pcf.glyphs);
+        assert_eq!(read.properties, pcf.properties);
+        assert_eq!(read.accelerators, pcf.accelerators);
+        assert_eq!(read.quirks, pcf.quirks);
+        assert_eq!(
+            read.tables[&PCF_BITMAPS].size,
+            pcf.tables[&PCF_BITMAPS].size
+        );
+        // the bitmap data isn't buffered
+        assert!(buffer.len() <= font.len() - pcf.bitmap.bitmap_sizes);
+
+        let lsb = include_bytes!("../../assets/OpenSans-Regular-12-lsb.pcf");
+        let read = PcfFont::from_reader(Cursor::new(lsb), &mut buffer).unwrap();
+        assert_eq!(read.glyphs, pcf.glyphs);
+    }
+
+    #[test]
+    fn it_reports_malformed_fonts_as_invalid_data() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let mut buffer = Vec::new();
+
+        // cut off in the table of contents, the metrics and the bitmap data
+        for bytes in [
+            &b"not a font"[..],
+            b"\x01fcp",
+            &font[..100],
+            &font[..1700],
+            &font[..3000],
+        ] {
+            let error = PcfFont::from_reader(Cursor::new(bytes), &mut buffer).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            assert!(error.get_ref().unwrap().is::<PcfError>());
+        }
+    }
+
+    #[test]
+    fn it_works_around_wrong_glyph_padding() {
+        let font = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let mut bytes = font.to_vec();
+        let pcf = PcfFont::new(font);
+        // declare 8 byte padding for bitmaps padded to 4 bytes
+        bytes[pcf.tables[&PCF_BITMAPS].offset] |= 3;
+
+        let mut buffer = Vec::new();
+        let read = PcfFont::from_reader(Cursor::new(&bytes), &mut buffer).unwrap();
+        assert_eq!(read.quirks, PcfFont::new(&bytes).quirks);
+        assert_eq!(read.glyphs, pcf.glyphs);
+    }
+}
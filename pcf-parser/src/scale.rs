@@ -0,0 +1,210 @@
+use crate::{BoundingBox, Coord, Glyph};
+
+/// The algorithm used to upscale glyph bitmaps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Filter {
+    /// Every pixel becomes a block of pixels.
+    #[default]
+    Nearest,
+    /// The Scale2x and Scale3x pixel art algorithms, which smooth diagonal edges. Other
+    /// factors fall back to `Nearest`.
+    ScaleNx,
+}
+
+impl BoundingBox {
+    /// Returns the bounding box scaled by integer factors.
+    pub fn scaled(&self, x: u32, y: u32) -> BoundingBox {
+        let (x, y) = (x as i32, y as i32);
+
+        BoundingBox {
+            size: Coord::new(self.size.x * x, self.size.y * y),
+            offset: Coord::new(self.offset.x * x, self.offset.y * y),
+        }
+    }
+}
+
+impl Glyph {
+    /// Returns the glyph scaled by integer factors, including its metrics.
+    ///
+    /// `Filter::ScaleNx` is only applied if both factors are equal.
+    pub fn scaled(&self, x: u32, y: u32, filter: Filter) -> Glyph {
+        let width = self.bounding_box.size.x;
+        let height = self.bounding_box.size.y;
+        let pixel = |x: i32, y: i32| {
+            (0..width).contains(&x)
+                && (0..height).contains(&y)
+                && self.pixel(x as usize, y as usize)
+        };
+
+        let (fx, fy) = (x as i32, y as i32);
+        let mut scaled = Glyph {
+            code_point: self.code_point,
+            encoding: self.encoding,
+            bitmap: Vec::new(),
+            bounding_box: self.bounding_box.scaled(fx as u32, fy as u32),
+            ink_bounds: self
+                .ink_bounds
+                .map(|bounds| bounds.scaled(fx as u32, fy as u32)),
+            shift_x: self.shift_x * fx,
+            shift_y: self.shift_y * fy,
+            tile_index: self.tile_index,
+            attributes: self.attributes,
+            name: self.name.clone(),
+            scalable_width: self.scalable_width,
+        };
+        scaled.bitmap = scaled.empty_bitmap();
+
+        for y in 0..height {
+            for x in 0..width {
+                let block = match (filter, fx, fy) {
+                    (Filter::ScaleNx, 2, 2) => scale2x(|dx, dy| pixel(x + dx, y + dy)).to_vec(),
+                    (Filter::ScaleNx, 3, 3) => scale3x(|dx, dy| pixel(x + dx, y + dy)).to_vec(),
+                    _ => vec![pixel(x, y); (fx * fy) as usize],
+                };
+
+                for (i, set) in block.into_iter().enumerate() {
+                    let (bx, by) = (x * fx + i as i32 % fx, y * fy + i as i32 / fx);
+                    scaled.set_pixel(bx as usize, by as usize, set);
+                }
+            }
+        }
+
+        scaled
+    }
+}
+
+/// Returns the 2x2 block of a pixel, row by row, given its neighbourhood.
+fn scale2x(pixel: impl Fn(i32, i32) -> bool) -> [bool; 4] {
+    let p = pixel(0, 0);
+    let a = pixel(0, -1);
+    let b = pixel(1, 0);
+    let c = pixel(-1, 0);
+    let d = pixel(0, 1);
+
+    [
+        if c == a && c != d && a != b { a } else { p },
+        if a == b && a != c && b != d { b } else { p },
+        if d == c && d != b && c != a { c } else { p },
+        if b == d && b != a && d != c { d } else { p },
+    ]
+}
+
+/// Returns the 3x3 block of a pixel, row by row, given its neighbourhood.
+fn scale3x(pixel: impl Fn(i32, i32) -> bool) -> [bool; 9] {
+    let [a, b, c] = [pixel(-1, -1), pixel(0, -1), pixel(1, -1)];
+    let [d, e, f] = [pixel(-1, 0), pixel(0, 0), pixel(1, 0)];
+    let [g, h, i] = [pixel(-1, 1), pixel(0, 1), pixel(1, 1)];
+
+    if b == h || d == f {
+        return [e; 9];
+    }
+
+    [
+        if d == b { d } else { e },
+        if (d == b && e != c) || (b == f && e != a) {
+            b
+        } else {
+            e
+        },
+        if b == f { f } else { e },
+        if (d == b && e != g) || (d == h && e != a) {
+            d
+        } else {
+            e
+        },
+        e,
+        if (b == f && e != i) || (h == f && e != c) {
+            f
+        } else {
+            e
+        },
+        if d == h { d } else { e },
+        if (d == h && e != i) || (h == f && e != g) {
+            h
+        } else {
+            e
+        },
+        if h == f { f } else { e },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Attributes;
+
+    fn glyph(rows: &[&str]) -> Glyph {
+        let mut glyph = Glyph {
+            code_point: 0,
+            encoding: None,
+            bitmap: Vec::new(),
+            bounding_box: BoundingBox {
+                size: Coord::new(rows[0].len() as i32, rows.len() as i32),
+                offset: Coord::new(0, -1),
+            },
+            ink_bounds: None,
+            shift_x: rows[0].len() as i32 + 1,
+            shift_y: 0,
+            tile_index: 0,
+            attributes: Attributes::default(),
+            name: None,
+            scalable_width: None,
+        };
+        glyph.bitmap = glyph.empty_bitmap();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, pixel) in row.bytes().enumerate() {
+                glyph.set_pixel(x, y, pixel == b'#');
+            }
+        }
+
+        glyph
+    }
+
+    fn rows(glyph: &Glyph) -> Vec<String> {
+        let size = glyph.bounding_box.size;
+
+        (0..size.y as usize)
+            .map(|y| {
+                (0..size.x as usize)
+                    .map(|x| if glyph.pixel(x, y) { '#' } else { ' ' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_scales_with_nearest_neighbor() {
+        let scaled = glyph(&["# ", " #"]).scaled(2, 2, Filter::Nearest);
+
+        assert_eq!(rows(&scaled), ["##  ", "##  ", "  ##", "  ##"]);
+        assert_eq!(scaled.bounding_box.offset, Coord::new(0, -2));
+        assert_eq!(scaled.shift_x, 6);
+    }
+
+    #[test]
+    fn it_scales_axes_independently() {
+        let scaled = glyph(&["# ", " #"]).scaled(2, 1, Filter::ScaleNx);
+
+        assert_eq!(rows(&scaled), ["##  ", "  ##"]);
+        assert_eq!(scaled.bounding_box.size, Coord::new(4, 2));
+        assert_eq!(scaled.bounding_box.offset, Coord::new(0, -1));
+        assert_eq!(scaled.shift_x, 6);
+    }
+
+    #[test]
+    fn it_smooths_diagonals_with_scale2x() {
+        let scaled = glyph(&["# ", " #"]).scaled(2, 2, Filter::ScaleNx);
+
+        assert_eq!(rows(&scaled), ["##  ", "### ", " ###", "  ##"]);
+    }
+
+    #[test]
+    fn it_smooths_diagonals_with_scale3x() {
+        let scaled = glyph(&["# ", " #"]).scaled(3, 3, Filter::ScaleNx);
+
+        assert_eq!(
+            rows(&scaled),
+            ["###   ", "###   ", "####  ", "  ####", "   ###", "   ###"]
+        );
+    }
+}
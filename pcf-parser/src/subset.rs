@@ -0,0 +1,233 @@
+use crate::{BoundingBox, Filter, Font, Glyph};
+
+/// A glyph of a [`Subset`] with its bounding box in the layout of `eg_pcf::PcfGlyph`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubsetGlyph {
+    pub character: char,
+    pub left: i32,
+    pub top: i32,
+    pub width: u32,
+    pub height: u32,
+    pub device_width: i32,
+    /// The glyph, scaled by the factors of the subset.
+    pub glyph: Glyph,
+}
+
+impl SubsetGlyph {
+    /// Returns the pixels of the glyph row by row.
+    pub fn pixels(&self) -> impl Iterator<Item = bool> + '_ {
+        let (width, height) = (self.width as usize, self.height as usize);
+
+        (0..height).flat_map(move |y| (0..width).map(move |x| self.glyph.pixel(x, y)))
+    }
+}
+
+/// The glyphs of a font that match a character set, with the metrics of the
+/// `eg_pcf::PcfFont` that `include_pcf!` and `pcf2rust` generate for them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subset {
+    /// The glyphs in ascending order of characters.
+    pub glyphs: Vec<SubsetGlyph>,
+    /// The font bounding box as left, top, width and height.
+    pub bounding_box: (i32, i32, u32, u32),
+    pub line_height: u32,
+    /// The index of the glyph drawn in place of missing characters.
+    pub replacement_character: usize,
+    /// The point size in decipoints and the horizontal and vertical resolution.
+    pub design_size: Option<(u32, u32, u32)>,
+    pub superscript: i32,
+    pub subscript: i32,
+    pub ascent: u32,
+    pub descent: u32,
+    pub cap_height: Option<u32>,
+    pub x_height: Option<u32>,
+    /// The advance of every glyph, if the font is a constant width terminal font.
+    pub monospace_advance: Option<i32>,
+}
+
+/// Converts a PCF bounding box into the left, top, width and height of an
+/// `embedded_graphics` rectangle relative to the drawing position of `eg_pcf`.
+fn rectangle(bounding_box: &BoundingBox) -> (i32, i32, u32, u32) {
+    (
+        bounding_box.offset.x,
+        -bounding_box.offset.y - bounding_box.size.y - 1,
+        bounding_box.size.x.max(0) as u32,
+        bounding_box.size.y.max(0) as u32,
+    )
+}
+
+impl Subset {
+    /// Selects the glyphs of `font` for which `contains` returns true.
+    ///
+    /// `default` is drawn in place of missing characters and always included. Without it the
+    /// font's default character is used if it's included, otherwise REPLACEMENT CHARACTER or
+    /// space.
+    pub fn new(
+        font: &dyn Font,
+        contains: impl Fn(char) -> bool,
+        default: Option<char>,
+    ) -> Result<Self, String> {
+        Self::scaled(font, contains, default, (1, 1), Filter::Nearest)
+    }
+
+    /// Selects glyphs like [`new`](Self::new) and scales them and the metrics by integer
+    /// factors.
+    pub fn scaled(
+        font: &dyn Font,
+        contains: impl Fn(char) -> bool,
+        default: Option<char>,
+        (scale_x, scale_y): (u32, u32),
+        filter: Filter,
+    ) -> Result<Self, String> {
+        if let Some(default) = default.filter(|c| !font.contains(*c)) {
+            return Err(format!("font has no glyph for {default:?}"));
+        }
+        let explicit = default;
+        let default = default.or(font.default_char());
+
+        let mut included: Vec<(char, &Glyph)> = font
+            .glyphs()
+            .values()
+            .filter_map(|glyph| {
+                let c = glyph.encoding?;
+                (contains(c) || Some(c) == explicit).then_some((c, glyph))
+            })
+            .collect();
+        // sorted by character so `PcfFont` can use binary search, which also makes the
+        // output independent of the hash map order and the same on every build
+        included.sort_by_key(|(c, _)| *c);
+        if included.is_empty() {
+            return Err("no glyphs match the character set".to_string());
+        }
+
+        let mut glyphs = Vec::new();
+        let mut replacement_character = None;
+        let mut fallback_character = None;
+        for (c, glyph) in included {
+            if Some(c) == default {
+                replacement_character = Some(glyphs.len());
+            }
+            if c == char::REPLACEMENT_CHARACTER || (c == ' ' && fallback_character.is_none()) {
+                fallback_character = Some(glyphs.len());
+            }
+
+            let glyph = glyph.scaled(scale_x, scale_y, filter);
+            let (left, top, width, height) = rectangle(&glyph.bounding_box);
+            glyphs.push(SubsetGlyph {
+                character: c,
+                left,
+                top,
+                width,
+                height,
+                device_width: glyph.shift_x,
+                glyph,
+            });
+        }
+
+        let monospace_advance = match glyphs.as_slice() {
+            [first, rest @ ..] if font.is_monospaced() => Some(first.device_width)
+                .filter(|advance| rest.iter().all(|glyph| glyph.device_width == *advance)),
+            _ => None,
+        };
+
+        let font_box = font.bounding_box().scaled(scale_x, scale_y);
+        let ascent = font_box.size.y + font_box.offset.y;
+        let script_offset = |name, percent| match font.integer_property(name) {
+            Some(offset) if offset != 0 => offset.abs() * scale_y as i32,
+            _ => (ascent * percent + 50) / 100,
+        };
+        let design_size = match (font.point_size(), font.resolution()) {
+            (Some(point_size), Some((x, y))) if x > 0 && y > 0 => Some((point_size, x, y)),
+            _ => None,
+        };
+        let pixels = |pixels: i32| (pixels.max(0) * scale_y as i32) as u32;
+
+        Ok(Self {
+            glyphs,
+            bounding_box: rectangle(&font_box),
+            line_height: font_box.size.y.max(0) as u32,
+            replacement_character: replacement_character
+                .or(fallback_character)
+                .unwrap_or_default(),
+            design_size,
+            superscript: script_offset("SUPERSCRIPT_Y", 40),
+            subscript: script_offset("SUBSCRIPT_Y", 20),
+            ascent: pixels(font.ascent()),
+            descent: pixels(font.descent()),
+            cap_height: font.cap_height().map(pixels),
+            x_height: font.x_height().map(pixels),
+            monospace_advance,
+        })
+    }
+
+    /// Returns the glyph bitmaps packed MSB first without padding, and the index of the
+    /// first bit of every glyph in them.
+    pub fn packed_bitmaps(&self) -> (Vec<usize>, Vec<u8>) {
+        let mut bits = Vec::new();
+        let start_indices = self
+            .glyphs
+            .iter()
+            .map(|glyph| {
+                let start_index = bits.len();
+                bits.extend(glyph.pixels());
+                start_index
+            })
+            .collect();
+
+        let data = bits
+            .chunks(8)
+            .map(|bits| {
+                bits.iter()
+                    .enumerate()
+                    .filter(|(_, b)| **b)
+                    .map(|(i, _)| 0x80 >> i)
+                    .sum()
+            })
+            .collect();
+
+        (start_indices, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PcfFont;
+
+    #[test]
+    fn it_selects_and_packs_glyphs() {
+        let font = include_bytes!("../../eg-pcf/examples/6x10.pcf");
+        let font = PcfFont::new(&font[..]);
+        let contains = |c| c == '\0' || ('A'..='C').contains(&c);
+        let subset = Subset::new(&font, contains, None).unwrap();
+
+        // the font's default character is the replacement
+        let chars: Vec<_> = subset.glyphs.iter().map(|g| g.character).collect();
+        assert_eq!(chars, ['\0', 'A', 'B', 'C']);
+        assert_eq!(subset.replacement_character, 0);
+
+        let a = &subset.glyphs[1];
+        assert_eq!((a.left, a.top, a.width, a.height), (0, -9, 6, 10));
+        let (start_indices, data) = subset.packed_bitmaps();
+        assert_eq!(start_indices, [0, 60, 120, 180]);
+        assert_eq!(data.len(), 4 * 60 / 8);
+        assert_eq!(subset.bounding_box, (0, -9, 6, 10));
+        assert_eq!((subset.ascent, subset.descent), (8, 2));
+
+        let error = Subset::new(&font, |_| false, Some('\u{2603}')).unwrap_err();
+        assert!(error.contains("no glyph"));
+    }
+
+    #[test]
+    fn it_scales_glyphs_and_metrics() {
+        let font = include_bytes!("../../eg-pcf/examples/6x10.pcf");
+        let font = PcfFont::new(&font[..]);
+        let subset = Subset::scaled(&font, |c| c == 'A', None, (2, 3), Filter::Nearest).unwrap();
+
+        let a = &subset.glyphs[0];
+        assert_eq!((a.left, a.top, a.width, a.height), (0, -25, 12, 30));
+        assert_eq!(a.device_width, 12);
+        assert_eq!(subset.line_height, 30);
+        assert_eq!((subset.ascent, subset.descent), (24, 6));
+    }
+}
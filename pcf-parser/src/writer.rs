@@ -0,0 +1,407 @@
+use std::io;
+
+use crate::{
+    Accelerators, Glyph, PcfFont, Property, UncompressedMetrics, PCF_ACCELERATORS,
+    PCF_ACCEL_W_INKBOUNDS, PCF_BDF_ACCELERATORS, PCF_BDF_ENCODINGS, PCF_BITMAPS, PCF_BIT_MASK,
+    PCF_BYTE_MASK, PCF_COMPRESSED_METRICS, PCF_DEFAULT_FORMAT, PCF_GLYPH_NAMES, PCF_INK_METRICS,
+    PCF_MAGIC, PCF_METRICS, PCF_PROPERTIES, PCF_SWIDTHS,
+};
+
+/// Tables are written most significant byte and bit first.
+const FORMAT: i32 = PCF_DEFAULT_FORMAT | PCF_BYTE_MASK | PCF_BIT_MASK;
+/// Glyph rows are padded to 4 bytes, the most common padding.
+const GLYPH_PAD: usize = 4;
+const GLYPH_PAD_INDEX: i32 = 2;
+
+/// Writes a font, or a subset of its glyphs, as a PCF file.
+///
+/// The encoding, metrics, bitmap, scalable widths and glyph names tables are regenerated
+/// from the written glyphs, and the accelerators are recomputed from their bounds. The
+/// properties and [`unknown_tables`](PcfFont::unknown_tables) are copied unchanged. The ink
+/// metrics table is only written if all glyphs have [ink bounds](Glyph::ink_bounds).
+///
+/// ```no_run
+/// # use pcf_parser::{PcfFont, PcfWriter};
+/// let bytes = std::fs::read("font.pcf").unwrap();
+/// let font = PcfFont::new(&bytes);
+///
+/// let digits = PcfWriter::new(&font).subset(('0'..='9').map(|c| c as i32));
+/// std::fs::write("digits.pcf", digits.to_bytes()).unwrap();
+/// ```
+pub struct PcfWriter<'f, 'a> {
+    font: &'f PcfFont<'a>,
+    code_points: Option<Vec<i32>>,
+}
+
+impl<'f, 'a> PcfWriter<'f, 'a> {
+    /// Creates a writer for all glyphs of `font`.
+    pub fn new(font: &'f PcfFont<'a>) -> Self {
+        Self {
+            font,
+            code_points: None,
+        }
+    }
+
+    /// Only writes the glyphs of `code_points`. Code points without a glyph are ignored.
+    pub fn subset(mut self, code_points: impl IntoIterator<Item = i32>) -> Self {
+        self.code_points = Some(code_points.into_iter().collect());
+        self
+    }
+
+    /// Writes the font to `writer`.
+    pub fn write(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    /// Returns the font as PCF data.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let glyphs = self.glyphs();
+        let accelerators = self
+            .font
+            .accelerators_for(glyphs.iter().copied())
+            .unwrap_or_default();
+
+        let ink_metrics = glyphs
+            .iter()
+            .map(|glyph| Some(UncompressedMetrics::with_bounds(glyph, glyph.ink_bounds?)))
+            .collect::<Option<Vec<_>>>()
+            .filter(|metrics| !metrics.is_empty());
+
+        let tables = [
+            (PCF_PROPERTIES, Some(properties(self.font))),
+            (PCF_ACCELERATORS, Some(accelerators_table(&accelerators, 0))),
+            (
+                PCF_METRICS,
+                Some(metrics(
+                    glyphs.iter().map(|glyph| (*glyph).into()).collect(),
+                )),
+            ),
+            (PCF_BITMAPS, Some(bitmaps(&glyphs))),
+            (PCF_INK_METRICS, ink_metrics.map(metrics)),
+            (
+                PCF_BDF_ENCODINGS,
+                Some(encodings(&glyphs, self.font.encoding.default_char)),
+            ),
+            (PCF_SWIDTHS, Some(scalable_widths(&glyphs))),
+            (PCF_GLYPH_NAMES, Some(glyph_names(&glyphs))),
+            (
+                PCF_BDF_ACCELERATORS,
+                Some(accelerators_table(&accelerators, PCF_ACCEL_W_INKBOUNDS)),
+            ),
+        ];
+        let mut tables: Vec<_> = tables
+            .into_iter()
+            .filter_map(|(kind, data)| Some((kind, data?)))
+            .map(|(kind, data)| {
+                (
+                    kind,
+                    i32::from_le_bytes(data[..4].try_into().unwrap()),
+                    data,
+                )
+            })
+            .chain(
+                self.font
+                    .unknown_tables
+                    .iter()
+                    .map(|table| (table.kind as usize, table.format, table.data.to_vec())),
+            )
+            .collect();
+
+        let mut bytes = PCF_MAGIC.to_le_bytes().to_vec();
+        bytes.extend((tables.len() as i32).to_le_bytes());
+        let mut offset = 8 + 16 * tables.len();
+        for (kind, format, data) in &mut tables {
+            data.resize(data.len().next_multiple_of(4), 0);
+            bytes.extend((*kind as i32).to_le_bytes());
+            bytes.extend(format.to_le_bytes());
+            bytes.extend((data.len() as i32).to_le_bytes());
+            bytes.extend((offset as i32).to_le_bytes());
+            offset += data.len();
+        }
+        for (_, _, data) in tables {
+            bytes.extend(data);
+        }
+
+        bytes
+    }
+
+    /// Returns the glyphs to write, sorted by code point.
+    ///
+    /// Code points above `0xFFFF` can't be encoded and are left out.
+    fn glyphs(&self) -> Vec<&'f Glyph> {
+        let mut glyphs: Vec<_> = match &self.code_points {
+            Some(code_points) => code_points
+                .iter()
+                .filter_map(|code_point| self.font.glyphs.get(code_point))
+                .collect(),
+            None => self.font.glyphs.values().collect(),
+        };
+        glyphs.retain(|glyph| (0..=0xFFFF).contains(&glyph.code_point));
+        glyphs.sort_by_key(|glyph| glyph.code_point);
+        glyphs.dedup_by_key(|glyph| glyph.code_point);
+
+        glyphs
+    }
+}
+
+/// Starts a table with its format, which is always little endian.
+fn table(format: i32) -> Vec<u8> {
+    format.to_le_bytes().to_vec()
+}
+
+/// Appends strings as NUL terminated bytes and returns their offsets.
+fn strings<'s>(data: &mut Vec<u8>, strings: impl IntoIterator<Item = &'s str>) -> Vec<i32> {
+    strings
+        .into_iter()
+        .map(|string| {
+            let offset = data.len() as i32;
+            data.extend(string.as_bytes());
+            data.push(0);
+
+            offset
+        })
+        .collect()
+}
+
+fn properties(font: &PcfFont) -> Vec<u8> {
+    let mut properties: Vec<_> = font.properties.iter().collect();
+    properties.sort_by_key(|(name, _)| *name);
+
+    let mut data = Vec::new();
+    let mut entries = Vec::new();
+    for (name, value) in properties {
+        let name = strings(&mut data, [name.as_str()])[0];
+        let (is_string, value) = match value {
+            Property::Integer(value) => (0, *value),
+            Property::String(value) => (1, strings(&mut data, [value.as_str()])[0]),
+        };
+        entries.push((name, is_string, value));
+    }
+
+    let mut table = table(FORMAT);
+    table.extend((entries.len() as i32).to_be_bytes());
+    for (name, is_string, value) in &entries {
+        table.extend(name.to_be_bytes());
+        table.push(*is_string);
+        table.extend(value.to_be_bytes());
+    }
+    table.resize(table.len().next_multiple_of(4), 0);
+    table.extend((data.len() as i32).to_be_bytes());
+    table.extend(data);
+
+    table
+}
+
+fn uncompressed_metrics(table: &mut Vec<u8>, metrics: &UncompressedMetrics) {
+    table.extend(metrics.left_side_bearing.to_be_bytes());
+    table.extend(metrics.right_side_bearing.to_be_bytes());
+    table.extend(metrics.character_width.to_be_bytes());
+    table.extend(metrics.character_ascent.to_be_bytes());
+    table.extend(metrics.character_descent.to_be_bytes());
+    table.extend(metrics.character_attributes.to_be_bytes());
+}
+
+fn accelerators_table(accelerators: &Accelerators, format: i32) -> Vec<u8> {
+    let mut table = table(FORMAT | format);
+    table.extend([
+        accelerators.no_overlap,
+        accelerators.constant_metrics,
+        accelerators.terminal_font,
+        accelerators.constant_width,
+        accelerators.ink_inside,
+        accelerators.ink_metrics,
+        accelerators.draw_direction,
+        accelerators.padding,
+    ]);
+    table.extend(accelerators.font_ascent.to_be_bytes());
+    table.extend(accelerators.font_descent.to_be_bytes());
+    table.extend(accelerators.max_overlap.to_be_bytes());
+    uncompressed_metrics(&mut table, &accelerators.minbounds);
+    uncompressed_metrics(&mut table, &accelerators.maxbounds);
+    if format & PCF_ACCEL_W_INKBOUNDS != 0 {
+        uncompressed_metrics(&mut table, &accelerators.ink_minbounds);
+        uncompressed_metrics(&mut table, &accelerators.ink_maxbounds);
+    }
+
+    table
+}
+
+/// Writes compressed metrics if all glyphs fit them, like `bdftopcf` does.
+fn metrics(metrics: Vec<UncompressedMetrics>) -> Vec<u8> {
+    let compressible = metrics.iter().all(|m| {
+        [
+            m.left_side_bearing,
+            m.right_side_bearing,
+            m.character_width,
+            m.character_ascent,
+            m.character_descent,
+        ]
+        .iter()
+        .all(|value| (-0x80..0x80).contains(value))
+            && m.character_attributes == 0
+    });
+
+    if compressible && metrics.len() <= i16::MAX as usize {
+        let mut table = table(FORMAT | PCF_COMPRESSED_METRICS);
+        table.extend((metrics.len() as i16).to_be_bytes());
+        for m in &metrics {
+            table.extend(
+                [
+                    m.left_side_bearing,
+                    m.right_side_bearing,
+                    m.character_width,
+                    m.character_ascent,
+                    m.character_descent,
+                ]
+                .map(|value| (value + 0x80) as u8),
+            );
+        }
+
+        table
+    } else {
+        let mut table = table(FORMAT);
+        table.extend((metrics.len() as i32).to_be_bytes());
+        for m in &metrics {
+            uncompressed_metrics(&mut table, m);
+        }
+
+        table
+    }
+}
+
+fn bitmaps(glyphs: &[&Glyph]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut offsets = Vec::new();
+    let mut sizes = [0i32; 4];
+
+    for glyph in glyphs {
+        let width = glyph.bounding_box.size.x.max(0) as usize;
+        let height = glyph.bounding_box.size.y.max(0) as usize;
+        for (i, size) in sizes.iter_mut().enumerate() {
+            let pad = 1 << i;
+            *size += (pad * width.div_ceil(8 * pad) * height) as i32;
+        }
+
+        offsets.push(data.len() as i32);
+        let stride = width.div_ceil(8);
+        for y in 0..height {
+            data.extend(&glyph.bitmap[y * stride..][..stride]);
+            data.resize(
+                data.len() + GLYPH_PAD * width.div_ceil(8 * GLYPH_PAD) - stride,
+                0,
+            );
+        }
+    }
+
+    let mut table = table(FORMAT | GLYPH_PAD_INDEX);
+    table.extend((glyphs.len() as i32).to_be_bytes());
+    for offset in offsets {
+        table.extend(offset.to_be_bytes());
+    }
+    for size in sizes {
+        table.extend(size.to_be_bytes());
+    }
+    table.extend(data);
+
+    table
+}
+
+fn encodings(glyphs: &[&Glyph], default_char: usize) -> Vec<u8> {
+    let bytes = |code_point: i32| ((code_point >> 8) as usize, (code_point & 0xFF) as usize);
+    let (mut min_byte1, mut max_byte1, mut min_byte2, mut max_byte2) = (0xFF, 0, 0xFF, 0);
+    for glyph in glyphs {
+        let (byte1, byte2) = bytes(glyph.code_point);
+        min_byte1 = min_byte1.min(byte1);
+        max_byte1 = max_byte1.max(byte1);
+        min_byte2 = min_byte2.min(byte2);
+        max_byte2 = max_byte2.max(byte2);
+    }
+    if glyphs.is_empty() {
+        (min_byte1, min_byte2) = (0, 0);
+    }
+
+    let columns = max_byte2 - min_byte2 + 1;
+    let mut indices = vec![0xFFFFu16; (max_byte1 - min_byte1 + 1) * columns];
+    for (index, glyph) in glyphs.iter().enumerate() {
+        let (byte1, byte2) = bytes(glyph.code_point);
+        indices[(byte1 - min_byte1) * columns + byte2 - min_byte2] = index as u16;
+    }
+
+    let mut table = table(FORMAT);
+    for value in [min_byte2, max_byte2, min_byte1, max_byte1, default_char] {
+        table.extend((value as u16).to_be_bytes());
+    }
+    for index in indices {
+        table.extend(index.to_be_bytes());
+    }
+
+    table
+}
+
+fn scalable_widths(glyphs: &[&Glyph]) -> Vec<u8> {
+    let mut table = table(FORMAT);
+    table.extend((glyphs.len() as i32).to_be_bytes());
+    for glyph in glyphs {
+        table.extend(glyph.scalable_width.unwrap_or_default().to_be_bytes());
+    }
+
+    table
+}
+
+fn glyph_names(glyphs: &[&Glyph]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let offsets = strings(
+        &mut data,
+        glyphs
+            .iter()
+            .map(|glyph| glyph.name.as_deref().unwrap_or_default()),
+    );
+
+    let mut table = table(FORMAT);
+    table.extend((glyphs.len() as i32).to_be_bytes());
+    for offset in offsets {
+        table.extend(offset.to_be_bytes());
+    }
+    table.extend((data.len() as i32).to_be_bytes());
+    table.extend(data);
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Font;
+
+    #[test]
+    fn it_writes_a_font_that_parses_the_same() {
+        let bytes = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let font = PcfFont::new(&bytes[..]);
+
+        let written = PcfWriter::new(&font).to_bytes();
+        let pcf = PcfFont::parse(&written).unwrap();
+
+        assert_eq!(pcf.glyphs, font.glyphs);
+        assert_eq!(pcf.properties, font.properties);
+        assert_eq!(pcf.bounding_box, font.bounding_box);
+        assert!(pcf.quirks.is_empty());
+    }
+
+    #[test]
+    fn it_writes_a_subset_of_glyphs() {
+        let bytes = include_bytes!("../../assets/OpenSans-Regular-12.pcf");
+        let mut font = PcfFont::new(&bytes[..]);
+        let code_points = ['0', '7', 'A', '\u{2603}'].map(|c| c as i32);
+
+        let written = PcfWriter::new(&font).subset(code_points).to_bytes();
+        let pcf = PcfFont::parse(&written).unwrap();
+
+        font.glyphs
+            .retain(|code_point, _| code_points.contains(code_point));
+        font.recompute_metrics();
+        assert_eq!(pcf.glyphs, font.glyphs);
+        assert_eq!(pcf.bounding_box, font.bounding_box);
+        assert_eq!(pcf.glyph_by_name("zero").map(|g| g.code_point), Some(48));
+        assert!(written.len() < bytes.len() / 2, "{} bytes", written.len());
+    }
+}
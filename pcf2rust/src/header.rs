@@ -0,0 +1,110 @@
+use std::fmt::Write;
+
+use pcf_parser::Subset;
+
+/// Returns a C header with the glyphs and data of `subset` as arrays prefixed by `name`.
+///
+/// The glyph struct has the field order and sizes of the records of `eg_pcf::raw`, so the
+/// glyph table has the same memory layout on little endian targets.
+pub fn generate(subset: &Subset, name: &str, source: &str) -> String {
+    let mut out = String::new();
+    let lower = name.to_lowercase();
+    let (left, top, width, height) = subset.bounding_box;
+    let (start_indices, data) = subset.packed_bitmaps();
+
+    writeln!(
+        out,
+        "/* Generated by pcf2rust from {source}. Do not edit. */"
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#ifndef {name}_H").unwrap();
+    writeln!(out, "#define {name}_H").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#include <stdint.h>").unwrap();
+    writeln!(out).unwrap();
+    out.push_str(
+        "#ifndef PCF_GLYPH_DEFINED\n\
+         #define PCF_GLYPH_DEFINED\n\
+         /* Pixel (x, y) of a glyph is bit `start_index + y * width + x` of the data, MSB first. */\n\
+         typedef struct {\n\
+         \x20   uint32_t character;\n\
+         \x20   uint32_t start_index;\n\
+         \x20   int16_t left;\n\
+         \x20   int16_t top;\n\
+         \x20   uint8_t width;\n\
+         \x20   uint8_t height;\n\
+         \x20   int16_t device_width;\n\
+         } pcf_glyph_t;\n\
+         #endif\n\n",
+    );
+    let defines = [
+        ("GLYPH_COUNT", subset.glyphs.len().to_string()),
+        (
+            "REPLACEMENT_CHARACTER",
+            subset.replacement_character.to_string(),
+        ),
+        ("LINE_HEIGHT", subset.line_height.to_string()),
+        ("ASCENT", subset.ascent.to_string()),
+        ("DESCENT", subset.descent.to_string()),
+        ("BOUNDING_BOX_LEFT", left.to_string()),
+        ("BOUNDING_BOX_TOP", top.to_string()),
+        ("BOUNDING_BOX_WIDTH", width.to_string()),
+        ("BOUNDING_BOX_HEIGHT", height.to_string()),
+    ];
+    for (define, value) in defines {
+        writeln!(out, "#define {name}_{define} {value}").unwrap();
+    }
+    writeln!(out).unwrap();
+
+    writeln!(out, "/* Sorted by character. */").unwrap();
+    writeln!(out, "static const pcf_glyph_t {lower}_glyphs[] = {{").unwrap();
+    for (glyph, start_index) in subset.glyphs.iter().zip(start_indices) {
+        writeln!(
+            out,
+            "    {{ 0x{:04x}, {}, {}, {}, {}, {}, {} }},",
+            u32::from(glyph.character),
+            start_index,
+            glyph.left,
+            glyph.top,
+            glyph.width,
+            glyph.height,
+            glyph.device_width
+        )
+        .unwrap();
+    }
+    writeln!(out, "}};").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "static const uint8_t {lower}_data[] = {{").unwrap();
+    for row in data.chunks(16) {
+        let bytes: Vec<_> = row.iter().map(|byte| format!("0x{byte:02x}")).collect();
+        writeln!(out, "    {},", bytes.join(", ")).unwrap();
+    }
+    writeln!(out, "}};").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#endif").unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pcf_parser::PcfFont;
+
+    #[test]
+    fn it_generates_a_header() {
+        let font = include_bytes!("../../eg-pcf/examples/6x10.pcf");
+        let font = PcfFont::new(&font[..]);
+        let subset = Subset::new(&font, |c| c == 'A' || c == 'B', None).unwrap();
+        let header = generate(&subset, "FONT", "6x10.pcf");
+
+        assert!(header.contains("#ifndef FONT_H\n#define FONT_H\n"));
+        assert!(header.contains("#define FONT_GLYPH_COUNT 2\n"));
+        assert!(header.contains("static const pcf_glyph_t font_glyphs[] = {\n"));
+        assert!(header.contains("    { 0x0041, 0, 0, -9, 6, 10, 6 },\n"));
+        assert!(header.contains("    { 0x0042, 60, 0, -9, 6, 10, 6 },\n"));
+        assert!(header.ends_with("#endif\n"));
+    }
+}
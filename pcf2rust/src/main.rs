@@ -0,0 +1,226 @@
+//! Converts a PCF or BDF font into a Rust source file with the `eg_pcf::PcfFont` that
+//! `include_pcf!` would generate, and optionally a C header with the same data, for build
+//! systems that can't run proc macros.
+//!
+//! ```text
+//! pcf2rust font.pcf [--name FONT] [--range A-Z]... [--chars STRING] [--default CHAR]
+//!                   [--output font.rs] [--header font.h]
+//! ```
+//!
+//! Ranges are characters or `U+` code points, e.g. `--range U+20-U+7E`. Without ranges
+//! and characters all glyphs are included. The Rust source is written to standard output
+//! unless `--output` is given.
+
+mod header;
+mod rust;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use pcf_parser::{BdfFont, Font, PcfError, PcfFont, Subset};
+
+const USAGE: &str = "usage: pcf2rust FONT [--name NAME] [--range FROM-TO]... [--chars STRING] \
+                     [--default CHAR] [--output FILE.rs] [--header FILE.h]";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    font: PathBuf,
+    name: Option<String>,
+    ranges: Vec<(char, char)>,
+    chars: Option<String>,
+    default: Option<char>,
+    output: Option<PathBuf>,
+    header: Option<PathBuf>,
+}
+
+impl Options {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut font = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("missing value for {arg}"));
+            match arg.as_str() {
+                "--name" => options.name = Some(value()?),
+                "--range" => options.ranges.push(parse_range(&value()?)?),
+                "--chars" => options.chars = Some(value()?),
+                "--default" => options.default = Some(parse_char(&value()?)?),
+                "--output" | "-o" => options.output = Some(value()?.into()),
+                "--header" => options.header = Some(value()?.into()),
+                _ if arg.starts_with('-') => return Err(format!("unknown option {arg}")),
+                _ if font.is_none() => font = Some(PathBuf::from(arg)),
+                _ => return Err(format!("unexpected argument {arg}")),
+            }
+        }
+        options.font = font.ok_or("missing font path")?;
+
+        Ok(options)
+    }
+
+    fn contains(&self, c: char) -> bool {
+        if self.ranges.is_empty() && self.chars.is_none() {
+            return true;
+        }
+
+        self.ranges
+            .iter()
+            .any(|(from, to)| (*from..=*to).contains(&c))
+            || self.chars.as_ref().is_some_and(|chars| chars.contains(c))
+    }
+
+    /// Returns the name of the generated constant, derived from the file name by default.
+    fn name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+
+        let stem = self.font.file_name().and_then(|name| name.to_str());
+        let stem = stem
+            .and_then(|name| name.split('.').next())
+            .unwrap_or("font");
+        let name: String = stem
+            .chars()
+            .map(|c| match c.is_ascii_alphanumeric() {
+                true => c.to_ascii_uppercase(),
+                false => '_',
+            })
+            .collect();
+
+        match name.starts_with(|c: char| c.is_ascii_digit()) {
+            true => format!("FONT_{name}"),
+            false => name,
+        }
+    }
+}
+
+/// Parses a character or a `U+` code point.
+fn parse_char(value: &str) -> Result<char, String> {
+    let invalid = || format!("invalid character {value:?}");
+
+    match value.strip_prefix("U+").or(value.strip_prefix("u+")) {
+        Some(hex) => u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(invalid),
+        None => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c),
+                _ => Err(invalid()),
+            }
+        }
+    }
+}
+
+/// Parses a `FROM-TO` character range.
+fn parse_range(value: &str) -> Result<(char, char), String> {
+    // the separator is the first `-` after the first character, so `--~` is a range too
+    let first = value.chars().next().map_or(0, char::len_utf8);
+    let separator = value[first..]
+        .find('-')
+        .map(|i| first + i)
+        .ok_or(format!("invalid range {value:?}"))?;
+
+    Ok((
+        parse_char(&value[..separator])?,
+        parse_char(&value[separator + 1..])?,
+    ))
+}
+
+fn load_font<'a>(path: &Path, bytes: &'a [u8]) -> Result<Box<dyn Font + 'a>, PcfError> {
+    Ok(match path.extension().and_then(|e| e.to_str()) {
+        Some(e) if e.eq_ignore_ascii_case("bdf") => Box::new(BdfFont::parse(bytes)?),
+        _ => Box::new(PcfFont::parse(bytes)?),
+    })
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let path = &options.font;
+    let error = |message: String| format!("{}: {message}", path.display());
+
+    let mut bytes = fs::read(path).map_err(|e| error(e.to_string()))?;
+    let mut uncompressed = path.clone();
+    if path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("gz"))
+    {
+        bytes = pcf_parser::gunzip(&bytes).map_err(|e| error(e.to_string()))?;
+        uncompressed.set_extension("");
+    }
+    let font = load_font(&uncompressed, &bytes).map_err(|e| error(e.to_string()))?;
+    let subset =
+        Subset::new(font.as_ref(), |c| options.contains(c), options.default).map_err(error)?;
+
+    let name = options.name();
+    let source = path
+        .file_name()
+        .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+
+    let rust = rust::generate(&subset, &name, &source);
+    match &options.output {
+        Some(output) => {
+            fs::write(output, rust).map_err(|e| format!("{}: {e}", output.display()))?
+        }
+        None => print!("{rust}"),
+    }
+    if let Some(header) = &options.header {
+        let contents = header::generate(&subset, &name, &source);
+        fs::write(header, contents).map_err(|e| format!("{}: {e}", header.display()))?;
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = Options::parse(env::args().skip(1)).and_then(|options| run(&options));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("pcf2rust: {message}\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn it_parses_options() {
+        let options = parse(&[
+            "fonts/6x10.pcf.gz",
+            "--range",
+            "A-Z",
+            "--range",
+            "U+20-U+2F",
+            "--chars",
+            "°",
+            "--default",
+            "?",
+        ])
+        .unwrap();
+
+        assert_eq!(options.font, PathBuf::from("fonts/6x10.pcf.gz"));
+        assert_eq!(options.ranges, [('A', 'Z'), (' ', '/')]);
+        assert_eq!(options.default, Some('?'));
+        assert!(options.contains('Q') && options.contains('°') && !options.contains('a'));
+        assert_eq!(options.name(), "FONT_6X10");
+
+        assert_eq!(
+            parse(&["a.pcf", "--range", "--~"]).unwrap().ranges,
+            [('-', '~')]
+        );
+        assert!(parse(&["--name", "FONT"]).is_err());
+        assert!(parse(&["a.pcf", "--range", "AZ"]).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::Write;
+
+use pcf_parser::Subset;
+
+/// Returns Rust source code defining the constant `name` with the `eg_pcf::PcfFont` that
+/// `include_pcf!` generates for the same glyphs.
+pub fn generate(subset: &Subset, name: &str, source: &str) -> String {
+    let mut out = String::new();
+    let (start_indices, data) = subset.packed_bitmaps();
+    let rectangle = |(left, top, width, height): (i32, i32, u32, u32)| {
+        format!(
+            "::embedded_graphics::primitives::Rectangle::new(\
+             ::embedded_graphics::geometry::Point::new({left}, {top}), \
+             ::embedded_graphics::geometry::Size::new({width}, {height}))"
+        )
+    };
+    let option = |value: Option<u32>| match value {
+        Some(value) => format!("Some({value})"),
+        None => "None".to_string(),
+    };
+
+    writeln!(out, "// Generated by pcf2rust from {source}. Do not edit.").unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "pub const {name}: ::eg_pcf::PcfFont = ::eg_pcf::PcfFont {{"
+    )
+    .unwrap();
+    writeln!(out, "    bounding_box: {},", rectangle(subset.bounding_box)).unwrap();
+    writeln!(out, "    glyphs: &[").unwrap();
+    for (glyph, start_index) in subset.glyphs.iter().zip(start_indices) {
+        let bounding_box = rectangle((glyph.left, glyph.top, glyph.width, glyph.height));
+        writeln!(
+            out,
+            "        ::eg_pcf::PcfGlyph::new({:?}, {bounding_box}, {}, {start_index}),",
+            glyph.character, glyph.device_width
+        )
+        .unwrap();
+    }
+    writeln!(out, "    ],").unwrap();
+    writeln!(out, "    data: ::eg_pcf::GlyphData::Slice(&[").unwrap();
+    for row in data.chunks(16) {
+        let bytes: Vec<_> = row.iter().map(|byte| format!("0x{byte:02x}")).collect();
+        writeln!(out, "        {},", bytes.join(", ")).unwrap();
+    }
+    writeln!(out, "    ]),").unwrap();
+    writeln!(out, "    line_height: {},", subset.line_height).unwrap();
+    writeln!(
+        out,
+        "    replacement_character: {},",
+        subset.replacement_character
+    )
+    .unwrap();
+    match subset.design_size {
+        Some((point_size, x, y)) => writeln!(
+            out,
+            "    design_size: Some(::eg_pcf::size::DesignSize {{ point_size: {point_size}, \
+             resolution: ::embedded_graphics::geometry::Size::new({x}, {y}) }}),"
+        ),
+        None => writeln!(out, "    design_size: None,"),
+    }
+    .unwrap();
+    writeln!(
+        out,
+        "    script_offsets: ::eg_pcf::script::ScriptOffsets {{ superscript: {}, subscript: {} }},",
+        subset.superscript, subset.subscript
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    metrics: ::eg_pcf::metrics::FontMetrics {{ ascent: {}, descent: {}, \
+         cap_height: {}, x_height: {} }},",
+        subset.ascent,
+        subset.descent,
+        option(subset.cap_height),
+        option(subset.x_height)
+    )
+    .unwrap();
+    match subset.monospace_advance {
+        Some(advance) => writeln!(out, "    monospace_advance: Some({advance}),"),
+        None => writeln!(out, "    monospace_advance: None,"),
+    }
+    .unwrap();
+    writeln!(out, "    codepage: ::eg_pcf::codepage::Codepage::Unicode,").unwrap();
+    writeln!(out, "}};").unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pcf_parser::PcfFont;
+
+    #[test]
+    fn it_generates_a_font_constant() {
+        let font = include_bytes!("../../eg-pcf/examples/6x10.pcf");
+        let font = PcfFont::new(&font[..]);
+        let subset = Subset::new(&font, |c| c == 'A', None).unwrap();
+        let source = generate(&subset, "FONT", "6x10.pcf");
+
+        assert!(source.starts_with("// Generated by pcf2rust from 6x10.pcf."));
+        assert!(source.contains("pub const FONT: ::eg_pcf::PcfFont = ::eg_pcf::PcfFont {"));
+        assert!(source.contains("::eg_pcf::PcfGlyph::new('A', "));
+        assert!(source.contains("    line_height: 10,\n"));
+        assert!(source.ends_with("};\n"));
+    }
+
+    mod generated {
+        include!("../tests/fixtures/font_6x10.rs");
+    }
+
+    #[test]
+    fn it_generates_the_font_include_pcf_does() {
+        let font = include_bytes!("../../eg-pcf/examples/6x10.pcf");
+        let font = PcfFont::new(&font[..]);
+        let contains = |c| (' '..='~').contains(&c) || c == '\u{2588}';
+        let subset = Subset::new(&font, contains, None).unwrap();
+
+        // the checked in output is up to date and builds the same font as the macro
+        assert_eq!(
+            generate(&subset, "FONT_6X10", "6x10.pcf"),
+            include_str!("../tests/fixtures/font_6x10.rs")
+        );
+        assert_eq!(
+            generated::FONT_6X10,
+            eg_pcf::include_pcf!("../eg-pcf/examples/6x10.pcf", ' '..='~' | '\u{2588}')
+        );
+    }
+}
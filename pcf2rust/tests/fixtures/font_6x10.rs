@@ -0,0 +1,157 @@
+// Generated by pcf2rust from 6x10.pcf. Do not edit.
+
+pub const FONT_6X10: ::eg_pcf::PcfFont = ::eg_pcf::PcfFont {
+    bounding_box: ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)),
+    glyphs: &[
+        ::eg_pcf::PcfGlyph::new(' ', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 0),
+        ::eg_pcf::PcfGlyph::new('!', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 60),
+        ::eg_pcf::PcfGlyph::new('"', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 120),
+        ::eg_pcf::PcfGlyph::new('#', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 180),
+        ::eg_pcf::PcfGlyph::new('$', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 240),
+        ::eg_pcf::PcfGlyph::new('%', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 300),
+        ::eg_pcf::PcfGlyph::new('&', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 360),
+        ::eg_pcf::PcfGlyph::new('\'', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 420),
+        ::eg_pcf::PcfGlyph::new('(', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 480),
+        ::eg_pcf::PcfGlyph::new(')', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 540),
+        ::eg_pcf::PcfGlyph::new('*', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 600),
+        ::eg_pcf::PcfGlyph::new('+', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 660),
+        ::eg_pcf::PcfGlyph::new(',', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 720),
+        ::eg_pcf::PcfGlyph::new('-', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 780),
+        ::eg_pcf::PcfGlyph::new('.', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 840),
+        ::eg_pcf::PcfGlyph::new('/', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 900),
+        ::eg_pcf::PcfGlyph::new('0', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 960),
+        ::eg_pcf::PcfGlyph::new('1', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1020),
+        ::eg_pcf::PcfGlyph::new('2', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1080),
+        ::eg_pcf::PcfGlyph::new('3', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1140),
+        ::eg_pcf::PcfGlyph::new('4', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1200),
+        ::eg_pcf::PcfGlyph::new('5', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1260),
+        ::eg_pcf::PcfGlyph::new('6', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1320),
+        ::eg_pcf::PcfGlyph::new('7', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1380),
+        ::eg_pcf::PcfGlyph::new('8', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1440),
+        ::eg_pcf::PcfGlyph::new('9', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1500),
+        ::eg_pcf::PcfGlyph::new(':', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1560),
+        ::eg_pcf::PcfGlyph::new(';', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1620),
+        ::eg_pcf::PcfGlyph::new('<', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1680),
+        ::eg_pcf::PcfGlyph::new('=', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1740),
+        ::eg_pcf::PcfGlyph::new('>', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1800),
+        ::eg_pcf::PcfGlyph::new('?', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1860),
+        ::eg_pcf::PcfGlyph::new('@', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1920),
+        ::eg_pcf::PcfGlyph::new('A', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 1980),
+        ::eg_pcf::PcfGlyph::new('B', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2040),
+        ::eg_pcf::PcfGlyph::new('C', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2100),
+        ::eg_pcf::PcfGlyph::new('D', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2160),
+        ::eg_pcf::PcfGlyph::new('E', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2220),
+        ::eg_pcf::PcfGlyph::new('F', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2280),
+        ::eg_pcf::PcfGlyph::new('G', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2340),
+        ::eg_pcf::PcfGlyph::new('H', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2400),
+        ::eg_pcf::PcfGlyph::new('I', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2460),
+        ::eg_pcf::PcfGlyph::new('J', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2520),
+        ::eg_pcf::PcfGlyph::new('K', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2580),
+        ::eg_pcf::PcfGlyph::new('L', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2640),
+        ::eg_pcf::PcfGlyph::new('M', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2700),
+        ::eg_pcf::PcfGlyph::new('N', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2760),
+        ::eg_pcf::PcfGlyph::new('O', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2820),
+        ::eg_pcf::PcfGlyph::new('P', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2880),
+        ::eg_pcf::PcfGlyph::new('Q', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 2940),
+        ::eg_pcf::PcfGlyph::new('R', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3000),
+        ::eg_pcf::PcfGlyph::new('S', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3060),
+        ::eg_pcf::PcfGlyph::new('T', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3120),
+        ::eg_pcf::PcfGlyph::new('U', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3180),
+        ::eg_pcf::PcfGlyph::new('V', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3240),
+        ::eg_pcf::PcfGlyph::new('W', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3300),
+        ::eg_pcf::PcfGlyph::new('X', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3360),
+        ::eg_pcf::PcfGlyph::new('Y', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3420),
+        ::eg_pcf::PcfGlyph::new('Z', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3480),
+        ::eg_pcf::PcfGlyph::new('[', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3540),
+        ::eg_pcf::PcfGlyph::new('\\', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3600),
+        ::eg_pcf::PcfGlyph::new(']', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3660),
+        ::eg_pcf::PcfGlyph::new('^', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3720),
+        ::eg_pcf::PcfGlyph::new('_', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3780),
+        ::eg_pcf::PcfGlyph::new('`', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3840),
+        ::eg_pcf::PcfGlyph::new('a', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3900),
+        ::eg_pcf::PcfGlyph::new('b', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 3960),
+        ::eg_pcf::PcfGlyph::new('c', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4020),
+        ::eg_pcf::PcfGlyph::new('d', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4080),
+        ::eg_pcf::PcfGlyph::new('e', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4140),
+        ::eg_pcf::PcfGlyph::new('f', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4200),
+        ::eg_pcf::PcfGlyph::new('g', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4260),
+        ::eg_pcf::PcfGlyph::new('h', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4320),
+        ::eg_pcf::PcfGlyph::new('i', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4380),
+        ::eg_pcf::PcfGlyph::new('j', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4440),
+        ::eg_pcf::PcfGlyph::new('k', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4500),
+        ::eg_pcf::PcfGlyph::new('l', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4560),
+        ::eg_pcf::PcfGlyph::new('m', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4620),
+        ::eg_pcf::PcfGlyph::new('n', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4680),
+        ::eg_pcf::PcfGlyph::new('o', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4740),
+        ::eg_pcf::PcfGlyph::new('p', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4800),
+        ::eg_pcf::PcfGlyph::new('q', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4860),
+        ::eg_pcf::PcfGlyph::new('r', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4920),
+        ::eg_pcf::PcfGlyph::new('s', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 4980),
+        ::eg_pcf::PcfGlyph::new('t', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5040),
+        ::eg_pcf::PcfGlyph::new('u', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5100),
+        ::eg_pcf::PcfGlyph::new('v', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5160),
+        ::eg_pcf::PcfGlyph::new('w', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5220),
+        ::eg_pcf::PcfGlyph::new('x', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5280),
+        ::eg_pcf::PcfGlyph::new('y', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5340),
+        ::eg_pcf::PcfGlyph::new('z', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5400),
+        ::eg_pcf::PcfGlyph::new('{', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5460),
+        ::eg_pcf::PcfGlyph::new('|', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5520),
+        ::eg_pcf::PcfGlyph::new('}', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5580),
+        ::eg_pcf::PcfGlyph::new('~', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5640),
+        ::eg_pcf::PcfGlyph::new('█', ::embedded_graphics::primitives::Rectangle::new(::embedded_graphics::geometry::Point::new(0, -9), ::embedded_graphics::geometry::Size::new(6, 10)), 6, 5700),
+    ],
+    data: ::eg_pcf::GlyphData::Slice(&[
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x20, 0x82, 0x08, 0x00, 0x80, 0x00, 0x01,
+        0x45, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x14, 0x53, 0xe5, 0x3e, 0x51, 0x40, 0x00, 0x00, 0x87,
+        0x28, 0x70, 0xa7, 0x08, 0x00, 0x00, 0x12, 0xa9, 0x42, 0x14, 0xaa, 0x40, 0x00, 0x01, 0x0a, 0x28,
+        0x42, 0xa9, 0x1a, 0x00, 0x00, 0x08, 0x20, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x42, 0x10, 0x41,
+        0x02, 0x04, 0x00, 0x00, 0x10, 0x20, 0x41, 0x04, 0x21, 0x00, 0x00, 0x00, 0x08, 0x94, 0xf9, 0x48,
+        0x80, 0x00, 0x00, 0x00, 0x20, 0x8f, 0x88, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x08,
+        0x40, 0x00, 0x00, 0x00, 0x0f, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x1c, 0x20,
+        0x00, 0x02, 0x08, 0x42, 0x10, 0x82, 0x00, 0x00, 0x00, 0x85, 0x22, 0x8a, 0x25, 0x08, 0x00, 0x00,
+        0x08, 0x62, 0x82, 0x08, 0x23, 0xe0, 0x00, 0x01, 0xc8, 0x82, 0x31, 0x08, 0x3e, 0x00, 0x00, 0x3e,
+        0x08, 0x43, 0x02, 0x89, 0xc0, 0x00, 0x00, 0x43, 0x14, 0x93, 0xe1, 0x04, 0x00, 0x00, 0x3e, 0x82,
+        0xcc, 0x82, 0x89, 0xc0, 0x00, 0x00, 0xc4, 0x20, 0xb3, 0x28, 0x9c, 0x00, 0x00, 0x3e, 0x08, 0x41,
+        0x08, 0x41, 0x00, 0x00, 0x01, 0xc8, 0xa2, 0x72, 0x28, 0x9c, 0x00, 0x00, 0x1c, 0x8a, 0x66, 0x82,
+        0x11, 0x80, 0x00, 0x00, 0x02, 0x1c, 0x20, 0x02, 0x1c, 0x20, 0x00, 0x00, 0x21, 0xc2, 0x00, 0x30,
+        0x84, 0x00, 0x00, 0x21, 0x08, 0x40, 0x81, 0x02, 0x00, 0x00, 0x00, 0x03, 0xe0, 0x3e, 0x00, 0x00,
+        0x00, 0x01, 0x02, 0x04, 0x08, 0x42, 0x10, 0x00, 0x00, 0x1c, 0x88, 0x42, 0x08, 0x00, 0x80, 0x00,
+        0x01, 0xc8, 0xa6, 0xaa, 0xc8, 0x1c, 0x00, 0x00, 0x08, 0x52, 0x28, 0xbe, 0x8a, 0x20, 0x00, 0x03,
+        0xc4, 0x92, 0x71, 0x24, 0xbc, 0x00, 0x00, 0x1c, 0x8a, 0x08, 0x20, 0x89, 0xc0, 0x00, 0x03, 0xc4,
+        0x92, 0x49, 0x24, 0xbc, 0x00, 0x00, 0x3e, 0x82, 0x0f, 0x20, 0x83, 0xe0, 0x00, 0x03, 0xe8, 0x20,
+        0xf2, 0x08, 0x20, 0x00, 0x00, 0x1c, 0x8a, 0x08, 0x26, 0x89, 0xc0, 0x00, 0x02, 0x28, 0xa2, 0xfa,
+        0x28, 0xa2, 0x00, 0x00, 0x1c, 0x20, 0x82, 0x08, 0x21, 0xc0, 0x00, 0x00, 0xe1, 0x04, 0x10, 0x49,
+        0x18, 0x00, 0x00, 0x22, 0x92, 0x8c, 0x28, 0x92, 0x20, 0x00, 0x02, 0x08, 0x20, 0x82, 0x08, 0x3e,
+        0x00, 0x00, 0x22, 0x8b, 0x6a, 0xa2, 0x8a, 0x20, 0x00, 0x02, 0x28, 0xb2, 0xaa, 0x68, 0xa2, 0x00,
+        0x00, 0x1c, 0x8a, 0x28, 0xa2, 0x89, 0xc0, 0x00, 0x03, 0xc8, 0xa2, 0xf2, 0x08, 0x20, 0x00, 0x00,
+        0x1c, 0x8a, 0x28, 0xa2, 0xa9, 0xc0, 0x80, 0x03, 0xc8, 0xa2, 0xf2, 0x89, 0x22, 0x00, 0x00, 0x1c,
+        0x8a, 0x07, 0x02, 0x89, 0xc0, 0x00, 0x03, 0xe2, 0x08, 0x20, 0x82, 0x08, 0x00, 0x00, 0x22, 0x8a,
+        0x28, 0xa2, 0x89, 0xc0, 0x00, 0x02, 0x28, 0xa2, 0x51, 0x45, 0x08, 0x00, 0x00, 0x22, 0x8a, 0x2a,
+        0xaa, 0xda, 0x20, 0x00, 0x02, 0x28, 0x94, 0x21, 0x48, 0xa2, 0x00, 0x00, 0x22, 0x89, 0x42, 0x08,
+        0x20, 0x80, 0x00, 0x03, 0xe0, 0x84, 0x21, 0x08, 0x3e, 0x00, 0x00, 0x1c, 0x41, 0x04, 0x10, 0x41,
+        0xc0, 0x00, 0x02, 0x08, 0x10, 0x20, 0x40, 0x82, 0x00, 0x00, 0x1c, 0x10, 0x41, 0x04, 0x11, 0xc0,
+        0x00, 0x00, 0x85, 0x22, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0f, 0x80,
+        0x20, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0xc0, 0x9e, 0x89, 0xe0, 0x00, 0x02,
+        0x08, 0x2c, 0xca, 0x2c, 0xac, 0x00, 0x00, 0x00, 0x01, 0xc8, 0xa0, 0x89, 0xc0, 0x00, 0x00, 0x20,
+        0x9a, 0x9a, 0x29, 0x9a, 0x00, 0x00, 0x00, 0x01, 0xc8, 0xbe, 0x81, 0xc0, 0x00, 0x00, 0xc4, 0x90,
+        0xf1, 0x04, 0x10, 0x00, 0x00, 0x00, 0x01, 0xe8, 0xa2, 0x78, 0x28, 0x9c, 0x02, 0x08, 0x2c, 0xca,
+        0x28, 0xa2, 0x00, 0x00, 0x08, 0x01, 0x82, 0x08, 0x21, 0xc0, 0x00, 0x00, 0x20, 0x06, 0x08, 0x20,
+        0x92, 0x48, 0xc0, 0x20, 0x82, 0x29, 0x38, 0x92, 0x20, 0x00, 0x01, 0x82, 0x08, 0x20, 0x82, 0x1c,
+        0x00, 0x00, 0x00, 0x03, 0x4a, 0xaa, 0xaa, 0x20, 0x00, 0x00, 0x00, 0x2c, 0xca, 0x28, 0xa2, 0x00,
+        0x00, 0x00, 0x01, 0xc8, 0xa2, 0x89, 0xc0, 0x00, 0x00, 0x00, 0x2c, 0xca, 0x2c, 0xac, 0x82, 0x00,
+        0x00, 0x01, 0xa9, 0xa2, 0x99, 0xa0, 0x82, 0x00, 0x00, 0x2c, 0xca, 0x08, 0x20, 0x00, 0x00, 0x00,
+        0x01, 0xc8, 0x1c, 0x0b, 0xc0, 0x00, 0x01, 0x04, 0x3c, 0x41, 0x04, 0x8c, 0x00, 0x00, 0x00, 0x02,
+        0x28, 0xa2, 0x99, 0xa0, 0x00, 0x00, 0x00, 0x22, 0x89, 0x45, 0x08, 0x00, 0x00, 0x00, 0x02, 0x28,
+        0xaa, 0xa9, 0x40, 0x00, 0x00, 0x00, 0x22, 0x50, 0x85, 0x22, 0x00, 0x00, 0x00, 0x02, 0x28, 0xa6,
+        0x68, 0x28, 0x9c, 0x00, 0x00, 0x3e, 0x10, 0x84, 0x3e, 0x00, 0x00, 0x06, 0x20, 0x46, 0x04, 0x20,
+        0x60, 0x00, 0x00, 0x82, 0x08, 0x20, 0x82, 0x08, 0x00, 0x00, 0x18, 0x10, 0x81, 0x88, 0x11, 0x80,
+        0x00, 0x01, 0x2a, 0xa4, 0x00, 0x00, 0x00, 0x00, 0x0f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    ]),
+    line_height: 10,
+    replacement_character: 0,
+    design_size: Some(::eg_pcf::size::DesignSize { point_size: 100, resolution: ::embedded_graphics::geometry::Size::new(75, 75) }),
+    script_offsets: ::eg_pcf::script::ScriptOffsets { superscript: 3, subscript: 2 },
+    metrics: ::eg_pcf::metrics::FontMetrics { ascent: 8, descent: 2, cap_height: Some(7), x_height: Some(5) },
+    monospace_advance: Some(6),
+    codepage: ::eg_pcf::codepage::Codepage::Unicode,
+};